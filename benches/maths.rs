@@ -0,0 +1,82 @@
+//! Compares the SIMD backend picked for `f32` against the scalar fallback. Build with
+//! `RUSTFLAGS="-C target-cpu=native"` to benchmark AVX where the processor has it.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use velocity::core::maths::{
+    simd::{fallback, Kernels},
+    Angle, Mat4, Normed, Quat, Scale3, Unit, Vec3,
+};
+
+fn transformation() -> Mat4 {
+    let axis = Vec3::new(1.0, 2.0, -3.0);
+    let rotation =
+        Quat::from_axis_angle(&Unit::from_normalised(axis / axis.norm()), Angle::degrees(37.0));
+
+    Mat4::translation(&Vec3::new(-1.0, 4.0, 0.5))
+        * Mat4::rotation(&rotation)
+        * Mat4::scale(&Scale3::new(2.0, 0.5, 3.0))
+}
+
+fn rotation() -> Quat {
+    let axis = Vec3::new(-2.0, 1.0, 0.5);
+
+    Quat::from_axis_angle(&Unit::from_normalised(axis / axis.norm()), Angle::degrees(110.0))
+}
+
+fn mat4_mul(c: &mut Criterion) {
+    let (lhs, rhs) = (transformation(), transformation().inverse());
+    let (lhs_data, rhs_data) = (<[f32; 16]>::from(lhs), <[f32; 16]>::from(rhs));
+
+    let mut group = c.benchmark_group("mat4_mul");
+    group.bench_function("simd", |b| {
+        b.iter(|| f32::mat4_mul(black_box(&lhs_data), black_box(&rhs_data)))
+    });
+    group.bench_function("scalar", |b| {
+        b.iter(|| fallback::mat4_mul(black_box(&lhs_data), black_box(&rhs_data)))
+    });
+    group.finish();
+}
+
+fn mat4_inverse(c: &mut Criterion) {
+    let matrix = transformation();
+    let data = <[f32; 16]>::from(matrix);
+
+    let mut group = c.benchmark_group("mat4_inverse");
+    group.bench_function("simd", |b| b.iter(|| f32::mat4_inverse(black_box(&data))));
+    group.bench_function("scalar", |b| {
+        b.iter(|| fallback::mat4_inverse(black_box(&data)))
+    });
+    group.finish();
+}
+
+fn mat4_transform(c: &mut Criterion) {
+    let matrix = transformation();
+    let data = <[f32; 16]>::from(matrix);
+    let vector_data = [1.5, -2.0, 0.25, 1.0];
+
+    let mut group = c.benchmark_group("mat4_transform");
+    group.bench_function("simd", |b| {
+        b.iter(|| f32::mat4_transform(black_box(&data), black_box(&vector_data)))
+    });
+    group.bench_function("scalar", |b| {
+        b.iter(|| fallback::mat4_transform(black_box(&data), black_box(&vector_data)))
+    });
+    group.finish();
+}
+
+fn quat_mul(c: &mut Criterion) {
+    let (lhs, rhs) = (rotation(), rotation().inverse());
+    let (lhs_data, rhs_data) = (lhs.to_array(), rhs.to_array());
+
+    let mut group = c.benchmark_group("quat_mul");
+    group.bench_function("simd", |b| {
+        b.iter(|| f32::quat_mul(black_box(&lhs_data), black_box(&rhs_data)))
+    });
+    group.bench_function("scalar", |b| {
+        b.iter(|| fallback::quat_mul(black_box(&lhs_data), black_box(&rhs_data)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, mat4_mul, mat4_inverse, mat4_transform, quat_mul);
+criterion_main!(benches);
@@ -1,57 +1,46 @@
 use velocity::{
     app::{App, LoopState},
-    core::maths::{Angle, Vec3},
+    core::maths::Vec3,
     engine::{Engine, EngineSettings},
-    graphics::{Buffer, Camera, Pipeline, Renderer, Vertex},
+    graphics::{Mesh, Pipeline, Renderer, Vertex},
     keyboard::Keyboard,
     mouse::Mouse,
+    window::Windows,
     Bump,
 };
 
 static VERTICES: &[Vertex] = &[
     Vertex {
         position: Vec3::new(0.5, 0.75, 0.0),
-        colour:   Vec3::unit_x(),
+        colour:   Vec3::new(1.0, 0.0, 0.0),
     },
     Vertex {
         position: Vec3::new(-0.75, -0.75, 0.0),
-        colour:   Vec3::unit_y(),
+        colour:   Vec3::new(0.0, 1.0, 0.0),
     },
     Vertex {
         position: Vec3::new(0.75, -0.75, 0.0),
-        colour:   Vec3::unit_z(),
-    },
-    Vertex {
-        position: Vec3::new(0.5, 0.75, 0.0),
-        colour:   Vec3::unit_x(),
+        colour:   Vec3::new(0.0, 0.0, 1.0),
     },
     Vertex {
         position: Vec3::new(-0.75, 0.75, 0.0),
-        colour:   Vec3::unit_y(),
-    },
-    Vertex {
-        position: Vec3::new(-0.75, -0.75, 0.0),
-        colour:   Vec3::unit_z(),
+        colour:   Vec3::new(0.0, 1.0, 0.0),
     },
 ];
 
+static INDICES: &[u16] = &[0, 1, 2, 0, 3, 1];
+
 struct Demo {
     pipeline: Pipeline,
-    buffer:   Buffer,
-    camera:   Camera,
+    mesh:     Mesh,
 }
 
 impl Demo {
     pub fn new(renderer: &mut Renderer) -> Self {
-        let pipeline = Pipeline::new(&renderer);
-        let buffer = renderer.create_buffer(VERTICES);
-        let camera = Camera::perspective(0.0, Angle::degrees(75.0), 0.1, 1000.0);
+        let pipeline = Pipeline::new(renderer);
+        let mesh = Mesh::new(renderer, VERTICES, INDICES);
 
-        Self {
-            pipeline,
-            buffer,
-            camera,
-        }
+        Self { pipeline, mesh }
     }
 }
 
@@ -60,22 +49,31 @@ impl App for Demo {
 
     fn update_and_render(
         &mut self,
+        windows: &mut Windows,
         _keyboard: &Keyboard,
         _mouse: &Mouse,
-        frame_memory: &mut Bump,
+        _frame_memory: &mut Bump,
         renderer: &mut Renderer,
     ) -> LoopState {
-        if let Err(err) = renderer.render(&self.pipeline, &self.buffer) {
-            LoopState::Exit
-        } else {
-            LoopState::Continue
-        }
+        let mut frame = match renderer.begin_frame(windows.primary()) {
+            Ok(frame) => frame,
+            Err(frame_error) if frame_error.is_fatal() => {
+                eprintln!("{}", frame_error);
+                return LoopState::Exit;
+            },
+            Err(_) => return LoopState::Continue,
+        };
+
+        frame.render_mesh(&self.pipeline, &self.mesh);
+        renderer.finish_frame(frame);
+
+        LoopState::Continue
     }
 }
 
 #[tokio::main]
 async fn main() {
-    let mut engine = Engine::new("demo", EngineSettings::default_game())
+    let engine = Engine::new("demo", EngineSettings::default_game())
         .await
         .expect("Unable to instatiate engine instance");
 
@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use bumpalo::Bump;
+use winit::{event::WindowEvent, window::WindowId};
 
-use crate::{graphics::Renderer, keyboard::Keyboard, mouse::Mouse};
+use crate::{graphics::Renderer, keyboard::Keyboard, mouse::Mouse, window::Windows};
 
 pub enum LoopState {
     Continue,
@@ -12,13 +15,66 @@ pub trait App<UserEvent = ()> {
     const EXIT_ON_CLOSE: bool;
     const INITIAL_FRAME_MEMORY: usize = 1024;
 
+    /// Called `EngineSettings::fixed_update_rate` times a second of unpaused time, before
+    /// `update_and_render`. The clock is paused while every window is minimised or the app is
+    /// suspended.
+    fn fixed_update(&mut self, step: Duration) {}
+
+    /// Updates the app and renders into its windows, beginning a frame for each window it draws.
     fn update_and_render(
         &mut self,
+        windows: &mut Windows<UserEvent>,
         keyboard: &Keyboard,
         mouse: &Mouse,
         frame_memory: &mut Bump,
         renderer: &mut Renderer,
     ) -> LoopState;
 
+    /// Called with every event sent to one of the windows, after the engine has handled it.
+    fn window_event(
+        &mut self,
+        window: WindowId,
+        event: &WindowEvent,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) {
+    }
+
+    /// Called with every event sent through an `EventProxy`, before the next update.
+    fn user_event(
+        &mut self,
+        event: UserEvent,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) {
+    }
+
+    fn focus_changed(&mut self, window: WindowId, focused: bool) {}
+
+    fn minimised(&mut self, window: WindowId) {}
+
+    fn restored(&mut self, window: WindowId) {}
+
+    /// Called when the platform suspends the app, e.g. when it's sent to the background on mobile.
+    fn suspended(&mut self) {}
+
+    fn resumed(&mut self) {}
+
+    /// Called when the user asks to close a window and `EXIT_ON_CLOSE` is false. Returning true
+    /// closes it, by default windows stay open, e.g. so the app can ask about unsaved changes
+    /// first. Closing the primary window exits the app.
+    fn close_requested(
+        &mut self,
+        window: WindowId,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) -> bool {
+        false
+    }
+
+    /// Called once a window other than the primary one has been closed by the user, the engine
+    /// has already removed it from the renderer.
+    fn window_closed(&mut self, window: WindowId, renderer: &mut Renderer) {}
+
     fn clean_up(&mut self) {}
 }
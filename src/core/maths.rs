@@ -1,42 +1,65 @@
 mod angle;
+mod circle;
+mod line2;
+mod line3;
+mod mat2;
+mod mat3;
 mod mat4;
+mod plane;
 mod point2;
 mod point3;
 mod quat;
+mod radial_vec2;
+mod radial_vec3;
+mod scalar;
 mod scale2;
 mod scale3;
+mod segment2;
+mod segment3;
+#[cfg(feature = "serde")]
+mod serialization;
+mod sphere;
+mod spherical_vec3;
+mod spline;
+mod triangle;
 mod unit;
 mod vec2;
 mod vec3;
-
-// TODO:
-//
-// mod line2;
-// mod line3;
-// mod segment2;
-// mod segment3;
-// mod radial_vec2;
-// mod radial_vec3;
-// mod spherical_vec3;
-//
-// Quat from_yaw_pitch_roll
-// impl PartialEq using fuzzy
+mod vec4;
 
 pub mod fuzzy;
+pub mod simd;
 
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 pub use crate::core::maths::{
     angle::*,
+    circle::*,
+    fuzzy::ApproxEq,
+    line2::*,
+    line3::*,
+    mat2::*,
+    mat3::*,
     mat4::*,
+    plane::*,
     point2::*,
     point3::*,
     quat::*,
+    radial_vec2::*,
+    radial_vec3::*,
+    scalar::*,
     scale2::*,
     scale3::*,
+    segment2::*,
+    segment3::*,
+    sphere::*,
+    spherical_vec3::*,
+    spline::*,
+    triangle::*,
     unit::*,
     vec2::*,
     vec3::*,
+    vec4::*,
 };
 
 // macro_rules! permute {
@@ -47,10 +70,13 @@ pub use crate::core::maths::{
 
 macro_rules! scalar {
     (standard $ref:ty => $struct:ty { $( $member:ident )* } ($trait:ident $fn:ident $op:tt)) => {
-        impl $trait<f32> for $ref {
+        impl<S> $trait<S> for $ref
+        where
+            S: Scalar,
+        {
             type Output = $struct;
 
-            fn $fn(self, rhs: f32) -> Self::Output {
+            fn $fn(self, rhs: S) -> Self::Output {
                 Self::Output {
                     $(
                         $member: self.$member $op rhs,
@@ -58,8 +84,13 @@ macro_rules! scalar {
                 }
             }
         }
+    };
 
-        impl $trait<$ref> for f32 {
+    // NOTE:
+    // A generic scalar on the left would be implementing a foreign trait for a foreign type, so
+    // these are implemented for each scalar type instead.
+    (left $scalar:ty, $ref:ty => $struct:ty { $( $member:ident )* } ($trait:ident $fn:ident $op:tt)) => {
+        impl $trait<$ref> for $scalar {
             type Output = $struct;
 
             fn $fn(self, rhs: $ref) -> Self::Output {
@@ -73,8 +104,11 @@ macro_rules! scalar {
     };
 
     (assign $ref:ty => $struct:ty { $( $member:ident )* } ($trait:ident $fn:ident $op:tt)) => {
-        impl $trait<f32> for $ref {
-            fn $fn(&mut self, rhs: f32) {
+        impl<S> $trait<S> for $ref
+        where
+            S: Scalar,
+        {
+            fn $fn(&mut self, rhs: S) {
                 $(
                     self.$member $op rhs;
                 )*
@@ -82,43 +116,62 @@ macro_rules! scalar {
         }
     };
 
+    (left $scalar:ty => $struct:ident { $( $member:ident )* }) => {
+        scalar!(left $scalar,      $struct<$scalar> => $struct<$scalar> { $( $member )* } (Mul mul *));
+        scalar!(left $scalar, &    $struct<$scalar> => $struct<$scalar> { $( $member )* } (Mul mul *));
+        scalar!(left $scalar, &mut $struct<$scalar> => $struct<$scalar> { $( $member )* } (Mul mul *));
+        scalar!(left $scalar,      $struct<$scalar> => $struct<$scalar> { $( $member )* } (Div div /));
+        scalar!(left $scalar, &    $struct<$scalar> => $struct<$scalar> { $( $member )* } (Div div /));
+        scalar!(left $scalar, &mut $struct<$scalar> => $struct<$scalar> { $( $member )* } (Div div /));
+
+        scalar!(left $scalar,      Unit<$struct<$scalar>> => $struct<$scalar> { $( $member )* } (Mul mul *));
+        scalar!(left $scalar, &    Unit<$struct<$scalar>> => $struct<$scalar> { $( $member )* } (Mul mul *));
+        scalar!(left $scalar, &mut Unit<$struct<$scalar>> => $struct<$scalar> { $( $member )* } (Mul mul *));
+        scalar!(left $scalar,      Unit<$struct<$scalar>> => $struct<$scalar> { $( $member )* } (Div div /));
+        scalar!(left $scalar, &    Unit<$struct<$scalar>> => $struct<$scalar> { $( $member )* } (Div div /));
+        scalar!(left $scalar, &mut Unit<$struct<$scalar>> => $struct<$scalar> { $( $member )* } (Div div /));
+    };
 
-
-    ($($struct:ty, $unit:ty => { $( $member:ident )* },)*) => {
+    ($($struct:ident => { $( $member:ident )* },)*) => {
         $(
-            scalar!(standard      $struct => $struct { $( $member )* } (Mul mul *));
-            scalar!(standard &    $struct => $struct { $( $member )* } (Mul mul *));
-            scalar!(standard &mut $struct => $struct { $( $member )* } (Mul mul *));
-            scalar!(standard      $struct => $struct { $( $member )* } (Div div /));
-            scalar!(standard &    $struct => $struct { $( $member )* } (Div div /));
-            scalar!(standard &mut $struct => $struct { $( $member )* } (Div div /));
-
-            scalar!(standard      $unit => $struct { $( $member )* } (Mul mul *));
-            scalar!(standard &    $unit => $struct { $( $member )* } (Mul mul *));
-            scalar!(standard &mut $unit => $struct { $( $member )* } (Mul mul *));
-            scalar!(standard      $unit => $struct { $( $member )* } (Div div /));
-            scalar!(standard &    $unit => $struct { $( $member )* } (Div div /));
-            scalar!(standard &mut $unit => $struct { $( $member )* } (Div div /));
-
-            scalar!(assign      $struct => $struct { $( $member )* } (MulAssign mul_assign *=));
-            scalar!(assign &mut $struct => $struct { $( $member )* } (MulAssign mul_assign *=));
-            scalar!(assign      $struct => $struct { $( $member )* } (DivAssign div_assign /=));
-            scalar!(assign &mut $struct => $struct { $( $member )* } (DivAssign div_assign /=));
+            scalar!(standard      $struct<S> => $struct<S> { $( $member )* } (Mul mul *));
+            scalar!(standard &    $struct<S> => $struct<S> { $( $member )* } (Mul mul *));
+            scalar!(standard &mut $struct<S> => $struct<S> { $( $member )* } (Mul mul *));
+            scalar!(standard      $struct<S> => $struct<S> { $( $member )* } (Div div /));
+            scalar!(standard &    $struct<S> => $struct<S> { $( $member )* } (Div div /));
+            scalar!(standard &mut $struct<S> => $struct<S> { $( $member )* } (Div div /));
+
+            scalar!(standard      Unit<$struct<S>> => $struct<S> { $( $member )* } (Mul mul *));
+            scalar!(standard &    Unit<$struct<S>> => $struct<S> { $( $member )* } (Mul mul *));
+            scalar!(standard &mut Unit<$struct<S>> => $struct<S> { $( $member )* } (Mul mul *));
+            scalar!(standard      Unit<$struct<S>> => $struct<S> { $( $member )* } (Div div /));
+            scalar!(standard &    Unit<$struct<S>> => $struct<S> { $( $member )* } (Div div /));
+            scalar!(standard &mut Unit<$struct<S>> => $struct<S> { $( $member )* } (Div div /));
+
+            scalar!(left f32 => $struct { $( $member )* });
+            scalar!(left f64 => $struct { $( $member )* });
+
+            scalar!(assign      $struct<S> => $struct<S> { $( $member )* } (MulAssign mul_assign *=));
+            scalar!(assign &mut $struct<S> => $struct<S> { $( $member )* } (MulAssign mul_assign *=));
+            scalar!(assign      $struct<S> => $struct<S> { $( $member )* } (DivAssign div_assign /=));
+            scalar!(assign &mut $struct<S> => $struct<S> { $( $member )* } (DivAssign div_assign /=));
         )*
     };
-
-
 }
 
 scalar!(
-    Vec2, Unit<Vec2> => { x y },
-    Vec3, Unit<Vec3> => { x y z },
-    Quat, Unit<Quat> => { v w },
+    TVec2 => { x y },
+    TVec3 => { x y z },
+    TVec4 => { x y z w },
+    TQuat => { v w },
 );
 
 macro_rules! vector {
     (standard $lhs:ty, $rhs:ty => $struct:ty { $( $member:ident )* } ($trait:ident $fn:ident $op:tt)) => {
-        impl $trait<$rhs> for $lhs {
+        impl<S> $trait<$rhs> for $lhs
+        where
+            S: Scalar,
+        {
             type Output = $struct;
 
             fn $fn(self, rhs: $rhs) -> Self::Output {
@@ -132,7 +185,10 @@ macro_rules! vector {
     };
 
     (assign $lhs:ty, $rhs:ty => $struct:ty { $( $member:ident )* } ($trait:ident $fn:ident $op:tt)) => {
-        impl $trait<$rhs> for $lhs {
+        impl<S> $trait<$rhs> for $lhs
+        where
+            S: Scalar,
+        {
             fn $fn(&mut self, rhs: $rhs) {
                 $(
                     self.$member $op rhs.$member;
@@ -179,14 +235,18 @@ macro_rules! vector {
 }
 
 vector!(
-    Vec2, Unit<Vec2> => { x y },
-    Vec3, Unit<Vec3> => { x y z },
-    Quat, Unit<Quat> => { v w },
+    TVec2<S>, Unit<TVec2<S>> => { x y },
+    TVec3<S>, Unit<TVec3<S>> => { x y z },
+    TVec4<S>, Unit<TVec4<S>> => { x y z w },
+    TQuat<S>, Unit<TQuat<S>> => { v w },
 );
 
 macro_rules! negate {
     ($ref:ty => $struct:ty { $( $member:ident )* }) => {
-        impl Neg for $ref {
+        impl<S> Neg for $ref
+        where
+            S: Scalar,
+        {
             type Output = $struct;
 
             fn neg(self) -> Self::Output {
@@ -209,68 +269,106 @@ macro_rules! negate {
 }
 
 negate!(
-    Vec2 { x y },
-    Vec3 { x y z },
+    TVec2<S> { x y },
+    TVec3<S> { x y z },
+    TVec4<S> { x y z w },
+    TQuat<S> { v w },
 );
 
 macro_rules! equal {
-    ($lhs:ty, $rhs:ty => { $( $member:ident => $eq:expr $( ; $ref:tt )?,)* }) => {
-        impl PartialEq<$rhs> for $lhs {
-            fn eq(&self, rhs: &$rhs) -> bool {
-                let mut equal = false;
+    ($lhs:ty, $rhs:ty => { $( $member:tt )* }) => {
+        impl<S> ApproxEq<$rhs> for $lhs
+        where
+            S: Scalar,
+        {
+            type Epsilon = S;
+
+            fn default_epsilon() -> S {
+                S::default_epsilon()
+            }
 
-                $(
-                    equal = equal && $eq($($ref)?self.$member, $($ref)?rhs.$member);
-                )*
+            fn default_max_relative() -> S {
+                S::default_max_relative()
+            }
+
+            fn abs_diff_eq(&self, rhs: &$rhs, epsilon: S) -> bool {
+                true $( && self.$member.abs_diff_eq(&rhs.$member, epsilon) )*
+            }
+
+            fn relative_eq(&self, rhs: &$rhs, epsilon: S, max_relative: S) -> bool {
+                true $( && self.$member.relative_eq(&rhs.$member, epsilon, max_relative) )*
+            }
 
-                equal
+            fn ulps_eq(&self, rhs: &$rhs, epsilon: S, max_ulps: u32) -> bool {
+                true $( && self.$member.ulps_eq(&rhs.$member, epsilon, max_ulps) )*
+            }
+        }
+
+        impl<S> PartialEq<$rhs> for $lhs
+        where
+            S: Scalar,
+        {
+            fn eq(&self, rhs: &$rhs) -> bool {
+                self.approx_eq(rhs)
             }
         }
     };
 
-    ($($struct:ty => { $( $member:ident => $eq:expr $( ; $ref:tt )?,)* },)*) => {
+    ($($struct:ty => { $( $member:tt )* },)*) => {
         $(
-            equal!($struct, $struct => { $( $member => $eq $(; $ref)?, )* });
+            equal!($struct, $struct => { $( $member )* });
         )*
     };
 
-    ($($struct:ty, $unit:ty => { $( $member:ident => $eq:expr $( ; $ref:tt )?,)* },)*) => {
+    ($($struct:ty, $unit:ty => { $( $member:tt )* },)*) => {
         $(
-            equal!($struct, $struct => { $( $member => $eq $(; $ref)?, )* });
-            equal!($struct, $unit   => { $( $member => $eq $(; $ref)?, )* });
-            equal!($unit  , $struct => { $( $member => $eq $(; $ref)?, )* });
-            equal!($unit  , $unit   => { $( $member => $eq $(; $ref)?, )* });
+            equal!($struct, $struct => { $( $member )* });
+            equal!($struct, $unit   => { $( $member )* });
+            equal!($unit  , $struct => { $( $member )* });
+            equal!($unit  , $unit   => { $( $member )* });
         )*
     };
 }
 
 equal!(
-    Vec2, Unit<Vec2> => {
-        x => fuzzy::eq,
-        y => fuzzy::eq,
-    },
-
-    Vec3, Unit<Vec3> => {
-        x => fuzzy::eq,
-        y => fuzzy::eq,
-        z => fuzzy::eq,
-    },
-
-    Quat, Unit<Quat> => {
-        v => Vec3 ::eq; &,
-        w => fuzzy::eq,
-    },
+    TVec2<S>, Unit<TVec2<S>> => { x y },
+    TVec3<S>, Unit<TVec3<S>> => { x y z },
+    TVec4<S>, Unit<TVec4<S>> => { x y z w },
+    TQuat<S>, Unit<TQuat<S>> => { v w },
 );
 
 equal!(
-    Point2 => {
-        x => fuzzy::eq,
-        y => fuzzy::eq,
-    },
-
-    Point3 => {
-        x => fuzzy::eq,
-        y => fuzzy::eq,
-        z => fuzzy::eq,
-    },
+    TMat2<S>   => { 0 },
+    TMat3<S>   => { 0 },
+    TMat4<S>   => { 0 },
+    TPoint2<S> => { x y },
+    TPoint3<S> => { x y z },
+    TScale2<S> => { w h },
+    TScale3<S> => { w h d },
+);
+
+// NOTE:
+// Arrays are foreign types, so like scalars on the left these are implemented for each scalar.
+macro_rules! into_array {
+    ($scalar:ty => $struct:ident [$size:expr] |$value:ident| $array:expr) => {
+        impl From<$struct<$scalar>> for [$scalar; $size] {
+            fn from($value: $struct<$scalar>) -> Self {
+                $array
+            }
+        }
+    };
+
+    ($($struct:ident [$size:expr] |$value:ident| $array:expr,)*) => {
+        $(
+            into_array!(f32 => $struct [$size] |$value| $array);
+            into_array!(f64 => $struct [$size] |$value| $array);
+        )*
+    };
+}
+
+into_array!(
+    TVec4 [4] |vec| [vec.x, vec.y, vec.z, vec.w],
+    TMat2 [4] |matrix| matrix.0,
+    TMat3 [9] |matrix| matrix.0,
+    TMat4 [16] |matrix| matrix.0,
 );
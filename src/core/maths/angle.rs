@@ -1,15 +1,21 @@
 use std::{
+    cmp::Ordering,
     f32::consts::{FRAC_PI_2, PI, TAU},
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 use bytemuck::{Pod, Zeroable};
 
+use crate::core::maths::{ApproxEq, Scalar, TVec2};
+
 const DEG_TO_RAD: f32 = TAU / 360.0;
 const RAD_TO_DEG: f32 = 360.0 / TAU;
 
+/// An angle in radians. Angles aren't wrapped, so a full turn is different from no turn at all,
+/// use `normalise` or `normalise_signed` when only the direction matters.
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Angle(f32);
 
 impl Angle {
@@ -31,21 +37,54 @@ impl Angle {
         Self(PI + FRAC_PI_2)
     }
 
+    pub const fn tau() -> Self {
+        Self(TAU)
+    }
+
     // TODO:
     // Make this a const when floating point arithmatic is allowed
     pub fn degrees(degrees: f32) -> Self {
-        Self((degrees * DEG_TO_RAD) % TAU)
+        Self(degrees * DEG_TO_RAD)
     }
 
-    // TODO:
-    // Make this a const when floating point arithmatic is allowed
-    pub fn radians(radians: f32) -> Self {
-        Self(radians % TAU)
+    pub const fn radians(radians: f32) -> Self {
+        Self(radians)
     }
 
-    // TODO:
-    // Make this a const when floating point arithmatic is allowed
-    pub fn into_degress(self) -> f32 {
+    /// The angle whose tangent is `y / x`, using the signs of both to pick the quadrant.
+    pub fn atan2(y: f32, x: f32) -> Self {
+        Self(f32::atan2(y, x))
+    }
+
+    /// The angle of `vec` anticlockwise from +X, in (-π, π].
+    pub fn from_vec2<S>(vec: &TVec2<S>) -> Self
+    where
+        S: Scalar,
+    {
+        Self::atan2(vec.y.to_f32(), vec.x.to_f32())
+    }
+
+    /// The signed angle that turns `from` to face `to`, anticlockwise being positive.
+    pub fn between<S>(from: &TVec2<S>, to: &TVec2<S>) -> Self
+    where
+        S: Scalar,
+    {
+        let cross = from.x * to.y - from.y * to.x;
+
+        Self::atan2(cross.to_f32(), from.dot(to).to_f32())
+    }
+
+    /// Interpolates the angles themselves, so going from 0 to 3π turns one and a half times.
+    pub fn lerp(start: &Self, end: &Self, t: f32) -> Self {
+        Self(start.0 + (end.0 - start.0) * t)
+    }
+
+    /// Interpolates along the shortest arc between the directions the angles face.
+    pub fn lerp_shortest(start: &Self, end: &Self, t: f32) -> Self {
+        *start + start.shortest_difference(*end) * t
+    }
+
+    pub fn into_degrees(self) -> f32 {
         self.0 * RAD_TO_DEG
     }
 
@@ -53,6 +92,51 @@ impl Angle {
         self.0
     }
 
+    /// The same direction in [0, 2π).
+    pub fn normalise(&self) -> Self {
+        let radians = self.0.rem_euclid(TAU);
+
+        // NOTE:
+        // Tiny negative angles round up to 2π, which is outside of the range.
+        if radians < TAU {
+            Self(radians)
+        } else {
+            Self(0.0)
+        }
+    }
+
+    /// The same direction in (-π, π].
+    pub fn normalise_signed(&self) -> Self {
+        let radians = self.normalise().0;
+
+        if radians > PI {
+            Self(radians - TAU)
+        } else {
+            Self(radians)
+        }
+    }
+
+    /// The smallest signed angle that turns `self` to face `to`, in (-π, π].
+    pub fn shortest_difference(&self, to: Angle) -> Self {
+        (to - *self).normalise_signed()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    pub fn sin(&self) -> f32 {
+        self.0.sin()
+    }
+
+    pub fn cos(&self) -> f32 {
+        self.0.cos()
+    }
+
+    pub fn tan(&self) -> f32 {
+        self.0.tan()
+    }
+
     pub fn sin_cos(&self) -> (f32, f32) {
         (f32::sin(self.0), f32::cos(self.0))
     }
@@ -69,7 +153,7 @@ impl Add for Angle {
 
 impl AddAssign for Angle {
     fn add_assign(&mut self, rhs: Self) {
-        self.0 = (self.0 + rhs.0) % TAU;
+        self.0 += rhs.0;
     }
 }
 
@@ -84,7 +168,15 @@ impl Sub for Angle {
 
 impl SubAssign for Angle {
     fn sub_assign(&mut self, rhs: Self) {
-        self.0 = (self.0 - rhs.0) % TAU;
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
     }
 }
 
@@ -99,7 +191,7 @@ impl Mul<f32> for Angle {
 
 impl MulAssign<f32> for Angle {
     fn mul_assign(&mut self, rhs: f32) {
-        self.0 = (self.0 * rhs) % TAU;
+        self.0 *= rhs;
     }
 }
 
@@ -114,6 +206,155 @@ impl Div<f32> for Angle {
 
 impl DivAssign<f32> for Angle {
     fn div_assign(&mut self, rhs: f32) {
-        self.0 = (self.0 / rhs) % TAU;
+        self.0 /= rhs;
+    }
+}
+
+impl ApproxEq for Angle {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> f32 {
+        f32::default_epsilon()
+    }
+
+    fn default_max_relative() -> f32 {
+        f32::default_max_relative()
+    }
+
+    fn abs_diff_eq(&self, rhs: &Angle, epsilon: f32) -> bool {
+        self.0.abs_diff_eq(&rhs.0, epsilon)
+    }
+
+    fn relative_eq(&self, rhs: &Angle, epsilon: f32, max_relative: f32) -> bool {
+        self.0.relative_eq(&rhs.0, epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, rhs: &Angle, epsilon: f32, max_ulps: u32) -> bool {
+        self.0.ulps_eq(&rhs.0, epsilon, max_ulps)
+    }
+}
+
+impl PartialEq for Angle {
+    fn eq(&self, rhs: &Angle) -> bool {
+        self.approx_eq(rhs)
+    }
+}
+
+impl PartialOrd for Angle {
+    fn partial_cmp(&self, rhs: &Angle) -> Option<Ordering> {
+        // NOTE:
+        // Equality is approximate, ordering has to agree with it.
+        if self == rhs {
+            Some(Ordering::Equal)
+        } else {
+            self.0.partial_cmp(&rhs.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_approx_eq, core::maths::Vec2};
+
+    use super::*;
+
+    #[test]
+    fn angles_are_not_wrapped() {
+        assert_approx_eq!(Angle::degrees(720.0).into_degrees(), 720.0, epsilon = 1e-4);
+        assert_approx_eq!(Angle::radians(-1.0).into_radians(), -1.0);
+        assert_approx_eq!((Angle::pi() + Angle::pi()).into_radians(), TAU);
+        assert_approx_eq!((-Angle::half_pi() * 3.0).into_radians(), -3.0 * FRAC_PI_2);
+    }
+
+    #[test]
+    fn normalise() {
+        assert_approx_eq!(
+            Angle::radians(-FRAC_PI_2).normalise(),
+            Angle::radians(3.0 * FRAC_PI_2)
+        );
+        assert_approx_eq!(
+            Angle::radians(5.0 * PI).normalise(),
+            Angle::pi(),
+            epsilon = 1e-5
+        );
+        assert_approx_eq!(Angle::tau().normalise(), Angle::zero());
+        assert!(Angle::radians(-1e-9).normalise().into_radians() < TAU);
+    }
+
+    #[test]
+    fn normalise_signed() {
+        assert_approx_eq!(
+            Angle::radians(3.0 * FRAC_PI_2).normalise_signed(),
+            -Angle::half_pi(),
+            epsilon = 1e-6
+        );
+        assert_approx_eq!(Angle::pi().normalise_signed(), Angle::pi());
+        assert_approx_eq!((-Angle::pi()).normalise_signed(), Angle::pi());
+        assert_approx_eq!(
+            Angle::degrees(-30.0).normalise_signed(),
+            Angle::degrees(-30.0)
+        );
+    }
+
+    #[test]
+    fn shortest_difference_crosses_zero() {
+        let (a, b) = (Angle::degrees(350.0), Angle::degrees(10.0));
+
+        assert_approx_eq!(
+            a.shortest_difference(b),
+            Angle::degrees(20.0),
+            epsilon = 1e-5
+        );
+        assert_approx_eq!(
+            b.shortest_difference(a),
+            Angle::degrees(-20.0),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn lerp() {
+        let (start, end) = (Angle::zero(), Angle::radians(3.0 * PI));
+
+        assert_approx_eq!(Angle::lerp(&start, &end, 0.5), Angle::radians(1.5 * PI));
+
+        let (start, end) = (Angle::degrees(350.0), Angle::degrees(10.0));
+        let mid = Angle::lerp_shortest(&start, &end, 0.5).normalise_signed();
+
+        assert_approx_eq!(mid, Angle::zero(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn from_vectors() {
+        assert_approx_eq!(Angle::from_vec2(&Vec2::new(0.0, 2.0)), Angle::half_pi());
+        assert_approx_eq!(Angle::from_vec2(&Vec2::new(-1.0, 0.0)), Angle::pi());
+        assert_approx_eq!(
+            Angle::between(&Vec2::new(1.0, 0.0), &Vec2::new(0.0, -3.0)),
+            -Angle::half_pi()
+        );
+        assert_approx_eq!(Angle::atan2(1.0, 1.0).tan(), 1.0);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Angle::degrees(10.0) < Angle::degrees(20.0));
+        assert!(Angle::degrees(-10.0) < Angle::zero());
+        assert!(Angle::degrees(720.0) > Angle::degrees(360.0));
+        assert_eq!(
+            Angle::radians(1.0).partial_cmp(&Angle::radians(1.0 + 1e-7)),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let json = serde_json::to_string(&Angle::radians(1.5)).unwrap();
+
+        assert_eq!(json, "1.5");
+        assert_eq!(
+            serde_json::from_str::<Angle>(&json).unwrap(),
+            Angle::radians(1.5)
+        );
     }
 }
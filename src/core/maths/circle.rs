@@ -0,0 +1,42 @@
+use crate::core::maths::{Normed, Point2};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle {
+    pub centre: Point2,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub const fn new(centre: Point2, radius: f32) -> Self {
+        Self { centre, radius }
+    }
+
+    pub fn contains_point(&self, point: &Point2) -> bool {
+        self.centre.vector_to(point).norm_squared() <= self.radius * self.radius
+    }
+
+    pub fn intersects(&self, rhs: &Circle) -> bool {
+        let radii = self.radius + rhs.radius;
+
+        self.centre.vector_to(&rhs.centre).norm_squared() <= radii * radii
+    }
+
+    /// The point on the circle closest to `point`, the centre is equally close to all of them so
+    /// the rightmost is picked for it.
+    pub fn closest_point(&self, point: &Point2) -> Point2 {
+        let offset = self.centre.vector_to(point);
+        let distance = offset.norm();
+
+        if distance == 0.0 {
+            return Point2::new(self.centre.x + self.radius, self.centre.y);
+        }
+
+        self.centre + offset * (self.radius / distance)
+    }
+
+    /// The distance from the circle to `point`, negative inside it.
+    pub fn signed_distance(&self, point: &Point2) -> f32 {
+        self.centre.vector_to(point).norm() - self.radius
+    }
+}
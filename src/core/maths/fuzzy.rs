@@ -1,12 +1,310 @@
-use core::f32::EPSILON;
+/// The absolute tolerance used by `eq` and friends and by `PartialEq` on maths types, a few ULPs
+/// around 1.0 so rounding in a handful of operations doesn't break equality.
+pub const DEFAULT_EPSILON: f32 = 8.0 * f32::EPSILON;
 
-// TODO:
-// Find a good default for this.
-pub(crate) const DEFAULT_EPSILON: f32 = 1e-10;
+/// The relative tolerance used by `ApproxEq::approx_eq`, scaled by the larger of the two values.
+pub const DEFAULT_MAX_RELATIVE: f32 = 8.0 * f32::EPSILON;
+
+pub const DEFAULT_MAX_ULPS: u32 = 8;
+
+/// Approximate equality, for types built from floats that rarely compare exactly equal.
+///
+/// Every comparison first accepts values within `epsilon` of each other, as relative and ULPs
+/// comparisons fall apart near zero.
+pub trait ApproxEq<Rhs = Self>
+where
+    Rhs: ?Sized,
+{
+    /// The type tolerances are given in, the scalar the type is built from.
+    type Epsilon: Copy;
+
+    fn default_epsilon() -> Self::Epsilon;
+
+    fn default_max_relative() -> Self::Epsilon;
+
+    fn default_max_ulps() -> u32 {
+        DEFAULT_MAX_ULPS
+    }
+
+    /// Equal when every component differs by at most `epsilon`.
+    fn abs_diff_eq(&self, other: &Rhs, epsilon: Self::Epsilon) -> bool;
+
+    /// Equal when every component differs by at most `max_relative` times the larger of the two.
+    fn relative_eq(&self, other: &Rhs, epsilon: Self::Epsilon, max_relative: Self::Epsilon)
+        -> bool;
+
+    /// Equal when every component is at most `max_ulps` representable floats away from the other.
+    fn ulps_eq(&self, other: &Rhs, epsilon: Self::Epsilon, max_ulps: u32) -> bool;
+
+    /// A relative comparison with the default tolerances, what `PartialEq` uses.
+    fn approx_eq(&self, other: &Rhs) -> bool {
+        self.relative_eq(other, Self::default_epsilon(), Self::default_max_relative())
+    }
+}
+
+macro_rules! approx_eq {
+    ($($scalar:ident $bits:ident => ($epsilon:expr, $max_relative:expr),)*) => {
+        $(
+            impl ApproxEq for $scalar {
+                type Epsilon = $scalar;
+
+                fn default_epsilon() -> $scalar {
+                    $epsilon
+                }
+
+                fn default_max_relative() -> $scalar {
+                    $max_relative
+                }
+
+                fn abs_diff_eq(&self, other: &$scalar, epsilon: $scalar) -> bool {
+                    self == other || (self - other).abs() <= epsilon
+                }
+
+                fn relative_eq(&self, other: &$scalar, epsilon: $scalar, max_relative: $scalar)
+                    -> bool
+                {
+                    if self == other {
+                        return true;
+                    }
+
+                    if self.is_infinite() || other.is_infinite() {
+                        return false;
+                    }
+
+                    let difference = (self - other).abs();
+                    let largest = self.abs().max(other.abs());
+
+                    difference <= epsilon || difference <= largest * max_relative
+                }
+
+                fn ulps_eq(&self, other: &$scalar, epsilon: $scalar, max_ulps: u32) -> bool {
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+
+                    if self.is_nan()
+                        || other.is_nan()
+                        || self.is_sign_negative() != other.is_sign_negative()
+                    {
+                        return false;
+                    }
+
+                    // NOTE:
+                    // Floats of the same sign order the same way as their bits.
+                    self.to_bits().abs_diff(other.to_bits()) <= max_ulps as $bits
+                }
+            }
+        )*
+    };
+}
+
+approx_eq!(
+    f32 u32 => (DEFAULT_EPSILON, DEFAULT_MAX_RELATIVE),
+    f64 u64 => (8.0 * f64::EPSILON, 8.0 * f64::EPSILON),
+);
+
+impl<T, const N: usize> ApproxEq for [T; N]
+where
+    T: ApproxEq,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        self.iter().zip(other).all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        self.iter().zip(other).all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        self.iter().zip(other).all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+// NOTE:
+// The assertion macros can't name the type being compared, these let them find its defaults.
+
+#[doc(hidden)]
+pub fn default_epsilon<T, Rhs>(_: &T, _: &Rhs) -> T::Epsilon
+where
+    T: ApproxEq<Rhs>,
+{
+    T::default_epsilon()
+}
+
+#[doc(hidden)]
+pub fn default_max_relative<T, Rhs>(_: &T, _: &Rhs) -> T::Epsilon
+where
+    T: ApproxEq<Rhs>,
+{
+    T::default_max_relative()
+}
+
+#[doc(hidden)]
+pub fn default_max_ulps<T, Rhs>(_: &T, _: &Rhs) -> u32
+where
+    T: ApproxEq<Rhs>,
+{
+    T::default_max_ulps()
+}
+
+/// Panics unless `ApproxEq::approx_eq` holds, or `abs_diff_eq` when an `epsilon` is given.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($lhs:expr, $rhs:expr $(,)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                if !$crate::core::maths::fuzzy::ApproxEq::approx_eq(lhs, rhs) {
+                    panic!(
+                        concat!(
+                            "assertion failed: `(left ≈ right)`\n",
+                            "  left: `{:?}`\n right: `{:?}`",
+                        ),
+                        lhs, rhs,
+                    );
+                }
+            }
+        }
+    };
+
+    ($lhs:expr, $rhs:expr, epsilon = $epsilon:expr $(,)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                if !$crate::core::maths::fuzzy::ApproxEq::abs_diff_eq(lhs, rhs, $epsilon) {
+                    panic!(
+                        concat!(
+                            "assertion failed: `(left ≈ right)` with epsilon `{:?}`\n",
+                            "  left: `{:?}`\n right: `{:?}`",
+                        ),
+                        $epsilon, lhs, rhs,
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Panics unless `ApproxEq::relative_eq` holds, with the default tolerances unless given.
+#[macro_export]
+macro_rules! assert_relative_eq {
+    (
+        $lhs:expr,
+        $rhs:expr
+        $(, epsilon = $epsilon:expr)?
+        $(, max_relative = $max_relative:expr)?
+        $(,)?
+    ) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                #[allow(unused_variables)]
+                let epsilon = $crate::core::maths::fuzzy::default_epsilon(lhs, rhs);
+                $( let epsilon = $epsilon; )?
+                #[allow(unused_variables)]
+                let max_relative = $crate::core::maths::fuzzy::default_max_relative(lhs, rhs);
+                $( let max_relative = $max_relative; )?
+
+                let equal = $crate::core::maths::fuzzy::ApproxEq::relative_eq(
+                    lhs,
+                    rhs,
+                    epsilon,
+                    max_relative,
+                );
+
+                if !equal {
+                    panic!(
+                        concat!(
+                            "assertion failed: `(left ≈ right)` with epsilon `{:?}` and max ",
+                            "relative `{:?}`\n",
+                            "  left: `{:?}`\n right: `{:?}`",
+                        ),
+                        epsilon, max_relative, lhs, rhs,
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Panics unless `ApproxEq::ulps_eq` holds, with the default tolerances unless given.
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    (
+        $lhs:expr,
+        $rhs:expr
+        $(, epsilon = $epsilon:expr)?
+        $(, max_ulps = $max_ulps:expr)?
+        $(,)?
+    ) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                #[allow(unused_variables)]
+                let epsilon = $crate::core::maths::fuzzy::default_epsilon(lhs, rhs);
+                $( let epsilon = $epsilon; )?
+                #[allow(unused_variables)]
+                let max_ulps = $crate::core::maths::fuzzy::default_max_ulps(lhs, rhs);
+                $( let max_ulps = $max_ulps; )?
+
+                if !$crate::core::maths::fuzzy::ApproxEq::ulps_eq(lhs, rhs, epsilon, max_ulps) {
+                    panic!(
+                        concat!(
+                            "assertion failed: `(left ≈ right)` with epsilon `{:?}` and max ",
+                            "ULPs `{:?}`\n",
+                            "  left: `{:?}`\n right: `{:?}`",
+                        ),
+                        epsilon, max_ulps, lhs, rhs,
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Panics if `ApproxEq::approx_eq` holds.
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($lhs:expr, $rhs:expr $(,)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                if $crate::core::maths::fuzzy::ApproxEq::approx_eq(lhs, rhs) {
+                    panic!(
+                        concat!(
+                            "assertion failed: `(left ≉ right)`\n",
+                            "  left: `{:?}`\n right: `{:?}`",
+                        ),
+                        lhs, rhs,
+                    );
+                }
+            }
+        }
+    };
+}
 
 #[inline(always)]
 pub fn eq_with_epsilon(a: f32, b: f32, epsilon: f32) -> bool {
-    (a - b).abs() <= epsilon + EPSILON
+    a.abs_diff_eq(&b, epsilon)
+}
+
+/// Compares `a` and `b` within `max_relative` times the larger of the two, or within `epsilon`
+/// for values near zero.
+#[inline]
+pub fn relative_eq(a: f32, b: f32, epsilon: f32, max_relative: f32) -> bool {
+    a.relative_eq(&b, epsilon, max_relative)
+}
+
+/// Compares `a` and `b` by how many representable floats lie between them, or within `epsilon`
+/// for values near zero.
+#[inline]
+pub fn ulps_eq(a: f32, b: f32, epsilon: f32, max_ulps: u32) -> bool {
+    a.ulps_eq(&b, epsilon, max_ulps)
 }
 
 #[inline(always)]
@@ -52,3 +350,58 @@ pub fn lt(a: f32, b: f32) -> bool {
 pub fn lte(a: f32, b: f32) -> bool {
     lte_with_epsilon(a, b, DEFAULT_EPSILON)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute() {
+        assert!(eq(0.1 + 0.2, 0.3));
+        assert!(eq(f32::INFINITY, f32::INFINITY));
+        assert!(!eq(1.0, 1.001));
+        assert!(!eq(f32::NAN, f32::NAN));
+        assert!(eq_with_epsilon(1.0, 1.001, 0.01));
+    }
+
+    #[test]
+    fn relative() {
+        assert!(relative_eq(1e6, 1e6 + 0.5, DEFAULT_EPSILON, DEFAULT_MAX_RELATIVE));
+        assert!(!relative_eq(1e6, 1e6 + 64.0, DEFAULT_EPSILON, DEFAULT_MAX_RELATIVE));
+        assert!(relative_eq(1e-9, -1e-9, DEFAULT_EPSILON, DEFAULT_MAX_RELATIVE));
+        assert!(!relative_eq(1e-9, -1e-9, 0.0, DEFAULT_MAX_RELATIVE));
+        assert!(!relative_eq(f32::INFINITY, f32::MAX, DEFAULT_EPSILON, DEFAULT_MAX_RELATIVE));
+    }
+
+    #[test]
+    fn ulps() {
+        let next = f32::from_bits(1e6f32.to_bits() + 3);
+
+        assert!(ulps_eq(1e6, next, 0.0, 3));
+        assert!(!ulps_eq(1e6, next, 0.0, 2));
+        assert!(!ulps_eq(1e-9, -1e-9, 0.0, DEFAULT_MAX_ULPS));
+        assert!(ulps_eq(0.0, -0.0, 0.0, 0));
+        assert!(!ulps_eq(f32::NAN, f32::NAN, 0.0, DEFAULT_MAX_ULPS));
+    }
+
+    #[test]
+    fn arrays() {
+        assert!([1.0, 2.0].approx_eq(&[1.0, 2.0 + f32::EPSILON]));
+        assert!(![1.0, 2.0].approx_eq(&[1.0, 2.1]));
+    }
+
+    #[test]
+    fn assertions() {
+        crate::assert_approx_eq!(0.1 + 0.2, 0.3);
+        crate::assert_approx_eq!(1.0, 1.001, epsilon = 0.01);
+        crate::assert_relative_eq!(100.0, 101.0, max_relative = 0.01);
+        crate::assert_ulps_eq!(1.0, 1.0 + f32::EPSILON, max_ulps = 1);
+        crate::assert_approx_ne!(1.0, 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn assertion_failure() {
+        crate::assert_approx_eq!(1.0, 1.1);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::core::maths::{Normed, Point2, Unit, Vec2};
+
+/// An infinite line through `origin` along `direction`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line2 {
+    pub origin:    Point2,
+    pub direction: Unit<Vec2>,
+}
+
+impl Line2 {
+    pub const fn new(origin: Point2, direction: Unit<Vec2>) -> Self {
+        Self { origin, direction }
+    }
+
+    /// The line through both points, which must not be the same point.
+    pub fn through(a: &Point2, b: &Point2) -> Self {
+        let direction = a.vector_to(b);
+
+        Self::new(*a, Unit::from_normalised(direction / direction.norm()))
+    }
+
+    /// The point `t` units along the line from its origin.
+    pub fn point_at(&self, t: f32) -> Point2 {
+        self.origin + t * self.direction
+    }
+
+    /// How far along the line the point closest to `point` is, negative behind the origin.
+    pub fn project(&self, point: &Point2) -> f32 {
+        self.origin.vector_to(point).dot(&self.direction)
+    }
+
+    pub fn closest_point(&self, point: &Point2) -> Point2 {
+        self.point_at(self.project(point))
+    }
+
+    /// The distance from the line to `point`, positive to the left of the direction and negative
+    /// to the right.
+    pub fn signed_distance(&self, point: &Point2) -> f32 {
+        self.direction.left_perpendicular_dot(&self.origin.vector_to(point))
+    }
+
+    pub fn distance_to(&self, point: &Point2) -> f32 {
+        self.signed_distance(point).abs()
+    }
+
+    /// Where the lines cross, `None` if they're parallel.
+    pub fn intersection(&self, rhs: &Line2) -> Option<Point2> {
+        let denominator = rhs.direction.left_perpendicular_dot(&self.direction);
+
+        if denominator.abs() < 1e-6 {
+            return None;
+        }
+
+        let t = rhs.direction.left_perpendicular_dot(&self.origin.vector_to(&rhs.origin))
+            / denominator;
+
+        Some(self.point_at(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::maths::fuzzy;
+
+    use super::*;
+
+    #[test]
+    fn closest_point() {
+        let line = Line2::through(&Point2::new(0.0, 1.0), &Point2::new(2.0, 1.0));
+        let Point2 { x, y } = line.closest_point(&Point2::new(5.0, 4.0));
+
+        assert_eq!([x, y], [5.0, 1.0]);
+        assert_eq!(line.signed_distance(&Point2::new(5.0, 4.0)), 3.0);
+        assert_eq!(line.signed_distance(&Point2::new(5.0, -1.0)), -2.0);
+    }
+
+    #[test]
+    fn intersection() {
+        let horizontal = Line2::through(&Point2::new(0.0, 1.0), &Point2::new(1.0, 1.0));
+        let diagonal = Line2::through(&Point2::new(0.0, 0.0), &Point2::new(1.0, 1.0));
+        let Point2 { x, y } = horizontal.intersection(&diagonal).unwrap();
+
+        assert!(fuzzy::eq_with_epsilon(x, 1.0, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(y, 1.0, 1e-5));
+        assert!(horizontal.intersection(&horizontal).is_none());
+    }
+}
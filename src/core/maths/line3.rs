@@ -0,0 +1,86 @@
+use crate::core::maths::{Normed, Point3, Unit, Vec3};
+
+/// An infinite line through `origin` along `direction`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line3 {
+    pub origin:    Point3,
+    pub direction: Unit<Vec3>,
+}
+
+impl Line3 {
+    pub const fn new(origin: Point3, direction: Unit<Vec3>) -> Self {
+        Self { origin, direction }
+    }
+
+    /// The line through both points, which must not be the same point.
+    pub fn through(a: &Point3, b: &Point3) -> Self {
+        let direction = a.vector_to(b);
+
+        Self::new(*a, Unit::from_normalised(direction / direction.norm()))
+    }
+
+    /// The point `t` units along the line from its origin.
+    pub fn point_at(&self, t: f32) -> Point3 {
+        self.origin + t * self.direction
+    }
+
+    /// How far along the line the point closest to `point` is, negative behind the origin.
+    pub fn project(&self, point: &Point3) -> f32 {
+        self.origin.vector_to(point).dot(&self.direction)
+    }
+
+    pub fn closest_point(&self, point: &Point3) -> Point3 {
+        self.point_at(self.project(point))
+    }
+
+    pub fn distance_to(&self, point: &Point3) -> f32 {
+        self.closest_point(point).vector_to(point).norm()
+    }
+
+    /// The closest points between the lines, on this line and then on `rhs`. `None` if the lines
+    /// are parallel, when every point is as close as any other.
+    pub fn closest_points(&self, rhs: &Line3) -> Option<(Point3, Point3)> {
+        let between = rhs.origin.vector_to(&self.origin);
+        let alignment = self.direction.dot(&rhs.direction);
+        let denominator = 1.0 - alignment * alignment;
+
+        if denominator < 1e-6 {
+            return None;
+        }
+
+        let self_along = self.direction.dot(&between);
+        let rhs_along = rhs.direction.dot(&between);
+
+        let s = (alignment * rhs_along - self_along) / denominator;
+        let t = (rhs_along - alignment * self_along) / denominator;
+
+        Some((self.point_at(s), rhs.point_at(t)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::maths::fuzzy;
+
+    use super::*;
+
+    #[test]
+    fn distance_to() {
+        let line = Line3::new(Point3::identity(), Vec3::unit_x());
+
+        assert_eq!(line.distance_to(&Point3::new(4.0, 3.0, 4.0)), 5.0);
+        assert_eq!(line.project(&Point3::new(-2.0, 3.0, 4.0)), -2.0);
+    }
+
+    #[test]
+    fn closest_points() {
+        let x_axis = Line3::new(Point3::identity(), Vec3::unit_x());
+        let skew = Line3::new(Point3::new(2.0, 0.0, 3.0), Vec3::unit_y());
+        let (a, b) = x_axis.closest_points(&skew).unwrap();
+
+        assert!(fuzzy::eq(a.x, 2.0) && fuzzy::eq(a.y, 0.0) && fuzzy::eq(a.z, 0.0));
+        assert!(fuzzy::eq(b.x, 2.0) && fuzzy::eq(b.y, 0.0) && fuzzy::eq(b.z, 3.0));
+        assert!(x_axis.closest_points(&x_axis).is_none());
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    borrow::Borrow,
+    ops::{Index, Mul, MulAssign},
+};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::maths::{Angle, Scalar, TScale2, TVec2};
+
+const MATRIX_WIDTH: usize = 2;
+const MATRIX_SIZE: usize = MATRIX_WIDTH * MATRIX_WIDTH;
+
+pub type Mat2 = TMat2<f32>;
+pub type DMat2 = TMat2<f64>;
+
+/// A 2x2 matrix stored in column major order, for rotating and scaling 2D vectors.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TMat2<S>(pub(in crate::core::maths) [S; MATRIX_SIZE]);
+
+impl<S> TMat2<S>
+where
+    S: Scalar,
+{
+    #[rustfmt::skip]
+    pub fn identity() -> Self {
+        let (zero, one) = (S::ZERO, S::ONE);
+
+        Self([
+            one , zero,
+            zero, one ,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn from_cols(x: &TVec2<S>, y: &TVec2<S>) -> Self {
+        Self([
+            x.x, x.y,
+            y.x, y.y,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn rotation(angle: Angle) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let (sin, cos) = (S::from_f32(sin), S::from_f32(cos));
+
+        Self([
+             cos, sin,
+            -sin, cos,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn scale(scale: &TScale2<S>) -> Self {
+        let TScale2 { w, h } = *scale;
+        let zero = S::ZERO;
+
+        Self([
+            w   , zero,
+            zero, h   ,
+        ])
+    }
+
+    pub fn row(&self, index: usize) -> TVec2<S> {
+        TVec2::new(self.0[index], self.0[index + MATRIX_WIDTH])
+    }
+
+    pub fn col(&self, index: usize) -> TVec2<S> {
+        let start = index * MATRIX_WIDTH;
+
+        TVec2::new(self.0[start], self.0[start + 1])
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_cols(&self.row(0), &self.row(1))
+    }
+
+    pub fn determinant(&self) -> S {
+        let Self([m00, m01, m10, m11]) = *self;
+
+        m00 * m11 - m10 * m01
+    }
+
+    /// Inverts the matrix, a singular matrix produces infinities or NaNs.
+    #[rustfmt::skip]
+    pub fn inverse(&self) -> Self {
+        let Self([m00, m01, m10, m11]) = *self;
+        let reciprocal = self.determinant().recip();
+
+        Self([
+             m11 * reciprocal, -m01 * reciprocal,
+            -m10 * reciprocal,  m00 * reciprocal,
+        ])
+    }
+
+    /// Converts to another scalar type.
+    pub fn cast<T>(&self) -> TMat2<T>
+    where
+        T: Scalar,
+    {
+        TMat2(self.0.map(|element| T::from_f64(element.to_f64())))
+    }
+}
+
+unsafe impl<S: Scalar> Pod for TMat2<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TMat2<S> {}
+
+impl<S> Mul for TMat2<S>
+where
+    S: Scalar,
+{
+    type Output = TMat2<S>;
+
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self *= &rhs;
+        self
+    }
+}
+
+impl<S, M> MulAssign<M> for TMat2<S>
+where
+    S: Scalar,
+    M: Borrow<TMat2<S>>,
+{
+    fn mul_assign(&mut self, rhs: M) {
+        let rhs = rhs.borrow();
+        let lhs = *self;
+
+        for i in 0..MATRIX_WIDTH {
+            for j in 0..MATRIX_WIDTH {
+                self.0[j * MATRIX_WIDTH + i] = lhs.row(i).dot(&rhs.col(j));
+            }
+        }
+    }
+}
+
+impl<S> Mul<TVec2<S>> for TMat2<S>
+where
+    S: Scalar,
+{
+    type Output = TVec2<S>;
+
+    fn mul(self, rhs: TVec2<S>) -> Self::Output {
+        TVec2::new(self.row(0).dot(&rhs), self.row(1).dot(&rhs))
+    }
+}
+
+impl<S> Index<usize> for TMat2<S> {
+    type Output = S;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<S> From<[S; MATRIX_SIZE]> for TMat2<S> {
+    fn from(data: [S; MATRIX_SIZE]) -> Self {
+        Self(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::{
+        assert_approx_eq,
+        core::maths::{fuzzy, Scale2, Vec2},
+    };
+
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        let Vec2 { x, y } = Mat2::rotation(Angle::radians(FRAC_PI_2)) * Vec2::unit_x();
+
+        assert!(fuzzy::eq(x, 0.0));
+        assert!(fuzzy::eq(y, 1.0));
+    }
+
+    #[test]
+    fn transpose() {
+        let transposed = Mat2::from([1.0, 2.0, 3.0, 4.0]).transpose();
+
+        assert_eq!(<[f32; 4]>::from(transposed), [1.0, 3.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(Mat2::from([1.0, 2.0, 3.0, 4.0]).determinant(), -2.0);
+        assert_eq!(Mat2::scale(&Scale2::new(2.0, 3.0)).determinant(), 6.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Mat2::rotation(Angle::radians(0.5)) * Mat2::scale(&Scale2::new(2.0, 3.0));
+
+        assert_approx_eq!(matrix * matrix.inverse(), Mat2::identity(), epsilon = 1e-5);
+        assert_approx_eq!(matrix.inverse() * matrix, Mat2::identity(), epsilon = 1e-5);
+    }
+}
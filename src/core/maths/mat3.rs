@@ -0,0 +1,392 @@
+use std::{
+    borrow::Borrow,
+    ops::{Index, Mul, MulAssign},
+};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::maths::{
+    Angle, Scalar, TMat2, TMat4, TPoint2, TQuat, TScale2, TScale3, TVec2, TVec3,
+};
+
+const MATRIX_WIDTH: usize = 3;
+const MATRIX_SIZE: usize = MATRIX_WIDTH * MATRIX_WIDTH;
+
+pub type Mat3 = TMat3<f32>;
+pub type DMat3 = TMat3<f64>;
+
+/// A 3x3 matrix stored in column major order. Used for 3D rotations and scales, normal matrices
+/// and 2D affine transforms.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TMat3<S>(pub(in crate::core::maths) [S; MATRIX_SIZE]);
+
+impl<S> TMat3<S>
+where
+    S: Scalar,
+{
+    #[rustfmt::skip]
+    pub fn identity() -> Self {
+        let (zero, one) = (S::ZERO, S::ONE);
+
+        Self([
+            one , zero, zero,
+            zero, one , zero,
+            zero, zero, one ,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn from_cols(x: &TVec3<S>, y: &TVec3<S>, z: &TVec3<S>) -> Self {
+        Self([
+            x.x, x.y, x.z,
+            y.x, y.y, y.z,
+            z.x, z.y, z.z,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn rotation(rotation: &TQuat<S>) -> Self {
+        let TQuat { v: TVec3 { x, y, z }, w } = *rotation;
+        let (one, two) = (S::ONE, S::TWO);
+
+        let x_x = x * x;
+        let y_y = y * y;
+        let z_z = z * z;
+        let x_y = x * y;
+        let x_z = x * z;
+        let y_z = y * z;
+        let x_w = x * w;
+        let y_w = y * w;
+        let z_w = z * w;
+
+        Self([
+            one - two * (y_y + z_z), two * (x_y + z_w)      , two * (x_z - y_w)      ,
+            two * (x_y - z_w)      , one - two * (x_x + z_z), two * (y_z + x_w)      ,
+            two * (x_z + y_w)      , two * (y_z - x_w)      , one - two * (x_x + y_y),
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn scale(scale: &TScale3<S>) -> Self {
+        let TScale3 { w, h, d } = *scale;
+        let zero = S::ZERO;
+
+        Self([
+            w   , zero, zero,
+            zero, h   , zero,
+            zero, zero, d   ,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub fn translation_2d(translation: &TVec2<S>) -> Self {
+        let TVec2 { x, y } = *translation;
+        let (zero, one) = (S::ZERO, S::ONE);
+
+        Self([
+            one , zero, zero,
+            zero, one , zero,
+            x   , y   , one ,
+        ])
+    }
+
+    pub fn rotation_2d(angle: Angle) -> Self {
+        Self::from(TMat2::rotation(angle))
+    }
+
+    pub fn scale_2d(scale: &TScale2<S>) -> Self {
+        Self::from(TMat2::scale(scale))
+    }
+
+    /// The matrix that transforms normals by `model`, the inverse transpose of its upper 3x3.
+    /// Unlike the model matrix itself it keeps normals perpendicular to surfaces under non-uniform
+    /// scales.
+    pub fn normal(model: &TMat4<S>) -> Self {
+        Self::from(*model).inverse().transpose()
+    }
+
+    pub fn row(&self, index: usize) -> TVec3<S> {
+        TVec3::new(
+            self.0[index],
+            self.0[index + MATRIX_WIDTH],
+            self.0[index + 2 * MATRIX_WIDTH],
+        )
+    }
+
+    pub fn col(&self, index: usize) -> TVec3<S> {
+        let start = index * MATRIX_WIDTH;
+
+        TVec3::new(self.0[start], self.0[start + 1], self.0[start + 2])
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_cols(&self.row(0), &self.row(1), &self.row(2))
+    }
+
+    pub fn determinant(&self) -> S {
+        self.col(0).cross(&self.col(1)).dot(&self.col(2))
+    }
+
+    /// Inverts the matrix, a singular matrix produces infinities or NaNs.
+    pub fn inverse(&self) -> Self {
+        let (x, y, z) = (self.col(0), self.col(1), self.col(2));
+
+        // NOTE:
+        // The rows of the inverse are the cross products of the other two columns, divided by the
+        // determinant.
+        let y_z = y.cross(&z);
+        let z_x = z.cross(&x);
+        let x_y = x.cross(&y);
+        let reciprocal = x.dot(&y_z).recip();
+
+        Self::from_cols(&(y_z * reciprocal), &(z_x * reciprocal), &(x_y * reciprocal)).transpose()
+    }
+
+    /// Transforms a point by the matrix as a 2D affine transform, translation included.
+    pub fn transform_point(&self, point: &TPoint2<S>) -> TPoint2<S> {
+        let TVec3 { x, y, .. } = *self * TVec3::new(point.x, point.y, S::ONE);
+
+        TPoint2::new(x, y)
+    }
+
+    /// Transforms a vector by the matrix as a 2D affine transform, ignoring translation.
+    pub fn transform_vector(&self, vector: &TVec2<S>) -> TVec2<S> {
+        let TVec3 { x, y, .. } = *self * TVec3::new(vector.x, vector.y, S::ZERO);
+
+        TVec2::new(x, y)
+    }
+
+    /// Converts to another scalar type.
+    pub fn cast<T>(&self) -> TMat3<T>
+    where
+        T: Scalar,
+    {
+        TMat3(self.0.map(|element| T::from_f64(element.to_f64())))
+    }
+}
+
+impl Mat3 {
+    /// The matrix laid out for a `mat3x3<f32>` in a uniform buffer, where every column is padded
+    /// to 16 bytes.
+    #[rustfmt::skip]
+    pub fn padded(&self) -> [f32; 12] {
+        let Self([
+            m00, m01, m02,
+            m10, m11, m12,
+            m20, m21, m22,
+        ]) = *self;
+
+        [
+            m00, m01, m02, 0.0,
+            m10, m11, m12, 0.0,
+            m20, m21, m22, 0.0,
+        ]
+    }
+}
+
+unsafe impl<S: Scalar> Pod for TMat3<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TMat3<S> {}
+
+impl<S> Mul for TMat3<S>
+where
+    S: Scalar,
+{
+    type Output = TMat3<S>;
+
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self *= &rhs;
+        self
+    }
+}
+
+impl<S, M> MulAssign<M> for TMat3<S>
+where
+    S: Scalar,
+    M: Borrow<TMat3<S>>,
+{
+    fn mul_assign(&mut self, rhs: M) {
+        let rhs = rhs.borrow();
+        let lhs = *self;
+
+        for i in 0..MATRIX_WIDTH {
+            for j in 0..MATRIX_WIDTH {
+                self.0[j * MATRIX_WIDTH + i] = lhs.row(i).dot(&rhs.col(j));
+            }
+        }
+    }
+}
+
+impl<S> Mul<TVec3<S>> for TMat3<S>
+where
+    S: Scalar,
+{
+    type Output = TVec3<S>;
+
+    fn mul(self, rhs: TVec3<S>) -> Self::Output {
+        TVec3::new(
+            self.row(0).dot(&rhs),
+            self.row(1).dot(&rhs),
+            self.row(2).dot(&rhs),
+        )
+    }
+}
+
+impl<S> Index<usize> for TMat3<S> {
+    type Output = S;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<S> From<[S; MATRIX_SIZE]> for TMat3<S> {
+    fn from(data: [S; MATRIX_SIZE]) -> Self {
+        Self(data)
+    }
+}
+
+impl<S> From<TMat2<S>> for TMat3<S>
+where
+    S: Scalar,
+{
+    #[rustfmt::skip]
+    fn from(matrix: TMat2<S>) -> Self {
+        let TMat2([
+            m00, m01,
+            m10, m11,
+        ]) = matrix;
+        let (zero, one) = (S::ZERO, S::ONE);
+
+        Self([
+            m00 , m01 , zero,
+            m10 , m11 , zero,
+            zero, zero, one ,
+        ])
+    }
+}
+
+/// The upper left 3x3 of the matrix, its rotation and scale without the translation.
+impl<S> From<TMat4<S>> for TMat3<S>
+where
+    S: Scalar,
+{
+    fn from(matrix: TMat4<S>) -> Self {
+        Self::from_cols(
+            &matrix.col(0).truncate(),
+            &matrix.col(1).truncate(),
+            &matrix.col(2).truncate(),
+        )
+    }
+}
+
+/// Extracts the rotation of a pure rotation matrix, scaled or sheared matrices have to have their
+/// rotation separated first, see `Mat4::decompose`.
+impl<S> From<TMat3<S>> for TQuat<S>
+where
+    S: Scalar,
+{
+    fn from(matrix: TMat3<S>) -> Self {
+        let (x, y, z) = (matrix.col(0), matrix.col(1), matrix.col(2));
+        let trace = x.x + y.y + z.z;
+        let (one, two, quarter) = (S::ONE, S::TWO, S::from_f32(0.25));
+
+        // NOTE:
+        // Dividing by whichever of w, x, y or z is largest keeps the result accurate, dividing by
+        // a component close to zero would not.
+        if trace > S::ZERO {
+            let s = (trace + one).sqrt() * two;
+
+            TQuat::new(quarter * s, TVec3::new(y.z - z.y, z.x - x.z, x.y - y.x) / s)
+        } else if x.x > y.y && x.x > z.z {
+            let s = (one + x.x - y.y - z.z).sqrt() * two;
+
+            TQuat::new((y.z - z.y) / s, TVec3::new(quarter * s, (y.x + x.y) / s, (z.x + x.z) / s))
+        } else if y.y > z.z {
+            let s = (one + y.y - x.x - z.z).sqrt() * two;
+
+            TQuat::new((z.x - x.z) / s, TVec3::new((y.x + x.y) / s, quarter * s, (z.y + y.z) / s))
+        } else {
+            let s = (one + z.z - x.x - y.y).sqrt() * two;
+
+            TQuat::new((x.y - y.x) / s, TVec3::new((z.x + x.z) / s, (z.y + y.z) / s, quarter * s))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::{
+        assert_approx_eq,
+        core::maths::{fuzzy, Mat4, Normed, Point2, Quat, Scale3, Unit, Vec2, Vec3},
+    };
+
+    use super::*;
+
+    fn rotation() -> Quat {
+        let axis = Vec3::new(1.0, 2.0, 3.0);
+
+        Quat::from_axis_angle(&Unit::from_normalised(axis / axis.norm()), Angle::radians(1.2))
+    }
+
+    #[test]
+    fn rotation_matches_quaternion() {
+        let vector = Vec3::new(0.5, -1.0, 2.0);
+        let Vec3 { x, y, z } = Mat3::rotation(&rotation()) * vector;
+        let expected = vector.rotate(&rotation());
+
+        assert!(fuzzy::eq_with_epsilon(x, expected.x, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(y, expected.y, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(z, expected.z, 1e-5));
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Mat3::rotation(&rotation()) * Mat3::scale(&Scale3::new(1.0, 2.0, 3.0));
+
+        assert_approx_eq!(matrix * matrix.inverse(), Mat3::identity(), epsilon = 1e-5);
+        assert_approx_eq!(matrix.inverse() * matrix, Mat3::identity(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn determinant() {
+        assert!(fuzzy::eq_with_epsilon(Mat3::rotation(&rotation()).determinant(), 1.0, 1e-5));
+        assert_eq!(Mat3::scale(&Scale3::new(1.0, 2.0, 3.0)).determinant(), 6.0);
+    }
+
+    #[test]
+    fn quaternion_round_trip() {
+        let rotation = rotation();
+        let extracted = Quat::from(Mat3::rotation(&rotation));
+
+        assert!(fuzzy::eq_with_epsilon(extracted.w, rotation.w, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(extracted.v.x, rotation.v.x, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(extracted.v.y, rotation.v.y, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(extracted.v.z, rotation.v.z, 1e-5));
+    }
+
+    #[test]
+    fn affine_2d() {
+        let matrix = Mat3::translation_2d(&Vec2::new(1.0, 2.0))
+            * Mat3::rotation_2d(Angle::radians(FRAC_PI_2));
+        let point = matrix.transform_point(&Point2::new(1.0, 0.0));
+        let vector = matrix.transform_vector(&Vec2::new(1.0, 0.0));
+
+        assert!(fuzzy::eq_with_epsilon(point.x, 1.0, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(point.y, 3.0, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(vector.x, 0.0, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(vector.y, 1.0, 1e-5));
+    }
+
+    #[test]
+    fn normal_matrix_keeps_normals_perpendicular() {
+        let model = Mat4::scale(&Scale3::new(4.0, 1.0, 1.0));
+        let tangent = Mat3::from(model) * Vec3::new(1.0, -1.0, 0.0);
+        let normal = Mat3::normal(&model) * Vec3::new(1.0, 1.0, 0.0);
+
+        assert!(fuzzy::eq_with_epsilon(tangent.dot(&normal), 0.0, 1e-5));
+    }
+}
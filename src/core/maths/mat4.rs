@@ -3,227 +3,209 @@ use std::{
     ops::{Index, Mul, MulAssign},
 };
 
-use crate::core::maths::{Angle, Point3, Quat, Scale3, Vec3};
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::maths::{Angle, Normed, Scalar, TMat3, TPoint3, TQuat, TScale3, TVec3, TVec4};
 
 const MATRIX_WIDTH: usize = 4;
 const MATRIX_SIZE: usize = MATRIX_WIDTH * MATRIX_WIDTH;
 
-#[repr(C)]
-pub struct Mat4(pub(in crate::core::maths) [f32; MATRIX_SIZE]);
+pub type Mat4 = TMat4<f32>;
+pub type DMat4 = TMat4<f64>;
 
-pub struct Camera {
-    pub position: Vec3,
-    pub right:    Vec3,
-    pub up:       Vec3,
-    pub forward:  Vec3,
-}
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TMat4<S>(pub(in crate::core::maths) [S; MATRIX_SIZE]);
 
-impl Mat4 {
+impl<S> TMat4<S>
+where
+    S: Scalar,
+{
     #[rustfmt::skip]
     pub fn identity() -> Self {
+        let (zero, one) = (S::ZERO, S::ONE);
+
         Self([
-            1.0, 0.0, 0.0, 0.0,
-            0.0, 1.0, 0.0, 0.0,
-            0.0, 0.0, 1.0, 0.0,
-            0.0, 0.0, 0.0, 1.0,
+            one , zero, zero, zero,
+            zero, one , zero, zero,
+            zero, zero, one , zero,
+            zero, zero, zero, one ,
         ])
     }
 
     #[rustfmt::skip]
-    pub fn translation(translation: &Vec3) -> Self {
-        let Vec3 { x, y, z } = translation.borrow();
+    pub fn translation(translation: &TVec3<S>) -> Self {
+        let TVec3 { x, y, z } = *translation;
+        let (zero, one) = (S::ZERO, S::ONE);
 
         Self([
-            1.0, 0.0, 0.0, 0.0,
-            0.0, 1.0, 0.0, 0.0,
-            0.0, 0.0, 1.0, 0.0,
-            *x , *y , *z , 1.0,
+            one , zero, zero, zero,
+            zero, one , zero, zero,
+            zero, zero, one , zero,
+            x   , y   , z   , one ,
         ])
     }
 
-    #[rustfmt::skip]
-    pub fn rotation(rotation: &Quat) -> Self {
-        let Quat { v: Vec3 { x, y, z }, w} = *rotation.borrow();
-
-        let two_x = 2.0 * x;
-        let two_y = 2.0 * y;
-        let two_z = 2.0 * z;
-
-        let two_x_squared = two_x.powf(2.0);
-        let two_y_squared = two_y.powf(2.0);
-        let two_z_squared = two_z.powf(2.0);
-
-        let scale_x = 1.0 - two_y_squared - two_z_squared;
-        let scale_y = 1.0 - two_x_squared - two_z_squared;
-        let scale_z = 1.0 - two_x_squared - two_y_squared;
-
-        let two_x_y = two_x * y;
-        let two_x_z = two_x * y;
-        let two_y_z = two_y * z;
-        let two_x_w = two_x * w;
-        let two_y_w = two_y * w;
-        let two_z_w = two_z * w;
-
-        let m01 = two_x_y + two_z_w;
-        let m02 = two_x_z - two_y_w;
-        let m10 = two_x_y - two_z_w;
-        let m12 = two_y_z + two_x_w;
-        let m20 = two_x_z + two_y_w;
-        let m21 = two_y_z - two_x_w;
-
-        Self([
-            scale_x, m01    , m02    , 0.0,
-            m10    , scale_y, m12    , 0.0,
-            m20    , m21    , scale_z, 0.0,
-            0.0    , 0.0    , 0.0    , 1.0,
-        ])
+    pub fn rotation(rotation: &TQuat<S>) -> Self {
+        Self::from(TMat3::rotation(rotation))
     }
 
     #[rustfmt::skip]
-    pub fn scale(scale: &Scale3) -> Self {
-        let Scale3 { w, h, d } = scale.borrow();
+    pub fn scale(scale: &TScale3<S>) -> Self {
+        let TScale3 { w, h, d } = *scale;
+        let (zero, one) = (S::ZERO, S::ONE);
 
         Self([
-            *w , 0.0, 0.0, 0.0,
-            0.0, *h , 0.0, 0.0,
-            0.0, 0.0, *d , 0.0,
-            0.0, 0.0, 0.0, 1.0,
+            w   , zero, zero, zero,
+            zero, h   , zero, zero,
+            zero, zero, d   , zero,
+            zero, zero, zero, one ,
         ])
     }
 
     #[rustfmt::skip]
-    pub fn perspective(aspect_ratio: f32, field_of_view: Angle, near: f32, far: f32) -> Self {
-        let tan_half_fov = field_of_view.into_radians().tan() * 0.5;
+    pub fn perspective(aspect_ratio: S, field_of_view: Angle, near: S, far: S) -> Self {
+        let tan_half_fov = S::from_f32((field_of_view * 0.5).into_radians()).tan();
+        let (zero, one) = (S::ZERO, S::ONE);
 
         let x_scale = (tan_half_fov * aspect_ratio).recip();
         let y_scale = tan_half_fov.recip();
         let z_scale = (-near - far) / (near - far);
 
-        let z_translation = 2.0 * near * far / (near - far);
+        let z_translation = S::TWO * near * far / (near - far);
 
         Self([
-            x_scale, 0.0    , 0.0          , 0.0,
-            0.0    , y_scale, 0.0          , 0.0,
-            0.0    , 0.0    , z_scale      , 1.0,
-            0.0    , 0.0    , z_translation, 0.0,
+            x_scale, zero   , zero         , zero,
+            zero   , y_scale, zero         , zero,
+            zero   , zero   , z_scale      , one ,
+            zero   , zero   , z_translation, zero,
         ])
     }
 
     #[rustfmt::skip]
-    pub fn orthographic(left: f32, right: f32, top: f32, bottom: f32, near: f32, far: f32) -> Self {
+    pub fn orthographic(left: S, right: S, top: S, bottom: S, near: S, far: S) -> Self {
+        let (zero, one, two) = (S::ZERO, S::ONE, S::TWO);
+
         let r_l = right - left;
         let t_b = top - bottom;
         let f_n = far - near;
 
-        let x_scale =  2.0 / r_l;
-        let y_scale =  2.0 / t_b;
-        let z_scale = -2.0 / f_n;
+        let x_scale =  two / r_l;
+        let y_scale =  two / t_b;
+        let z_scale = -two / f_n;
 
         let x_translation = -((right + left)   / r_l);
         let y_translation = -((top   + bottom) / t_b);
         let z_translation = -((far   + near)   / f_n);
 
         Self([
-            x_scale      , 0.0          , 0.0          , 0.0,
-            0.0          , y_scale      , 0.0          , 0.0,
-            0.0          , 0.0          , z_scale      , 0.0,
-            x_translation, y_translation, z_translation, 1.0,
+            x_scale      , zero         , zero         , zero,
+            zero         , y_scale      , zero         , zero,
+            zero         , zero         , z_scale      , zero,
+            x_translation, y_translation, z_translation, one ,
         ])
     }
 
+    /// A view matrix for a camera at `eye` looking towards `target`, with `up` roughly above it.
+    pub fn look_at(eye: &TPoint3<S>, target: &TPoint3<S>, up: &TVec3<S>) -> Self {
+        let forward = eye.vector_to(target);
+        let forward = forward / forward.norm();
+        let right = up.cross(&forward);
+        let right = right / right.norm();
+        let up = forward.cross(&right);
+
+        Self::view(forward, up, right, *eye)
+    }
+
+    /// A view matrix for a camera at `position` with the given orthonormal axes. Moves the world
+    /// so the camera sits at the origin looking down +Z, with +Y up and +X to its right.
     #[rustfmt::skip]
-    pub fn look_at(camera: &Camera, target: &Vec3) -> Self {
-        // let z_axis = < hector done goofed
+    pub fn view(forward: TVec3<S>, up: TVec3<S>, right: TVec3<S>, position: TPoint3<S>) -> Self {
+        let position = position.vector_from_origin();
+        let (zero, one) = (S::ZERO, S::ONE);
 
+        let x_translation = -right  .dot(&position);
+        let y_translation = -up     .dot(&position);
+        let z_translation = -forward.dot(&position);
 
         Self([
-            1.0, 0.0, 0.0, 0.0,
-            0.0, 1.0, 0.0, 0.0,
-            0.0, 0.0, 1.0, 0.0,
-            0.0, 0.0, 0.0, 1.0,
+            right.x      , up.x         , forward.x    , zero,
+            right.y      , up.y         , forward.y    , zero,
+            right.z      , up.z         , forward.z    , zero,
+            x_translation, y_translation, z_translation, one ,
         ])
     }
 
     #[rustfmt::skip]
-    pub fn view(forward: Vec3, up: Vec3, right: Vec3, position: Point3) -> Self {
+    pub fn from_cols(x: &TVec4<S>, y: &TVec4<S>, z: &TVec4<S>, w: &TVec4<S>) -> Self {
         Self([
-             right   .x,  right   .y,  right   .z, 0.0,
-             up      .x,  up      .y,  up      .z, 0.0,
-             forward .x,  forward .y,  forward .z, 0.0,
-            -position.x, -position.y, -position.z, 1.0,
+            x.x, x.y, x.z, x.w,
+            y.x, y.y, y.z, y.w,
+            z.x, z.y, z.z, z.w,
+            w.x, w.y, w.z, w.w,
         ])
     }
 
-    fn row(&self, index: usize) -> &[f32] {
-        &[
-            self.0[index + 0],
-            self.0[index + 4],
-            self.0[index + 8],
-            self.0[index + 12],
-        ]
+    pub fn row(&self, index: usize) -> TVec4<S> {
+        TVec4::new(
+            self.0[index],
+            self.0[index + MATRIX_WIDTH],
+            self.0[index + 2 * MATRIX_WIDTH],
+            self.0[index + 3 * MATRIX_WIDTH],
+        )
     }
 
-    #[inline(always)]
-    pub fn col(&self, index: usize) -> &[f32] {
-        &self.0[index..index + 4]
-    }
+    pub fn col(&self, index: usize) -> TVec4<S> {
+        let start = index * MATRIX_WIDTH;
 
-    // fn row(&self, index: usize) -> (f32, f32, f32, f32) {
-    //     (
-    //         self.0[index + 0],
-    //         self.0[index + 4],
-    //         self.0[index + 8],
-    //         self.0[index + 12],
-    //     )
-    // }
+        TVec4::new(
+            self.0[start],
+            self.0[start + 1],
+            self.0[start + 2],
+            self.0[start + 3],
+        )
+    }
 
-    #[inline(always)]
-    pub fn row_0(&self) -> &[f32] {
-        self.row(0)
+    pub fn transpose(&self) -> Self {
+        Self::from_cols(&self.row(0), &self.row(1), &self.row(2), &self.row(3))
     }
 
-    // #[inline(always)]
-    // pub fn row_0(&self) -> (f32, f32, f32, f32) {
-    //     self.row(0)
-    // }
+    /// Transforms a point by the matrix, translation included.
+    pub fn transform_point(&self, point: &TPoint3<S>) -> TPoint3<S> {
+        let TVec4 { x, y, z, .. } = *self * TVec4::new(point.x, point.y, point.z, S::ONE);
 
-    // #[inline(always)]
-    // pub fn row_1(&self) -> (f32, f32, f32, f32) {
-    //     self.row(1)
-    // }
+        TPoint3::new(x, y, z)
+    }
 
-    // #[inline(always)]
-    // pub fn row_2(&self) -> (f32, f32, f32, f32) {
-    //     self.row(2)
-    // }
+    /// Transforms a direction by the matrix, ignoring translation.
+    pub fn transform_vector(&self, vector: &TVec3<S>) -> TVec3<S> {
+        (*self * vector.extend(S::ZERO)).truncate()
+    }
 
-    // #[inline(always)]
-    // pub fn row_3(&self) -> (f32, f32, f32, f32) {
-    //     self.row(3)
-    // }
+    /// Splits a matrix built from a translation, a rotation and a scale, in that order, back into
+    /// its parts. Matrices with shear or perspective have no such parts and give meaningless
+    /// results.
+    pub fn decompose(&self) -> (TVec3<S>, TQuat<S>, TScale3<S>) {
+        let translation = self.col(3).truncate();
 
-    #[inline(always)]
-    pub fn col_0(&self, index: usize) -> &[f32] {
-        &self.0[0..4]
-    }
+        let x = self.col(0).truncate();
+        let y = self.col(1).truncate();
+        let z = self.col(2).truncate();
 
-    #[inline(always)]
-    pub fn col_1(&self, index: usize) -> &[f32] {
-        &self.0[4..8]
-    }
+        // NOTE:
+        // A negative determinant means the matrix mirrors, which can't be represented by a
+        // rotation so one of the axes is flipped in the scale instead.
+        let sign = if self.determinant() < S::ZERO { S::NEG_ONE } else { S::ONE };
+        let scale = TScale3::new(x.norm() * sign, y.norm(), z.norm());
 
-    #[inline(always)]
-    pub fn col_2(&self, index: usize) -> &[f32] {
-        &self.0[8..12]
-    }
+        let rotation =
+            TQuat::from(TMat3::from_cols(&(x / scale.w), &(y / scale.h), &(z / scale.d)));
 
-    #[inline(always)]
-    pub fn col_3(&self, index: usize) -> &[f32] {
-        &self.0[12..16]
+        (translation, rotation, scale)
     }
 
     #[rustfmt::skip]
-    pub fn determinant(&self) -> f32 {
+    pub fn determinant(&self) -> S {
         // The determinant of a matrix is the sum of the products of the elements of any one row or
         // column and their cofactors. Therefore if all elements in any row or column are 0, then
         // the determinant is 0. This also means that the fastest way to calculate the determinant
@@ -237,73 +219,36 @@ impl Mat4 {
             m30, m31, m32, m33,
         ]) = *self;
 
-        let c0 = m00 * Vec3::new(m11, m12, m13).cross(&Vec3::new(m21, m22, m23)).dot(&Vec3::new(m31, m32, m33));
-        let c1 = m01 * Vec3::new(m10, m12, m13).cross(&Vec3::new(m20, m22, m23)).dot(&Vec3::new(m30, m32, m33));
-        let c2 = m02 * Vec3::new(m10, m11, m13).cross(&Vec3::new(m20, m21, m23)).dot(&Vec3::new(m30, m31, m33));
-        let c3 = m03 * Vec3::new(m10, m11, m12).cross(&Vec3::new(m20, m21, m22)).dot(&Vec3::new(m30, m31, m32));
+        let c0 = m00 * TVec3::new(m11, m12, m13).cross(&TVec3::new(m21, m22, m23)).dot(&TVec3::new(m31, m32, m33));
+        let c1 = m01 * TVec3::new(m10, m12, m13).cross(&TVec3::new(m20, m22, m23)).dot(&TVec3::new(m30, m32, m33));
+        let c2 = m02 * TVec3::new(m10, m11, m13).cross(&TVec3::new(m20, m21, m23)).dot(&TVec3::new(m30, m31, m33));
+        let c3 = m03 * TVec3::new(m10, m11, m12).cross(&TVec3::new(m20, m21, m22)).dot(&TVec3::new(m30, m31, m32));
 
         c0 - c1 + c2 - c3
     }
 
-    #[rustfmt::skip]
     pub fn inverse(&self) -> Self {
-        let Self([
-            m00, m01, m02, m03,
-            m10, m11, m12, m13,
-            m20, m21, m22, m23,
-            m30, m31, m32, m33,
-        ]) = *self;
-
-        // Upper 2D matrix determinants.
-        let b00 = m00 * m11 - m01 * m10;
-        let b01 = m00 * m12 - m02 * m10;
-        let b02 = m00 * m13 - m03 * m10;
-        let b03 = m01 * m12 - m02 * m11;
-        let b04 = m01 * m13 - m03 * m11;
-        let b05 = m02 * m13 - m03 * m12;
-
-        // Lower 2D matrix determinants.
-        let b06 = m20 * m31 - m21 * m30;
-        let b07 = m20 * m32 - m22 * m30;
-        let b08 = m20 * m33 - m23 * m30;
-        let b09 = m21 * m32 - m22 * m31;
-        let b10 = m21 * m33 - m23 * m31;
-        let b11 = m22 * m33 - m23 * m32;
-
-        let determinant = b00 * b11
-                        - b01 * b10
-                        + b02 * b09
-                        + b03 * b08
-                        - b04 * b07
-                        + b05 * b06;
-
-        let reciprocal = determinant.recip();
-
-        let data = [
-            (m11 * b11 - m12 * b10 + m13 * b09) * reciprocal,
-            (m02 * b10 - m01 * b11 - m03 * b09) * reciprocal,
-            (m31 * b05 - m32 * b04 + m33 * b03) * reciprocal,
-            (m22 * b04 - m21 * b05 - m23 * b03) * reciprocal,
-            (m12 * b08 - m10 * b11 - m13 * b07) * reciprocal,
-            (m00 * b11 - m02 * b08 + m03 * b07) * reciprocal,
-            (m32 * b02 - m30 * b05 - m33 * b01) * reciprocal,
-            (m20 * b05 - m22 * b02 + m23 * b01) * reciprocal,
-            (m10 * b10 - m11 * b08 + m13 * b06) * reciprocal,
-            (m01 * b08 - m00 * b10 - m03 * b06) * reciprocal,
-            (m30 * b04 - m31 * b02 + m33 * b00) * reciprocal,
-            (m21 * b02 - m20 * b04 - m23 * b00) * reciprocal,
-            (m11 * b07 - m10 * b09 - m12 * b06) * reciprocal,
-            (m00 * b09 - m01 * b07 + m02 * b06) * reciprocal,
-            (m31 * b01 - m30 * b03 - m32 * b00) * reciprocal,
-            (m20 * b03 - m21 * b01 + m22 * b00) * reciprocal,
-        ];
+        Self(S::mat4_inverse(&self.0))
+    }
 
-        Self(data)
+    /// Converts to another scalar type, e.g. an `f64` model matrix to `f32` for the GPU.
+    pub fn cast<T>(&self) -> TMat4<T>
+    where
+        T: Scalar,
+    {
+        TMat4(self.0.map(|element| T::from_f64(element.to_f64())))
     }
 }
 
-impl Mul for Mat4 {
-    type Output = Mat4;
+unsafe impl<S: Scalar> Pod for TMat4<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TMat4<S> {}
+
+impl<S> Mul for TMat4<S>
+where
+    S: Scalar,
+{
+    type Output = TMat4<S>;
 
     fn mul(mut self, rhs: Self) -> Self::Output {
         self *= &rhs;
@@ -311,47 +256,165 @@ impl Mul for Mat4 {
     }
 }
 
-impl<M> MulAssign<M> for Mat4
+impl<S, M> MulAssign<M> for TMat4<S>
 where
-    M: Borrow<Mat4>,
+    S: Scalar,
+    M: Borrow<TMat4<S>>,
 {
-    #[rustfmt::skip]
     fn mul_assign(&mut self, rhs: M) {
-        let rhs = rhs.borrow();
+        self.0 = S::mat4_mul(&self.0, &rhs.borrow().0);
+    }
+}
 
-        for i in 0..MATRIX_WIDTH {
-            let row = self.row(i);
+impl<S> Mul<TVec4<S>> for TMat4<S>
+where
+    S: Scalar,
+{
+    type Output = TVec4<S>;
+
+    fn mul(self, rhs: TVec4<S>) -> Self::Output {
+        let [x, y, z, w] = S::mat4_transform(&self.0, &[rhs.x, rhs.y, rhs.z, rhs.w]);
+
+        TVec4::new(x, y, z, w)
+    }
+}
 
-            for j in 0..MATRIX_WIDTH {
-                let col = rhs.col(j);
+impl<S> Index<usize> for TMat4<S> {
+    type Output = S;
 
-                self.0[(j * MATRIX_WIDTH) + i]
-                    = col[0] * row[0]
-                    + col[1] * row[1]
-                    + col[2] * row[2]
-                    + col[3] * row[3];
-            }
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
 
+impl<S> From<[S; MATRIX_SIZE]> for TMat4<S> {
+    fn from(data: [S; MATRIX_SIZE]) -> Self {
+        Self(data)
+    }
+}
 
-            // let m0i = self.0[i + 0];
-            // let m1i = self.0[i + 4];
-            // let m2i = self.0[i + 8];
-            // let m3i = self.0[i + 12];
+impl<S> From<TMat3<S>> for TMat4<S>
+where
+    S: Scalar,
+{
+    #[rustfmt::skip]
+    fn from(matrix: TMat3<S>) -> Self {
+        let TMat3([
+            m00, m01, m02,
+            m10, m11, m12,
+            m20, m21, m22,
+        ]) = matrix;
+        let (zero, one) = (S::ZERO, S::ONE);
 
-            // for j in (0..MATRIX_SIZE).step_by(4) {
-            //     self.0[j + i] = rhs.0[j + 0] * m0i
-            //                   + rhs.0[j + 1] * m1i
-            //                   + rhs.0[j + 2] * m2i
-            //                   + rhs.0[j + 3] * m3i;
-            // }
-        }
+        Self([
+            m00 , m01 , m02 , zero,
+            m10 , m11 , m12 , zero,
+            m20 , m21 , m22 , zero,
+            zero, zero, zero, one ,
+        ])
     }
 }
 
-impl Index<usize> for Mat4 {
-    type Output = f32;
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_approx_eq,
+        core::maths::{DScale3, DVec3, Point3, Quat, Scale3, Unit, Vec3},
+    };
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.0[index]
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn rotation() -> Quat {
+        let axis = Vec3::new(1.0, -2.0, 0.5);
+
+        Quat::from_axis_angle(&Unit::from_normalised(axis / axis.norm()), Angle::radians(0.8))
+    }
+
+    fn transformation() -> Mat4 {
+        Mat4::translation(&Vec3::new(1.0, 2.0, 3.0))
+            * Mat4::rotation(&rotation())
+            * Mat4::scale(&Scale3::new(2.0, 3.0, 4.0))
+    }
+
+    #[test]
+    fn multiplication_applies_the_right_matrix_first() {
+        let matrix = Mat4::translation(&Vec3::new(1.0, 0.0, 0.0))
+            * Mat4::scale(&Scale3::new(2.0, 2.0, 2.0));
+        let Point3 { x, y, z } = matrix.transform_point(&Point3::new(1.0, 1.0, 1.0));
+
+        assert_eq!([x, y, z], [3.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn rotation_matches_quaternion() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let rotated = Mat4::rotation(&rotation()).transform_vector(&vector);
+
+        assert_approx_eq!(rotated, vector.rotate(&rotation()), epsilon = EPSILON);
+    }
+
+    #[test]
+    fn transpose() {
+        let matrix = transformation();
+
+        assert_eq!(matrix.transpose().row(3).truncate().x, matrix.col(3).x);
+        assert_approx_eq!(matrix.transpose().transpose(), matrix);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = transformation();
+
+        assert_approx_eq!(matrix * matrix.inverse(), Mat4::identity(), epsilon = EPSILON);
+        assert_approx_eq!(matrix.inverse() * matrix, Mat4::identity(), epsilon = EPSILON);
+        assert_approx_eq!(matrix.determinant(), 24.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn look_at_moves_the_target_in_front_of_the_camera() {
+        let eye = Point3::new(1.0, 2.0, 3.0);
+        let target = Point3::new(4.0, 2.0, 7.0);
+        let view = Mat4::look_at(&eye, &target, &Vec3::unit_y());
+
+        let target = view.transform_point(&target);
+        assert_approx_eq!(target, Point3::new(0.0, 0.0, 5.0), epsilon = EPSILON);
+
+        let eye = view.transform_point(&eye);
+        assert_approx_eq!(eye, Point3::new(0.0, 0.0, 0.0), epsilon = EPSILON);
+    }
+
+    #[test]
+    fn decompose() {
+        let (translation, rotation, scale) = transformation().decompose();
+
+        assert_approx_eq!(translation, Vec3::new(1.0, 2.0, 3.0), epsilon = EPSILON);
+        assert_approx_eq!(scale, Scale3::new(2.0, 3.0, 4.0), epsilon = EPSILON);
+        assert_approx_eq!(rotation, self::rotation(), epsilon = EPSILON);
+    }
+
+    #[test]
+    fn decompose_mirrored() {
+        let matrix = Mat4::rotation(&rotation()) * Mat4::scale(&Scale3::new(-1.0, 1.0, 1.0));
+        let (translation, rotation, scale) = matrix.decompose();
+        let rebuilt =
+            Mat4::translation(&translation) * Mat4::rotation(&rotation) * Mat4::scale(&scale);
+
+        assert_eq!(scale.w, -1.0);
+        assert_approx_eq!(rebuilt, matrix, epsilon = EPSILON);
+    }
+
+    #[test]
+    fn doubles() {
+        let matrix = DMat4::translation(&DVec3::new(1e9, 2.0, 3.0))
+            * DMat4::rotation(&rotation().cast())
+            * DMat4::scale(&DScale3::new(2.0, 3.0, 4.0));
+        let expected = Mat4::translation(&Vec3::new(1e9, 2.0, 3.0))
+            * Mat4::rotation(&rotation())
+            * Mat4::scale(&Scale3::new(2.0, 3.0, 4.0));
+
+        assert_approx_eq!(matrix * matrix.inverse(), DMat4::identity(), epsilon = 1e-6);
+        assert_approx_eq!(matrix.cast::<f32>(), expected, epsilon = 1e-5);
     }
 }
@@ -0,0 +1,89 @@
+use crate::core::maths::{Line3, Normed, Point3, Unit, Vec3};
+
+/// An infinite plane, every point `p` on it satisfying `normal.dot(p) == distance`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    pub normal:   Unit<Vec3>,
+    /// The signed distance from the origin to the plane along the normal.
+    pub distance: f32,
+}
+
+impl Plane {
+    pub const fn new(normal: Unit<Vec3>, distance: f32) -> Self {
+        Self { normal, distance }
+    }
+
+    pub fn from_point_normal(point: &Point3, normal: Unit<Vec3>) -> Self {
+        Self::new(normal, normal.dot(&point.vector_from_origin()))
+    }
+
+    /// The plane through three points, facing the side they appear clockwise from, as triangles'
+    /// front faces do. The points must not lie on a line.
+    pub fn from_points(a: &Point3, b: &Point3, c: &Point3) -> Self {
+        let normal = a.vector_to(b).cross(&a.vector_to(c));
+
+        Self::from_point_normal(a, Unit::from_normalised(normal / normal.norm()))
+    }
+
+    /// The distance from the plane to `point`, positive on the side the normal faces.
+    pub fn signed_distance(&self, point: &Point3) -> f32 {
+        self.normal.dot(&point.vector_from_origin()) - self.distance
+    }
+
+    pub fn distance_to(&self, point: &Point3) -> f32 {
+        self.signed_distance(point).abs()
+    }
+
+    /// The point on the plane closest to `point`.
+    pub fn project(&self, point: &Point3) -> Point3 {
+        *point + -self.signed_distance(point) * self.normal
+    }
+
+    /// Where the line crosses the plane, `None` if it runs parallel to it.
+    pub fn intersection(&self, line: &Line3) -> Option<Point3> {
+        let alignment = self.normal.dot(&line.direction);
+
+        if alignment.abs() < 1e-6 {
+            return None;
+        }
+
+        Some(line.point_at(-self.signed_distance(&line.origin) / alignment))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_points() {
+        let plane = Plane::from_points(
+            &Point3::new(0.0, 2.0, 0.0),
+            &Point3::new(0.0, 2.0, 1.0),
+            &Point3::new(1.0, 2.0, 0.0),
+        );
+
+        assert_eq!([plane.normal.x, plane.normal.y, plane.normal.z], [0.0, 1.0, 0.0]);
+        assert_eq!(plane.distance, 2.0);
+        assert_eq!(plane.signed_distance(&Point3::new(5.0, -1.0, 5.0)), -3.0);
+    }
+
+    #[test]
+    fn project() {
+        let plane = Plane::from_point_normal(&Point3::new(0.0, 0.0, 1.0), Vec3::unit_z());
+        let Point3 { x, y, z } = plane.project(&Point3::new(3.0, 4.0, 5.0));
+
+        assert_eq!([x, y, z], [3.0, 4.0, 1.0]);
+    }
+
+    #[test]
+    fn intersection() {
+        let plane = Plane::new(Vec3::unit_y(), 1.0);
+        let line = Line3::through(&Point3::new(0.0, 3.0, 0.0), &Point3::new(1.0, 2.0, 0.0));
+        let Point3 { x, y, z } = plane.intersection(&line).unwrap();
+
+        assert!((x - 2.0).abs() < 1e-5 && (y - 1.0).abs() < 1e-5 && z == 0.0);
+        assert!(plane.intersection(&Line3::new(Point3::identity(), Vec3::unit_x())).is_none());
+    }
+}
@@ -2,65 +2,89 @@ use std::ops::{Add, AddAssign};
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::core::maths::Vec2;
+use crate::core::maths::{Scalar, TVec2};
+
+pub type Point2 = TPoint2<f32>;
+pub type DPoint2 = TPoint2<f64>;
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Point2 {
-    pub x: f32,
-    pub y: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TPoint2<S> {
+    pub x: S,
+    pub y: S,
 }
 
-impl Point2 {
-    pub const fn new(x: f32, y: f32) -> Self {
+impl<S> TPoint2<S>
+where
+    S: Scalar,
+{
+    pub const fn new(x: S, y: S) -> Self {
         Self { x, y }
     }
 
-    pub const fn splat(value: f32) -> Self {
+    pub const fn splat(value: S) -> Self {
         Self::new(value, value)
     }
 
     pub const fn identity() -> Self {
-        Self::new(0.0, 0.0)
+        Self::new(S::ZERO, S::ZERO)
+    }
+
+    pub const fn vector_from_origin(&self) -> TVec2<S> {
+        TVec2::new(self.x, self.y)
     }
 
-    pub const fn vector_from_origin(&self) -> Vec2 {
-        Vec2::new(self.x, self.y)
+    pub fn vector_from(&self, rhs: &Self) -> TVec2<S> {
+        TVec2::new(self.x - rhs.x, self.y - rhs.y)
     }
 
-    pub fn vector_from(&self, rhs: &Self) -> Vec2 {
-        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    pub fn vector_to(&self, rhs: &Self) -> TVec2<S> {
+        TVec2::new(rhs.x - self.x, rhs.y - self.y)
     }
 
-    pub fn vector_to(&self, rhs: &Self) -> Vec2 {
-        Vec2::new(rhs.x - self.x, rhs.y - self.y)
+    /// Converts to another scalar type, e.g. an `f64` world position to `f32` for the GPU.
+    pub fn cast<T>(&self) -> TPoint2<T>
+    where
+        T: Scalar,
+    {
+        TPoint2::new(T::from_f64(self.x.to_f64()), T::from_f64(self.y.to_f64()))
     }
 }
 
-impl Add<Vec2> for Point2 {
-    type Output = Point2;
+unsafe impl<S: Scalar> Pod for TPoint2<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TPoint2<S> {}
+
+impl<S> Add<TVec2<S>> for TPoint2<S>
+where
+    S: Scalar,
+{
+    type Output = TPoint2<S>;
 
-    fn add(mut self, rhs: Vec2) -> Self::Output {
+    fn add(mut self, rhs: TVec2<S>) -> Self::Output {
         self += rhs;
         self
     }
 }
 
-impl AddAssign<Vec2> for Point2 {
-    fn add_assign(&mut self, rhs: Vec2) {
+impl<S> AddAssign<TVec2<S>> for TPoint2<S>
+where
+    S: Scalar,
+{
+    fn add_assign(&mut self, rhs: TVec2<S>) {
         self.x += rhs.x;
         self.y += rhs.y;
     }
 }
 
-impl From<[f32; 2]> for Point2 {
-    fn from([x, y]: [f32; 2]) -> Self {
+impl<S> From<[S; 2]> for TPoint2<S> {
+    fn from([x, y]: [S; 2]) -> Self {
         Self { x, y }
     }
 }
 
-impl From<(f32, f32)> for Point2 {
-    fn from((x, y): (f32, f32)) -> Self {
+impl<S> From<(S, S)> for TPoint2<S> {
+    fn from((x, y): (S, S)) -> Self {
         Self { x, y }
     }
 }
@@ -2,67 +2,95 @@ use std::ops::{Add, AddAssign};
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::core::maths::Vec3;
+use crate::core::maths::{Scalar, TVec3};
+
+pub type Point3 = TPoint3<f32>;
+pub type DPoint3 = TPoint3<f64>;
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Point3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TPoint3<S> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
 }
 
-impl Point3 {
-    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+impl<S> TPoint3<S>
+where
+    S: Scalar,
+{
+    pub const fn new(x: S, y: S, z: S) -> Self {
         Self { x, y, z }
     }
 
-    pub const fn splat(value: f32) -> Self {
+    pub const fn splat(value: S) -> Self {
         Self::new(value, value, value)
     }
 
     pub const fn identity() -> Self {
-        Self::new(0.0, 0.0, 0.0)
+        Self::new(S::ZERO, S::ZERO, S::ZERO)
+    }
+
+    pub const fn vector_from_origin(&self) -> TVec3<S> {
+        TVec3::new(self.x, self.y, self.z)
     }
 
-    pub const fn vector_from_origin(&self) -> Vec3 {
-        Vec3::new(self.x, self.y, self.z)
+    pub fn vector_from(&self, rhs: &Self) -> TVec3<S> {
+        TVec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
     }
 
-    pub fn vector_from(&self, rhs: &Self) -> Vec3 {
-        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    pub fn vector_to(&self, rhs: &Self) -> TVec3<S> {
+        TVec3::new(rhs.x - self.x, rhs.y - self.y, rhs.z - self.z)
     }
 
-    pub fn vector_to(&self, rhs: &Self) -> Vec3 {
-        Vec3::new(rhs.x - self.x, rhs.y - self.y, rhs.z - self.z)
+    /// Converts to another scalar type, e.g. an `f64` world position to `f32` for the GPU.
+    pub fn cast<T>(&self) -> TPoint3<T>
+    where
+        T: Scalar,
+    {
+        TPoint3::new(
+            T::from_f64(self.x.to_f64()),
+            T::from_f64(self.y.to_f64()),
+            T::from_f64(self.z.to_f64()),
+        )
     }
 }
 
-impl Add<Vec3> for Point3 {
-    type Output = Point3;
+unsafe impl<S: Scalar> Pod for TPoint3<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TPoint3<S> {}
+
+impl<S> Add<TVec3<S>> for TPoint3<S>
+where
+    S: Scalar,
+{
+    type Output = TPoint3<S>;
 
-    fn add(mut self, rhs: Vec3) -> Self::Output {
+    fn add(mut self, rhs: TVec3<S>) -> Self::Output {
         self += rhs;
         self
     }
 }
 
-impl AddAssign<Vec3> for Point3 {
-    fn add_assign(&mut self, rhs: Vec3) {
+impl<S> AddAssign<TVec3<S>> for TPoint3<S>
+where
+    S: Scalar,
+{
+    fn add_assign(&mut self, rhs: TVec3<S>) {
         self.x += rhs.x;
         self.y += rhs.y;
         self.z += rhs.z;
     }
 }
 
-impl From<[f32; 3]> for Point3 {
-    fn from([x, y, z]: [f32; 3]) -> Self {
+impl<S> From<[S; 3]> for TPoint3<S> {
+    fn from([x, y, z]: [S; 3]) -> Self {
         Self { x, y, z }
     }
 }
 
-impl From<(f32, f32, f32)> for Point3 {
-    fn from((x, y, z): (f32, f32, f32)) -> Self {
+impl<S> From<(S, S, S)> for TPoint3<S> {
+    fn from((x, y, z): (S, S, S)) -> Self {
         Self { x, y, z }
     }
 }
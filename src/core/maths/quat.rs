@@ -1,52 +1,234 @@
-use std::{
-    borrow::Borrow,
-    ops::{Mul, MulAssign},
-};
+use std::ops::{Mul, MulAssign};
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::core::maths::{Angle, Normed, Unit, Vec3};
+use crate::core::maths::{Angle, Normed, Scalar, TMat3, TMat4, TVec3, Unit};
+
+// NOTE:
+// Below this the sine of the angle between two rotations is too small to divide by, and the
+// rotations are close enough that interpolating linearly is just as good.
+const SLERP_THRESHOLD: f64 = 0.9995;
+
+/// The order rotations about each axis are applied in, e.g. `Xyz` rotates about X first and Z
+/// last. Every rotation is about the fixed world axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationOrder {
+    Xyz,
+    Xzy,
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx,
+}
+
+impl RotationOrder {
+    // The axes in the order they're applied, and whether that's an even permutation of XYZ.
+    fn axes(self) -> ([usize; 3], bool) {
+        match self {
+            RotationOrder::Xyz => ([0, 1, 2], true),
+            RotationOrder::Xzy => ([0, 2, 1], false),
+            RotationOrder::Yxz => ([1, 0, 2], false),
+            RotationOrder::Yzx => ([1, 2, 0], true),
+            RotationOrder::Zxy => ([2, 0, 1], true),
+            RotationOrder::Zyx => ([2, 1, 0], false),
+        }
+    }
+}
+
+pub type Quat = TQuat<f32>;
+pub type DQuat = TQuat<f64>;
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Quat {
-    pub v: Vec3,
-    pub w: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TQuat<S> {
+    pub v: TVec3<S>,
+    pub w: S,
 }
 
-impl Quat {
+impl<S> TQuat<S>
+where
+    S: Scalar,
+{
     pub const fn identity() -> Self {
         Self {
-            w: 1.0,
-            v: Vec3::zero(),
+            w: S::ONE,
+            v: TVec3::zero(),
         }
     }
 
-    pub const fn new(w: f32, v: Vec3) -> Self {
+    pub const fn new(w: S, v: TVec3<S>) -> Self {
         Self { w, v }
     }
 
-    pub fn lerp(start: &Self, end: &Self, t: f32) -> Unit<Self> {
-        let mid = (1.0 - t) * start + t * end;
+    pub fn lerp(start: &Self, end: &Self, t: S) -> Unit<Self> {
+        let mid = start * (S::ONE - t) + end * t;
         let mid = mid / mid.norm();
 
         Unit::new(mid)
     }
 
-    pub fn from_axis_angle(axis: &Unit<Vec3>, angle: Angle) -> Self {
+    /// Interpolates linearly and normalises, taking the shortest path between the rotations. Faster
+    /// than `slerp` but the rotation speeds up towards the middle.
+    pub fn nlerp(start: &Self, end: &Self, t: S) -> Unit<Self> {
+        let end = if start.dot(end) < S::ZERO { -end } else { *end };
+        let mid = start * (S::ONE - t) + end * t;
+
+        Unit::from_normalised(mid / mid.norm())
+    }
+
+    /// Interpolates along the shortest arc between the rotations at a constant angular speed.
+    pub fn slerp(start: &Self, end: &Self, t: S) -> Unit<Self> {
+        let mut cos = start.dot(end);
+        let mut end = *end;
+
+        // NOTE:
+        // q and -q are the same rotation, flipping one of them when they're more than 90 degrees
+        // apart gives the shorter of the two arcs between them.
+        if cos < S::ZERO {
+            cos = -cos;
+            end = -end;
+        }
+
+        if cos > S::from_f64(SLERP_THRESHOLD) {
+            return Self::nlerp(start, &end, t);
+        }
+
+        let angle = cos.acos();
+        let start_weight = ((S::ONE - t) * angle).sin();
+        let end_weight = (t * angle).sin();
+
+        Unit::from_normalised((start * start_weight + end * end_weight) / angle.sin())
+    }
+
+    pub fn from_axis_angle(axis: &Unit<TVec3<S>>, angle: Angle) -> Self {
         let (half_sin, half_cos) = (angle * 0.5).sin_cos();
 
         Self {
-            w: half_cos,
-            v: half_sin * axis,
+            w: S::from_f32(half_cos),
+            v: axis * S::from_f32(half_sin),
+        }
+    }
+
+    /// Rotates about the X, Y and Z axes by the given angles, in `order`.
+    pub fn from_euler(order: RotationOrder, x: Angle, y: Angle, z: Angle) -> Self {
+        let rotations = [
+            Self::from_axis_angle(&TVec3::unit_x(), x),
+            Self::from_axis_angle(&TVec3::unit_y(), y),
+            Self::from_axis_angle(&TVec3::unit_z(), z),
+        ];
+
+        let ([first, second, third], _) = order.axes();
+
+        rotations[third] * rotations[second] * rotations[first]
+    }
+
+    /// The angles about the X, Y and Z axes that `from_euler` builds this rotation from. When the
+    /// second rotation is a quarter turn the first and third are about the same axis, so the third
+    /// is taken to be zero.
+    pub fn to_euler(&self, order: RotationOrder) -> (Angle, Angle, Angle) {
+        let ([i, j, k], even) = order.axes();
+        let sign = if even { S::ONE } else { S::NEG_ONE };
+
+        let matrix = TMat3::rotation(self);
+        let element = |row: usize, col: usize| matrix[col * 3 + row];
+
+        let mut angles = [S::ZERO; 3];
+        let sin_j = (-sign * element(k, i)).clamp(S::NEG_ONE, S::ONE);
+        angles[j] = sin_j.asin();
+
+        if sin_j.abs() < S::ONE - S::from_f32(1e-6) {
+            angles[i] = (sign * element(k, j)).atan2(element(k, k));
+            angles[k] = (sign * element(j, i)).atan2(element(i, i));
+        } else {
+            angles[i] = (-sign * element(j, k)).atan2(element(j, j));
         }
+
+        let [x, y, z] = angles.map(|angle| Angle::radians(angle.to_f32()));
+
+        (x, y, z)
+    }
+
+    /// Rolls about Z, then pitches about X, then yaws about Y, the usual order for cameras and
+    /// vehicles.
+    pub fn from_pitch_yaw_roll(pitch: Angle, yaw: Angle, roll: Angle) -> Self {
+        Self::from_euler(RotationOrder::Zxy, pitch, yaw, roll)
+    }
+
+    /// The rotation that turns +Z to face `forward`, keeping +Y as close to `up` as possible.
+    pub fn look_rotation(forward: &TVec3<S>, up: &TVec3<S>) -> Self {
+        let forward = *forward / forward.norm();
+        let right = up.cross(&forward);
+        let right = right / right.norm();
+        let up = forward.cross(&right);
+
+        Self::from(TMat3::from_cols(&right, &up, &forward))
+    }
+
+    /// The shortest rotation that turns `from` to face `to`.
+    pub fn from_rotation_arc(from: &Unit<TVec3<S>>, to: &Unit<TVec3<S>>) -> Self {
+        let cos = from.dot(to);
+
+        // NOTE:
+        // Opposite directions have no single shortest arc, any half turn about an axis
+        // perpendicular to them will do.
+        if cos < S::NEG_ONE + S::from_f32(1e-6) {
+            let axis = from.cross(&TVec3::unit_x());
+            let axis = if axis.norm_squared() < S::from_f32(1e-6) {
+                from.cross(&TVec3::unit_y())
+            } else {
+                axis
+            };
+
+            return Self::new(S::ZERO, axis / axis.norm());
+        }
+
+        let half_way = Self::new(S::ONE + cos, from.cross(to));
+
+        half_way / half_way.norm()
+    }
+
+    /// The axis and angle, from 0 to 2π, the rotation turns by. The identity rotation has no axis,
+    /// so X is returned for it.
+    pub fn to_axis_angle(&self) -> (Unit<TVec3<S>>, Angle) {
+        // NOTE:
+        // The length of v is the sine of half the angle. Taking it from v rather than from w keeps
+        // small angles accurate, since w is then too close to 1 to tell them apart.
+        let half_sin = self.v.norm();
+
+        let axis = if half_sin < S::from_f32(1e-6) {
+            TVec3::unit_x()
+        } else {
+            Unit::from_normalised(self.v / half_sin)
+        };
+
+        (axis, Angle::radians((S::TWO * half_sin.atan2(self.w)).to_f32()))
     }
 
-    // pub fn from_pitch_yaw_roll(pitch: f32, yaw: f32, roll: f32) -> Self {}
+    /// Splits the rotation into a twist about `axis` and a swing about an axis perpendicular to
+    /// it, such that `self == swing * twist`. Useful for limiting how far joints can twist.
+    pub fn swing_twist(&self, axis: &Unit<TVec3<S>>) -> (Self, Self) {
+        let projection = **axis * self.v.dot(axis);
+        let twist = Self::new(self.w, projection);
+        let norm = twist.norm();
+
+        // NOTE:
+        // A half turn swing leaves nothing of the twist, any twist is then as good as another.
+        let twist = if norm < S::from_f32(1e-6) {
+            Self::identity()
+        } else {
+            twist / norm
+        };
+
+        (self * twist.conjugate(), twist)
+    }
+
+    pub fn dot(&self, rhs: &Self) -> S {
+        self.v.dot(&rhs.v) + self.w * rhs.w
+    }
 
     pub fn conjugate(&self) -> Self {
         Self {
-            v: self.v * -1.0,
+            v: -self.v,
             w: self.w,
         }
     }
@@ -57,10 +239,28 @@ impl Quat {
         // norm_squared() is 1 -> 1.recip() is 1/1 which is 1 -> conjugate() * 1 is conjugate().
         self.conjugate() * self.norm_squared().recip()
     }
+
+    /// The components in memory order, `[x, y, z, w]`.
+    pub const fn to_array(&self) -> [S; 4] {
+        [self.v.x, self.v.y, self.v.z, self.w]
+    }
+
+    /// Converts to another scalar type.
+    pub fn cast<T>(&self) -> TQuat<T>
+    where
+        T: Scalar,
+    {
+        TQuat::new(T::from_f64(self.w.to_f64()), self.v.cast())
+    }
 }
 
-impl Normed for Quat {
-    fn norm_squared(&self) -> f32 {
+impl<S> Normed for TQuat<S>
+where
+    S: Scalar,
+{
+    type Scalar = S;
+
+    fn norm_squared(&self) -> S {
         let v = self.v.dot(&self.v);
         let w = self.w * self.w;
 
@@ -70,68 +270,81 @@ impl Normed for Quat {
 
 macro_rules! quaternion {
     (standard $lhs:ty, $rhs:ty) => {
-        impl Mul<$rhs> for $lhs {
-            type Output = Quat;
+        impl<S> Mul<$rhs> for $lhs
+        where
+            S: Scalar,
+        {
+            type Output = TQuat<S>;
 
             fn mul(self, rhs: $rhs) -> Self::Output {
-                let scalar = self.w * rhs.w - self.v.dot(&rhs.v);
-                let imaginary = self.w * rhs.v + rhs.w * self.v + self.v.cross(&rhs.v);
-
-                Self::Output {
-                    w: scalar,
-                    v: imaginary,
-                }
+                TQuat::from(S::quat_mul(&self.to_array(), &rhs.to_array()))
             }
         }
     };
 
     (assign $lhs:ty, $rhs:ty) => {
-        impl MulAssign<$rhs> for $lhs {
+        impl<S> MulAssign<$rhs> for $lhs
+        where
+            S: Scalar,
+        {
             fn mul_assign(&mut self, rhs: $rhs) {
-                let scalar = self.w * rhs.w - self.v.dot(&rhs.v);
-                let imaginary = self.w * rhs.v + rhs.w * self.v + self.v.cross(&rhs.v);
+                let [x, y, z, w] = S::quat_mul(&self.to_array(), &rhs.to_array());
 
-                self.w = scalar;
-                self.v = imaginary;
+                self.v = TVec3::new(x, y, z);
+                self.w = w;
             }
         }
     };
 
     () => {
-        quaternion!(standard      Quat,      Quat);
-        quaternion!(standard      Quat, &    Quat);
-        quaternion!(standard      Quat, &mut Quat);
-        quaternion!(standard &    Quat,      Quat);
-        quaternion!(standard &    Quat, &    Quat);
-        quaternion!(standard &    Quat, &mut Quat);
-        quaternion!(standard &mut Quat,      Quat);
-        quaternion!(standard &mut Quat, &    Quat);
-        quaternion!(standard &mut Quat, &mut Quat);
-
-        quaternion!(assign      Quat,      Quat);
-        quaternion!(assign      Quat, &    Quat);
-        quaternion!(assign      Quat, &mut Quat);
-        quaternion!(assign &mut Quat,      Quat);
-        quaternion!(assign &mut Quat, &    Quat);
-        quaternion!(assign &mut Quat, &mut Quat);
+        quaternion!(standard      TQuat<S>,      TQuat<S>);
+        quaternion!(standard      TQuat<S>, &    TQuat<S>);
+        quaternion!(standard      TQuat<S>, &mut TQuat<S>);
+        quaternion!(standard &    TQuat<S>,      TQuat<S>);
+        quaternion!(standard &    TQuat<S>, &    TQuat<S>);
+        quaternion!(standard &    TQuat<S>, &mut TQuat<S>);
+        quaternion!(standard &mut TQuat<S>,      TQuat<S>);
+        quaternion!(standard &mut TQuat<S>, &    TQuat<S>);
+        quaternion!(standard &mut TQuat<S>, &mut TQuat<S>);
+
+        quaternion!(assign      TQuat<S>,      TQuat<S>);
+        quaternion!(assign      TQuat<S>, &    TQuat<S>);
+        quaternion!(assign      TQuat<S>, &mut TQuat<S>);
+        quaternion!(assign &mut TQuat<S>,      TQuat<S>);
+        quaternion!(assign &mut TQuat<S>, &    TQuat<S>);
+        quaternion!(assign &mut TQuat<S>, &mut TQuat<S>);
     };
 }
 
 quaternion!();
 
-impl From<[f32; 4]> for Quat {
-    fn from([x, y, z, w]: [f32; 4]) -> Self {
+unsafe impl<S: Scalar> Pod for TQuat<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TQuat<S> {}
+
+/// Extracts the rotation of a matrix without scale or shear, see `Mat4::decompose` otherwise.
+impl<S> From<TMat4<S>> for TQuat<S>
+where
+    S: Scalar,
+{
+    fn from(matrix: TMat4<S>) -> Self {
+        Self::from(TMat3::from(matrix))
+    }
+}
+
+impl<S> From<[S; 4]> for TQuat<S> {
+    fn from([x, y, z, w]: [S; 4]) -> Self {
         Self {
-            v: Vec3 { x, y, z },
+            v: TVec3 { x, y, z },
             w,
         }
     }
 }
 
-impl From<(f32, f32, f32, f32)> for Quat {
-    fn from((x, y, z, w): (f32, f32, f32, f32)) -> Self {
+impl<S> From<(S, S, S, S)> for TQuat<S> {
+    fn from((x, y, z, w): (S, S, S, S)) -> Self {
         Self {
-            v: Vec3 { x, y, z },
+            v: TVec3 { x, y, z },
             w,
         }
     }
@@ -139,22 +352,281 @@ impl From<(f32, f32, f32, f32)> for Quat {
 
 #[cfg(test)]
 mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use crate::core::maths::{fuzzy, Mat4, Vec3};
+
     use super::*;
 
+    const CASES: usize = 256;
+    const EPSILON: f32 = 1e-4;
+
+    // NOTE:
+    // A xorshift generator with a fixed seed, so the property tests below check the same cases on
+    // every run.
+    struct Random(u32);
+
+    impl Random {
+        fn new() -> Self {
+            Self(0x9e37_79b9)
+        }
+
+        fn next(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+
+            self.0 as f32 / u32::MAX as f32
+        }
+
+        fn range(&mut self, min: f32, max: f32) -> f32 {
+            min + (max - min) * self.next()
+        }
+
+        fn direction(&mut self) -> Unit<Vec3> {
+            loop {
+                let vector = Vec3::new(
+                    self.range(-1.0, 1.0),
+                    self.range(-1.0, 1.0),
+                    self.range(-1.0, 1.0),
+                );
+                let norm = vector.norm();
+
+                if norm > 0.1 && norm <= 1.0 {
+                    return Unit::from_normalised(vector / norm);
+                }
+            }
+        }
+
+        fn rotation(&mut self) -> Quat {
+            let axis = self.direction();
+
+            Quat::from_axis_angle(&axis, Angle::radians(self.range(-PI, PI)))
+        }
+    }
+
+    fn near(a: f32, b: f32) -> bool {
+        fuzzy::eq_with_epsilon(a, b, EPSILON)
+    }
+
+    fn assert_vec_near(a: &Vec3, b: &Vec3) {
+        assert!(
+            near(a.x, b.x) && near(a.y, b.y) && near(a.z, b.z),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    // q and -q are the same rotation.
+    fn assert_same_rotation(a: &Quat, b: &Quat) {
+        assert!(near(a.dot(b).abs(), 1.0), "{:?} != {:?}", a, b);
+    }
+
+    fn angle_between(a: &Quat, b: &Quat) -> f32 {
+        2.0 * a.dot(b).abs().clamp(0.0, 1.0).acos()
+    }
+
     #[test]
     fn identity() {
         assert_eq!(Quat::new(1.0, Vec3::zero()), Quat::identity());
     }
 
     #[test]
-    fn lerp() {}
+    fn lerp() {
+        let end = Quat::from_axis_angle(&Vec3::unit_y(), Angle::radians(FRAC_PI_2));
+        let mid = Quat::lerp(&Quat::identity(), &end, 0.5);
+
+        assert!(near(mid.norm(), 1.0));
+    }
+
+    #[test]
+    fn from_axis_angle() {
+        let quat = Quat::from_axis_angle(&Vec3::unit_z(), Angle::radians(FRAC_PI_2));
+
+        assert_vec_near(&Vec3::new(1.0, 0.0, 0.0).rotate(&quat), &Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn conjugate() {
+        let Quat { v, w } = Quat::new(1.0, Vec3::new(2.0, 3.0, 4.0)).conjugate();
+
+        assert_eq!([v.x, v.y, v.z, w], [-2.0, -3.0, -4.0, 1.0]);
+    }
+
+    #[test]
+    fn inverse() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let quat = random.rotation();
+
+            assert_same_rotation(&(quat * quat.inverse()), &Quat::identity());
+        }
+    }
+
+    #[test]
+    fn slerp_ends_at_its_endpoints() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let start = random.rotation();
+            let end = random.rotation();
+
+            assert_same_rotation(&Quat::slerp(&start, &end, 0.0), &start);
+            assert_same_rotation(&Quat::slerp(&start, &end, 1.0), &end);
+        }
+    }
+
+    #[test]
+    fn slerp_has_constant_angular_speed_along_the_shortest_path() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let start = random.rotation();
+            let end = random.rotation();
+            let t = random.next();
+            let total = angle_between(&start, &end);
+            let mid = Quat::slerp(&start, &end, t);
+
+            assert!(total <= PI + EPSILON);
+            assert!(near(mid.norm(), 1.0));
+            assert!(fuzzy::eq_with_epsilon(angle_between(&start, &mid), total * t, 1e-3));
+        }
+    }
+
+    #[test]
+    fn slerp_takes_the_short_way_round() {
+        let start = Quat::identity();
+        let end = -Quat::from_axis_angle(&Vec3::unit_y(), Angle::radians(0.5));
+
+        assert!(Quat::slerp(&start, &end, 0.5).w > 0.0);
+        assert!(Quat::nlerp(&start, &end, 0.5).w > 0.0);
+    }
+
+    #[test]
+    fn euler_angles_round_trip_in_every_order() {
+        let mut random = Random::new();
+        let orders = [
+            RotationOrder::Xyz,
+            RotationOrder::Xzy,
+            RotationOrder::Yxz,
+            RotationOrder::Yzx,
+            RotationOrder::Zxy,
+            RotationOrder::Zyx,
+        ];
+
+        for &order in &orders {
+            for _ in 0..CASES {
+                let quat = random.rotation();
+                let (x, y, z) = quat.to_euler(order);
+
+                assert_same_rotation(&Quat::from_euler(order, x, y, z), &quat);
+            }
+        }
+    }
+
+    #[test]
+    fn euler_angles_apply_in_order() {
+        let quat = Quat::from_euler(
+            RotationOrder::Xyz,
+            Angle::radians(FRAC_PI_2),
+            Angle::radians(FRAC_PI_2),
+            Angle::zero(),
+        );
+
+        // X first turns +Y to +Z, then Y turns +Z to +X.
+        assert_vec_near(&Vec3::new(0.0, 1.0, 0.0).rotate(&quat), &Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn euler_angles_survive_gimbal_lock() {
+        let quat = Quat::from_euler(
+            RotationOrder::Xyz,
+            Angle::radians(0.3),
+            Angle::radians(FRAC_PI_2),
+            Angle::radians(0.2),
+        );
+        let (x, y, z) = quat.to_euler(RotationOrder::Xyz);
+
+        assert_same_rotation(&Quat::from_euler(RotationOrder::Xyz, x, y, z), &quat);
+    }
+
+    #[test]
+    fn look_rotation_faces_forward() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let forward = random.direction();
+            let up = random.direction();
+
+            if forward.dot(&up).abs() > 0.99 {
+                continue;
+            }
+
+            let quat = Quat::look_rotation(&forward, &up);
+            let rotated_up = Vec3::unit_y().rotate(&quat);
+
+            assert_vec_near(&Vec3::unit_z().rotate(&quat), &forward);
+            assert!(near(rotated_up.dot(&forward), 0.0));
+            assert!(rotated_up.dot(&up) > 0.0);
+        }
+    }
+
+    #[test]
+    fn rotation_arc_turns_from_to() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let from = random.direction();
+            let to = random.direction();
+            let quat = Quat::from_rotation_arc(&from, &to);
+
+            assert_vec_near(&from.rotate(&quat), &to);
+            assert!(near(angle_between(&quat, &Quat::identity()), from.dot(&to).acos()));
+        }
+
+        let backwards = Unit::from_normalised(-*Vec3::unit_x());
+        let opposite = Quat::from_rotation_arc(&Vec3::unit_x(), &backwards);
+        assert_vec_near(&Vec3::unit_x().rotate(&opposite), &-*Vec3::unit_x());
+    }
 
     #[test]
-    fn from_axis_angle() {}
+    fn axis_angle_round_trip() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let quat = random.rotation();
+            let (axis, angle) = quat.to_axis_angle();
+
+            assert!(near(axis.norm(), 1.0));
+            assert_same_rotation(&Quat::from_axis_angle(&axis, angle), &quat);
+        }
+    }
 
     #[test]
-    fn conjugate() {}
+    fn matrix_round_trip() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let quat = random.rotation();
+
+            assert_same_rotation(&Quat::from(Mat4::rotation(&quat)), &quat);
+        }
+    }
 
     #[test]
-    fn inverse() {}
+    fn swing_twist_recomposes() {
+        let mut random = Random::new();
+
+        for _ in 0..CASES {
+            let quat = random.rotation();
+            let axis = random.direction();
+            let (swing, twist) = quat.swing_twist(&axis);
+
+            assert_same_rotation(&(swing * twist), &quat);
+            assert!(near(twist.v.cross(&axis).norm(), 0.0));
+            assert!(near(swing.v.dot(&axis), 0.0));
+        }
+    }
 }
@@ -0,0 +1,55 @@
+use crate::core::maths::{Angle, Normed, Vec2};
+
+/// A 2D vector in polar coordinates, its length and its angle anticlockwise from +X.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialVec2 {
+    pub radius: f32,
+    pub angle:  Angle,
+}
+
+impl RadialVec2 {
+    pub const fn new(radius: f32, angle: Angle) -> Self {
+        Self { radius, angle }
+    }
+}
+
+impl From<Vec2> for RadialVec2 {
+    fn from(vec: Vec2) -> Self {
+        Self {
+            radius: vec.norm(),
+            angle:  Angle::from_vec2(&vec),
+        }
+    }
+}
+
+impl From<RadialVec2> for Vec2 {
+    fn from(RadialVec2 { radius, angle }: RadialVec2) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Vec2::new(radius * cos, radius * sin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::core::maths::fuzzy;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let radial = RadialVec2::from(Vec2::new(0.0, 2.0));
+
+        assert_eq!(radial.radius, 2.0);
+        assert!(fuzzy::eq(radial.angle.into_radians(), FRAC_PI_2));
+
+        let Vec2 { x, y } = Vec2::from(RadialVec2::new(3.0, Angle::radians(-1.0)));
+        let radial = RadialVec2::from(Vec2::new(x, y));
+
+        assert!(fuzzy::eq_with_epsilon(radial.radius, 3.0, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(radial.angle.into_radians(), -1.0, 1e-5));
+    }
+}
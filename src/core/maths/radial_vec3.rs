@@ -0,0 +1,62 @@
+use crate::core::maths::{Angle, Vec3};
+
+/// A 3D vector in cylindrical coordinates about the Y axis: its distance from the axis, its angle
+/// about the axis from +Z towards +X, and its height along it.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialVec3 {
+    pub radius: f32,
+    pub angle:  Angle,
+    pub height: f32,
+}
+
+impl RadialVec3 {
+    pub const fn new(radius: f32, angle: Angle, height: f32) -> Self {
+        Self {
+            radius,
+            angle,
+            height,
+        }
+    }
+}
+
+impl From<Vec3> for RadialVec3 {
+    fn from(vec: Vec3) -> Self {
+        Self {
+            radius: vec.x.hypot(vec.z),
+            angle:  Angle::atan2(vec.x, vec.z),
+            height: vec.y,
+        }
+    }
+}
+
+impl From<RadialVec3> for Vec3 {
+    fn from(
+        RadialVec3 {
+            radius,
+            angle,
+            height,
+        }: RadialVec3,
+    ) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Vec3::new(radius * sin, height, radius * cos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::maths::fuzzy;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let vec = Vec3::new(1.0, -2.0, 3.0);
+        let Vec3 { x, y, z } = Vec3::from(RadialVec3::from(vec));
+
+        assert!(fuzzy::eq_with_epsilon(x, vec.x, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(y, vec.y, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(z, vec.z, 1e-5));
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use bytemuck::Pod;
+
+use crate::core::maths::{simd::Kernels, ApproxEq};
+
+/// The floating point types the maths types can be built from. `f32` is what the GPU takes, `f64`
+/// keeps positions far from the origin precise.
+pub trait Scalar:
+    Debug
+    + Default
+    + PartialOrd
+    + Pod
+    + ApproxEq<Epsilon = Self>
+    + Kernels
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const NEG_ONE: Self;
+    const TWO: Self;
+    const HALF: Self;
+    const EPSILON: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    /// Converts a constant, e.g. `S::from_f32(0.25)`, or an `f32` such as an `Angle`'s radians.
+    fn from_f32(value: f32) -> Self;
+
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f32(self) -> f32;
+
+    fn to_f64(self) -> f64;
+
+    fn abs(self) -> Self;
+
+    fn sqrt(self) -> Self;
+
+    fn recip(self) -> Self;
+
+    fn sin(self) -> Self;
+
+    fn cos(self) -> Self;
+
+    fn tan(self) -> Self;
+
+    fn asin(self) -> Self;
+
+    fn acos(self) -> Self;
+
+    fn atan2(self, other: Self) -> Self;
+
+    fn min(self, other: Self) -> Self;
+
+    fn max(self, other: Self) -> Self;
+
+    fn clamp(self, min: Self, max: Self) -> Self;
+}
+
+macro_rules! scalar {
+    ($($scalar:ident),*) => {
+        $(
+            impl Scalar for $scalar {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+                const NEG_ONE: Self = -1.0;
+                const TWO: Self = 2.0;
+                const HALF: Self = 0.5;
+                const EPSILON: Self = $scalar::EPSILON;
+                const INFINITY: Self = $scalar::INFINITY;
+                const NEG_INFINITY: Self = $scalar::NEG_INFINITY;
+
+                #[inline(always)]
+                fn from_f32(value: f32) -> Self {
+                    value as $scalar
+                }
+
+                #[inline(always)]
+                fn from_f64(value: f64) -> Self {
+                    value as $scalar
+                }
+
+                #[inline(always)]
+                fn to_f32(self) -> f32 {
+                    self as f32
+                }
+
+                #[inline(always)]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline(always)]
+                fn abs(self) -> Self {
+                    $scalar::abs(self)
+                }
+
+                #[inline(always)]
+                fn sqrt(self) -> Self {
+                    $scalar::sqrt(self)
+                }
+
+                #[inline(always)]
+                fn recip(self) -> Self {
+                    $scalar::recip(self)
+                }
+
+                #[inline(always)]
+                fn sin(self) -> Self {
+                    $scalar::sin(self)
+                }
+
+                #[inline(always)]
+                fn cos(self) -> Self {
+                    $scalar::cos(self)
+                }
+
+                #[inline(always)]
+                fn tan(self) -> Self {
+                    $scalar::tan(self)
+                }
+
+                #[inline(always)]
+                fn asin(self) -> Self {
+                    $scalar::asin(self)
+                }
+
+                #[inline(always)]
+                fn acos(self) -> Self {
+                    $scalar::acos(self)
+                }
+
+                #[inline(always)]
+                fn atan2(self, other: Self) -> Self {
+                    $scalar::atan2(self, other)
+                }
+
+                #[inline(always)]
+                fn min(self, other: Self) -> Self {
+                    $scalar::min(self, other)
+                }
+
+                #[inline(always)]
+                fn max(self, other: Self) -> Self {
+                    $scalar::max(self, other)
+                }
+
+                #[inline(always)]
+                fn clamp(self, min: Self, max: Self) -> Self {
+                    $scalar::clamp(self, min, max)
+                }
+            }
+        )*
+    };
+}
+
+scalar!(f32, f64);
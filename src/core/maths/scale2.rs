@@ -1,18 +1,33 @@
 use bytemuck::{Pod, Zeroable};
 
+use crate::core::maths::Scalar;
+
+pub type Scale2 = TScale2<f32>;
+pub type DScale2 = TScale2<f64>;
+
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Scale2 {
-    pub w: f32,
-    pub h: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TScale2<S> {
+    pub w: S,
+    pub h: S,
 }
 
-impl Scale2 {
-    pub const fn new(w: f32, h: f32) -> Self {
+impl<S> TScale2<S>
+where
+    S: Scalar,
+{
+    pub const fn new(w: S, h: S) -> Self {
         Self { w, h }
     }
 
     pub const fn identity() -> Self {
-        Self { w: 1.0, h: 1.0 }
+        Self {
+            w: S::ONE,
+            h: S::ONE,
+        }
     }
 }
+
+unsafe impl<S: Scalar> Pod for TScale2<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TScale2<S> {}
@@ -1,23 +1,35 @@
 use bytemuck::{Pod, Zeroable};
 
+use crate::core::maths::Scalar;
+
+pub type Scale3 = TScale3<f32>;
+pub type DScale3 = TScale3<f64>;
+
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Scale3 {
-    pub w: f32,
-    pub h: f32,
-    pub d: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TScale3<S> {
+    pub w: S,
+    pub h: S,
+    pub d: S,
 }
 
-impl Scale3 {
-    pub const fn new(w: f32, h: f32, d: f32) -> Self {
+impl<S> TScale3<S>
+where
+    S: Scalar,
+{
+    pub const fn new(w: S, h: S, d: S) -> Self {
         Self { w, h, d }
     }
 
     pub const fn identity() -> Self {
         Self {
-            w: 1.0,
-            h: 1.0,
-            d: 1.0,
+            w: S::ONE,
+            h: S::ONE,
+            d: S::ONE,
         }
     }
 }
+
+unsafe impl<S: Scalar> Pod for TScale3<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TScale3<S> {}
@@ -0,0 +1,100 @@
+use crate::core::maths::{Normed, Point2, Vec2};
+
+/// The part of a line between two points.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment2 {
+    pub start: Point2,
+    pub end:   Point2,
+}
+
+impl Segment2 {
+    pub const fn new(start: Point2, end: Point2) -> Self {
+        Self { start, end }
+    }
+
+    /// The vector from the start to the end.
+    pub fn vector(&self) -> Vec2 {
+        self.start.vector_to(&self.end)
+    }
+
+    pub fn length(&self) -> f32 {
+        self.vector().norm()
+    }
+
+    pub fn midpoint(&self) -> Point2 {
+        self.point_at(0.5)
+    }
+
+    /// The point `t` of the way from the start to the end.
+    pub fn point_at(&self, t: f32) -> Point2 {
+        self.start + t * self.vector()
+    }
+
+    /// How far from the start to the end, from 0 to 1, the point closest to `point` is.
+    pub fn project(&self, point: &Point2) -> f32 {
+        let vector = self.vector();
+        let length_squared = vector.norm_squared();
+
+        if length_squared == 0.0 {
+            return 0.0;
+        }
+
+        (self.start.vector_to(point).dot(&vector) / length_squared).clamp(0.0, 1.0)
+    }
+
+    pub fn closest_point(&self, point: &Point2) -> Point2 {
+        self.point_at(self.project(point))
+    }
+
+    pub fn distance_to(&self, point: &Point2) -> f32 {
+        self.closest_point(point).vector_to(point).norm()
+    }
+
+    /// Where the segments cross, `None` if they don't or if they're parallel.
+    pub fn intersection(&self, rhs: &Segment2) -> Option<Point2> {
+        let vector = self.vector();
+        let rhs_vector = rhs.vector();
+        let denominator = rhs_vector.left_perpendicular_dot(&vector);
+
+        if denominator.abs() < 1e-6 {
+            return None;
+        }
+
+        let between = self.start.vector_to(&rhs.start);
+        let t = rhs_vector.left_perpendicular_dot(&between) / denominator;
+        let u = vector.left_perpendicular_dot(&between) / denominator;
+
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            Some(self.point_at(t))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_point_is_clamped_to_the_ends() {
+        let segment = Segment2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 0.0));
+        let Point2 { x, y } = segment.closest_point(&Point2::new(5.0, 4.0));
+
+        assert_eq!([x, y], [2.0, 0.0]);
+        assert_eq!(segment.distance_to(&Point2::new(5.0, 4.0)), 5.0);
+        assert_eq!(segment.distance_to(&Point2::new(1.0, -2.0)), 2.0);
+    }
+
+    #[test]
+    fn intersection() {
+        let a = Segment2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 2.0));
+        let b = Segment2::new(Point2::new(0.0, 2.0), Point2::new(2.0, 0.0));
+        let c = Segment2::new(Point2::new(3.0, 0.0), Point2::new(3.0, 2.0));
+        let Point2 { x, y } = a.intersection(&b).unwrap();
+
+        assert_eq!([x, y], [1.0, 1.0]);
+        assert!(a.intersection(&c).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::core::maths::{Normed, Point3, Vec3};
+
+/// The part of a line between two points.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment3 {
+    pub start: Point3,
+    pub end:   Point3,
+}
+
+impl Segment3 {
+    pub const fn new(start: Point3, end: Point3) -> Self {
+        Self { start, end }
+    }
+
+    /// The vector from the start to the end.
+    pub fn vector(&self) -> Vec3 {
+        self.start.vector_to(&self.end)
+    }
+
+    pub fn length(&self) -> f32 {
+        self.vector().norm()
+    }
+
+    pub fn midpoint(&self) -> Point3 {
+        self.point_at(0.5)
+    }
+
+    /// The point `t` of the way from the start to the end.
+    pub fn point_at(&self, t: f32) -> Point3 {
+        self.start + t * self.vector()
+    }
+
+    /// How far from the start to the end, from 0 to 1, the point closest to `point` is.
+    pub fn project(&self, point: &Point3) -> f32 {
+        let vector = self.vector();
+        let length_squared = vector.norm_squared();
+
+        if length_squared == 0.0 {
+            return 0.0;
+        }
+
+        (self.start.vector_to(point).dot(&vector) / length_squared).clamp(0.0, 1.0)
+    }
+
+    pub fn closest_point(&self, point: &Point3) -> Point3 {
+        self.point_at(self.project(point))
+    }
+
+    pub fn distance_to(&self, point: &Point3) -> f32 {
+        self.closest_point(point).vector_to(point).norm()
+    }
+
+    /// The closest points between the segments, on this segment and then on `rhs`.
+    pub fn closest_points(&self, rhs: &Segment3) -> (Point3, Point3) {
+        // Source:
+        // Real-Time Collision Detection, Christer Ericson, 5.1.9
+        let d1 = self.vector();
+        let d2 = rhs.vector();
+        let r = rhs.start.vector_to(&self.start);
+        let a = d1.norm_squared();
+        let e = d2.norm_squared();
+        let f = d2.dot(&r);
+
+        if a == 0.0 && e == 0.0 {
+            return (self.start, rhs.start);
+        }
+
+        let (s, t) = if a == 0.0 {
+            (0.0, (f / e).clamp(0.0, 1.0))
+        } else {
+            let c = d1.dot(&r);
+
+            if e == 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else {
+                let b = d1.dot(&d2);
+                let denominator = a * e - b * b;
+
+                // NOTE:
+                // Parallel segments have no single closest pair, so any point on this segment is
+                // picked and the closest point on the other found from it.
+                let s = if denominator != 0.0 {
+                    ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                let t = (b * s + f) / e;
+
+                if t < 0.0 {
+                    ((-c / a).clamp(0.0, 1.0), 0.0)
+                } else if t > 1.0 {
+                    (((b - c) / a).clamp(0.0, 1.0), 1.0)
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        (self.point_at(s), rhs.point_at(t))
+    }
+
+    /// The shortest distance between the segments.
+    pub fn distance_to_segment(&self, rhs: &Segment3) -> f32 {
+        let (a, b) = self.closest_points(rhs);
+
+        a.vector_to(&b).norm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_point_is_clamped_to_the_ends() {
+        let segment = Segment3::new(Point3::identity(), Point3::new(0.0, 0.0, 2.0));
+
+        assert_eq!(segment.distance_to(&Point3::new(0.0, 3.0, 1.0)), 3.0);
+        assert_eq!(segment.distance_to(&Point3::new(0.0, 0.0, 5.0)), 3.0);
+        assert_eq!(segment.project(&Point3::new(0.0, 0.0, -5.0)), 0.0);
+    }
+
+    #[test]
+    fn distance_between_segments() {
+        let a = Segment3::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let crossing = Segment3::new(Point3::new(0.0, -1.0, 2.0), Point3::new(0.0, 1.0, 2.0));
+        let beyond = Segment3::new(Point3::new(3.0, -1.0, 0.0), Point3::new(3.0, 1.0, 0.0));
+        let parallel = Segment3::new(Point3::new(0.0, 1.0, 0.0), Point3::new(4.0, 1.0, 0.0));
+
+        assert_eq!(a.distance_to_segment(&crossing), 2.0);
+        assert_eq!(a.distance_to_segment(&beyond), 2.0);
+        assert_eq!(a.distance_to_segment(&parallel), 1.0);
+    }
+}
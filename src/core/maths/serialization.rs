@@ -0,0 +1,321 @@
+use std::ops::{DivAssign, MulAssign};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::maths::{
+    BSpline, CatmullRom, CurvePoint, Hermite, Normed, Scalar, TMat2, TMat3, TMat4, TPoint2,
+    TPoint3, TQuat, TScale2, TScale3, TVec2, TVec3, TVec4, Unit,
+};
+
+// NOTE:
+// Hand written files round their numbers, so a unit value only has to be close to unit length to
+// be accepted. It's normalised again once it is.
+const UNIT_TOLERANCE: f32 = 1e-4;
+
+/// Serializes the members as an array, e.g. `[x, y, z]`, which is far more compact than a map in
+/// every format.
+macro_rules! compact {
+    ($struct:ident [$len:literal] { $( $member:ident )* }) => {
+        impl<S> Serialize for $struct<S>
+        where
+            S: Scalar + Serialize,
+        {
+            fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+            where
+                R: Serializer,
+            {
+                [$( self.$member ),*].serialize(serializer)
+            }
+        }
+
+        impl<'de, S> Deserialize<'de> for $struct<S>
+        where
+            S: Scalar + Deserialize<'de>,
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let [$( $member ),*] = <[S; $len]>::deserialize(deserializer)?;
+
+                Ok(Self { $( $member ),* })
+            }
+        }
+    };
+
+    ($struct:ident [$len:literal]) => {
+        impl<S> Serialize for $struct<S>
+        where
+            S: Scalar + Serialize,
+        {
+            fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+            where
+                R: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<'de, S> Deserialize<'de> for $struct<S>
+        where
+            S: Scalar + Deserialize<'de>,
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                Ok(Self(<[S; $len]>::deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+compact!(TVec2 [2] { x y });
+compact!(TVec3 [3] { x y z });
+compact!(TVec4 [4] { x y z w });
+compact!(TPoint2 [2] { x y });
+compact!(TPoint3 [3] { x y z });
+compact!(TScale2 [2] { w h });
+compact!(TScale3 [3] { w h d });
+
+// Matrices are serialized column-major, as they're laid out in memory.
+compact!(TMat2 [4]);
+compact!(TMat3 [9]);
+compact!(TMat4 [16]);
+
+/// Quaternions are serialized `[x, y, z, w]`, as they're laid out in memory.
+impl<S> Serialize for TQuat<S>
+where
+    S: Scalar + Serialize,
+{
+    fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+    where
+        R: Serializer,
+    {
+        self.to_array().serialize(serializer)
+    }
+}
+
+impl<'de, S> Deserialize<'de> for TQuat<S>
+where
+    S: Scalar + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [x, y, z, w] = <[S; 4]>::deserialize(deserializer)?;
+
+        Ok(Self::new(w, TVec3::new(x, y, z)))
+    }
+}
+
+/// Serialized as the value itself.
+impl<T> Serialize for Unit<T>
+where
+    T: Normed + Serialize,
+{
+    fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error>
+    where
+        R: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Fails unless the value is unit length.
+impl<'de, T> Deserialize<'de> for Unit<T>
+where
+    T: Normed + MulAssign<T::Scalar> + DivAssign<T::Scalar> + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = T::deserialize(deserializer)?;
+        let norm_squared = value.norm_squared().to_f32();
+
+        if (norm_squared - 1.0).abs() > UNIT_TOLERANCE {
+            return Err(D::Error::custom(format!(
+                "expected a unit length value, found one of length {}",
+                norm_squared.sqrt()
+            )));
+        }
+
+        Ok(Unit::new(value))
+    }
+}
+
+// NOTE:
+// Splines are read into these first and then built with their constructors, so they're checked
+// the same way whether they're made in code or loaded.
+#[derive(Deserialize)]
+struct SplinePoints<V> {
+    points: Vec<V>,
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+struct HermitePoints<V> {
+    points:   Vec<V>,
+    tangents: Vec<V>,
+}
+
+fn check_point_count<E>(points: &[impl CurvePoint]) -> Result<(), E>
+where
+    E: Error,
+{
+    if points.len() < 2 {
+        return Err(E::custom(format!("expected at least 2 points, found {}", points.len())));
+    }
+
+    Ok(())
+}
+
+/// Fails unless there are at least 2 points.
+impl<'de, V> Deserialize<'de> for CatmullRom<V>
+where
+    V: CurvePoint + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let SplinePoints { points, closed } = SplinePoints::deserialize(deserializer)?;
+
+        check_point_count(&points)?;
+
+        Ok(if closed {
+            CatmullRom::closed(points)
+        } else {
+            CatmullRom::new(points)
+        })
+    }
+}
+
+/// Fails unless there are at least 2 points.
+impl<'de, V> Deserialize<'de> for BSpline<V>
+where
+    V: CurvePoint + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let SplinePoints { points, closed } = SplinePoints::deserialize(deserializer)?;
+
+        check_point_count(&points)?;
+
+        Ok(if closed {
+            BSpline::closed(points)
+        } else {
+            BSpline::new(points)
+        })
+    }
+}
+
+/// Fails unless there are at least 2 points and a tangent for every one of them.
+impl<'de, V> Deserialize<'de> for Hermite<V>
+where
+    V: CurvePoint + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let HermitePoints { points, tangents } = HermitePoints::deserialize(deserializer)?;
+
+        check_point_count(&points)?;
+
+        if points.len() != tangents.len() {
+            return Err(D::Error::custom(format!(
+                "expected a tangent for each of the {} points, found {}",
+                points.len(),
+                tangents.len()
+            )));
+        }
+
+        Ok(Hermite::new(points, tangents))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::maths::{Curve, DVec3, Mat4, Point3, Quat, Scale3, Vec2, Vec3};
+
+    use super::*;
+
+    #[test]
+    fn vectors_are_arrays() {
+        assert_eq!(serde_json::to_string(&Vec3::new(1.0, 2.0, 3.0)).unwrap(), "[1.0,2.0,3.0]");
+        assert_eq!(serde_json::to_string(&Scale3::new(1.0, 2.0, 3.0)).unwrap(), "[1.0,2.0,3.0]");
+        assert_eq!(serde_json::to_string(&DVec3::new(1.0, 2.0, 3.0)).unwrap(), "[1.0,2.0,3.0]");
+        assert_eq!(
+            serde_json::from_str::<Point3>("[1.0,2.0,3.0]").unwrap(),
+            Point3::new(1.0, 2.0, 3.0)
+        );
+        assert!(serde_json::from_str::<Vec2>("[1.0,2.0,3.0]").is_err());
+    }
+
+    #[test]
+    fn quaternions_are_xyzw() {
+        let quat = Quat::new(4.0, Vec3::new(1.0, 2.0, 3.0));
+        let json = serde_json::to_string(&quat).unwrap();
+
+        assert_eq!(json, "[1.0,2.0,3.0,4.0]");
+        assert_eq!(serde_json::from_str::<Quat>(&json).unwrap(), quat);
+    }
+
+    #[test]
+    fn matrices_are_column_major() {
+        let matrix = Mat4::translation(&Vec3::new(1.0, 2.0, 3.0));
+        let json = serde_json::to_string(&matrix).unwrap();
+
+        assert!(json.ends_with("1.0,2.0,3.0,1.0]"));
+        assert_eq!(serde_json::from_str::<Mat4>(&json).unwrap(), matrix);
+    }
+
+    #[test]
+    fn units_must_be_unit_length() {
+        let unit = serde_json::from_str::<Unit<Vec3>>("[0.0,0.6,0.8]").unwrap();
+
+        assert_eq!(*unit, Vec3::new(0.0, 0.6, 0.8));
+        assert!(serde_json::from_str::<Unit<Vec3>>("[0.0,0.0,0.0]").is_err());
+        assert!(serde_json::from_str::<Unit<Vec3>>("[1.0,1.0,0.0]").is_err());
+
+        let rotation = serde_json::from_str::<Unit<Quat>>("[0.0,0.7071,0.0,0.7071]").unwrap();
+
+        assert!((rotation.norm() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn splines_need_two_points() {
+        let json = r#"{"points":[[0.0,0.0],[1.0,2.0],[3.0,2.0]],"closed":true}"#;
+        let spline = serde_json::from_str::<CatmullRom<Vec2>>(json).unwrap();
+
+        assert!(spline.is_closed());
+        assert_eq!(spline.segment_count(), 3);
+        assert_eq!(serde_json::to_string(&spline).unwrap(), json);
+
+        let single = r#"{"points":[[0.0,0.0]],"closed":false}"#;
+
+        assert!(serde_json::from_str::<CatmullRom<Vec2>>(single).is_err());
+        assert!(serde_json::from_str::<BSpline<Vec2>>(single).is_err());
+        assert!(serde_json::from_str::<BSpline<Vec2>>(json).is_ok());
+    }
+
+    #[test]
+    fn hermite_splines_need_a_tangent_per_point() {
+        let json = r#"{"points":[[0.0,0.0],[1.0,1.0]],"tangents":[[1.0,0.0],[1.0,0.0]]}"#;
+        let spline = serde_json::from_str::<Hermite<Vec2>>(json).unwrap();
+
+        assert_eq!(spline.tangents().len(), 2);
+        assert_eq!(serde_json::to_string(&spline).unwrap(), json);
+
+        let missing = r#"{"points":[[0.0,0.0],[1.0,1.0]],"tangents":[[1.0,0.0]]}"#;
+        let single = r#"{"points":[[0.0,0.0]],"tangents":[[1.0,0.0]]}"#;
+
+        assert!(serde_json::from_str::<Hermite<Vec2>>(missing).is_err());
+        assert!(serde_json::from_str::<Hermite<Vec2>>(single).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! SIMD backends for the hottest maths operations.
+//!
+//! The backend is picked at compile time: AVX when the crate is built with the `avx` target feature
+//! (e.g. `-C target-cpu=native`), otherwise SSE2 on x86_64, NEON on aarch64 and plain scalar code
+//! everywhere else. Only `f32` is accelerated, `f64` always takes the scalar path.
+
+pub mod fallback;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx"))]
+mod avx;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(target_arch = "x86_64")]
+mod sse2;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx"))]
+use self::avx as backend;
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+use self::fallback as backend;
+#[cfg(target_arch = "aarch64")]
+use self::neon as backend;
+#[cfg(all(target_arch = "x86_64", not(target_feature = "avx")))]
+use self::sse2 as backend;
+
+/// The operations a scalar type can accelerate, on the raw data of the maths types. Matrices are
+/// column-major and quaternions are laid out `[x, y, z, w]`, as in memory.
+pub trait Kernels: Sized {
+    fn mat4_mul(lhs: &[Self; 16], rhs: &[Self; 16]) -> [Self; 16];
+
+    fn mat4_inverse(matrix: &[Self; 16]) -> [Self; 16];
+
+    fn mat4_transform(matrix: &[Self; 16], vector: &[Self; 4]) -> [Self; 4];
+
+    fn quat_mul(lhs: &[Self; 4], rhs: &[Self; 4]) -> [Self; 4];
+}
+
+impl Kernels for f32 {
+    #[inline(always)]
+    fn mat4_mul(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+        backend::mat4_mul(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn mat4_inverse(matrix: &[f32; 16]) -> [f32; 16] {
+        backend::mat4_inverse(matrix)
+    }
+
+    #[inline(always)]
+    fn mat4_transform(matrix: &[f32; 16], vector: &[f32; 4]) -> [f32; 4] {
+        backend::mat4_transform(matrix, vector)
+    }
+
+    #[inline(always)]
+    fn quat_mul(lhs: &[f32; 4], rhs: &[f32; 4]) -> [f32; 4] {
+        backend::quat_mul(lhs, rhs)
+    }
+}
+
+impl Kernels for f64 {
+    #[inline(always)]
+    fn mat4_mul(lhs: &[f64; 16], rhs: &[f64; 16]) -> [f64; 16] {
+        fallback::mat4_mul(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn mat4_inverse(matrix: &[f64; 16]) -> [f64; 16] {
+        fallback::mat4_inverse(matrix)
+    }
+
+    #[inline(always)]
+    fn mat4_transform(matrix: &[f64; 16], vector: &[f64; 4]) -> [f64; 4] {
+        fallback::mat4_transform(matrix, vector)
+    }
+
+    #[inline(always)]
+    fn quat_mul(lhs: &[f64; 4], rhs: &[f64; 4]) -> [f64; 4] {
+        fallback::quat_mul(lhs, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_relative_eq,
+        core::maths::{Angle, Mat4, Normed, Point3, Quat, Scale3, Unit, Vec3},
+    };
+
+    use super::*;
+
+    fn matrices() -> Vec<[f32; 16]> {
+        let axis = Vec3::new(1.0, 2.0, -3.0);
+        let rotation =
+            Quat::from_axis_angle(&Unit::from_normalised(axis / axis.norm()), Angle::degrees(37.0));
+
+        vec![
+            Mat4::identity().0,
+            Mat4::translation(&Vec3::new(3.0, -2.0, 7.5)).0,
+            (Mat4::translation(&Vec3::new(-1.0, 4.0, 0.5))
+                * Mat4::rotation(&rotation)
+                * Mat4::scale(&Scale3::new(2.0, 0.5, 3.0)))
+            .0,
+            Mat4::perspective(16.0 / 9.0, Angle::degrees(70.0), 0.1, 100.0).0,
+            Mat4::view(
+                Vec3::new(0.0, 0.0, 1.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 3.0),
+            )
+            .0,
+            [
+                2.0, 1.0, -3.0, 0.5, //
+                4.0, -1.0, 0.0, 2.0, //
+                -2.0, 3.0, 1.0, 1.0, //
+                0.0, 5.0, -4.0, 3.0,
+            ],
+        ]
+    }
+
+    fn quaternions() -> Vec<[f32; 4]> {
+        vec![
+            [0.0, 0.0, 0.0, 1.0],
+            [0.5, -0.5, 0.5, 0.5],
+            [1.0, 2.0, 3.0, 4.0],
+            [-0.25, 0.75, -1.5, 0.1],
+        ]
+    }
+
+    fn assert_slices_near(a: &[f32], b: &[f32]) {
+        for (a, b) in a.iter().zip(b) {
+            assert_relative_eq!(*a, *b, epsilon = 1e-5, max_relative = 1e-5);
+        }
+    }
+
+    #[test]
+    fn mat4_mul_matches_the_fallback() {
+        for lhs in &matrices() {
+            for rhs in &matrices() {
+                assert_slices_near(&f32::mat4_mul(lhs, rhs), &fallback::mat4_mul(lhs, rhs));
+            }
+        }
+    }
+
+    #[test]
+    fn mat4_inverse_matches_the_fallback() {
+        for matrix in &matrices() {
+            assert_slices_near(&f32::mat4_inverse(matrix), &fallback::mat4_inverse(matrix));
+        }
+    }
+
+    #[test]
+    fn mat4_transform_matches_the_fallback() {
+        let vector = [1.5, -2.0, 0.25, 1.0];
+
+        for matrix in &matrices() {
+            assert_slices_near(
+                &f32::mat4_transform(matrix, &vector),
+                &fallback::mat4_transform(matrix, &vector),
+            );
+        }
+    }
+
+    #[test]
+    fn quat_mul_matches_the_fallback() {
+        for lhs in &quaternions() {
+            for rhs in &quaternions() {
+                assert_slices_near(&f32::quat_mul(lhs, rhs), &fallback::quat_mul(lhs, rhs));
+            }
+        }
+    }
+
+    // NOTE:
+    // Only one backend is used by `f32` but the SSE2 one is always compiled on x86_64, so make sure
+    // it stays correct when AVX is enabled.
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn sse2_matches_the_fallback() {
+        for lhs in &matrices() {
+            assert_slices_near(&sse2::mat4_inverse(lhs), &fallback::mat4_inverse(lhs));
+
+            for rhs in &matrices() {
+                assert_slices_near(&sse2::mat4_mul(lhs, rhs), &fallback::mat4_mul(lhs, rhs));
+            }
+        }
+
+        for lhs in &quaternions() {
+            for rhs in &quaternions() {
+                assert_slices_near(&sse2::quat_mul(lhs, rhs), &fallback::quat_mul(lhs, rhs));
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! AVX, which works on two matrix columns at once. Everything that fits in a single SSE register is
+//! left to the SSE2 backend.
+
+use std::arch::x86_64::*;
+
+pub use super::sse2::{mat4_inverse, mat4_transform, quat_mul};
+
+/// Loads a column into both halves of a register.
+#[inline(always)]
+unsafe fn load_twice(data: &[f32]) -> __m256 {
+    debug_assert!(data.len() >= 4);
+
+    let col = _mm_loadu_ps(data.as_ptr());
+
+    _mm256_insertf128_ps(_mm256_castps128_ps256(col), col, 1)
+}
+
+/// `a * b + c`, fused when the target has FMA, which almost every AVX processor does.
+#[inline(always)]
+unsafe fn mul_add(a: __m256, b: __m256, c: __m256) -> __m256 {
+    #[cfg(target_feature = "fma")]
+    return _mm256_fmadd_ps(a, b, c);
+
+    #[cfg(not(target_feature = "fma"))]
+    return _mm256_add_ps(_mm256_mul_ps(a, b), c);
+}
+
+#[inline]
+pub fn mat4_mul(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+    let mut data = [0.0; 16];
+
+    unsafe {
+        let c0 = load_twice(&lhs[0..]);
+        let c1 = load_twice(&lhs[4..]);
+        let c2 = load_twice(&lhs[8..]);
+        let c3 = load_twice(&lhs[12..]);
+
+        for i in (0..16).step_by(8) {
+            // NOTE:
+            // `_mm256_permute_ps` shuffles each half on its own, so every lane is multiplied by the
+            // right element of its own column of `rhs`.
+            let weights = _mm256_loadu_ps(rhs[i..].as_ptr());
+
+            let col = _mm256_mul_ps(c0, _mm256_permute_ps(weights, 0b00_00_00_00));
+            let col = mul_add(c1, _mm256_permute_ps(weights, 0b01_01_01_01), col);
+            let col = mul_add(c2, _mm256_permute_ps(weights, 0b10_10_10_10), col);
+            let col = mul_add(c3, _mm256_permute_ps(weights, 0b11_11_11_11), col);
+
+            _mm256_storeu_ps(data[i..].as_mut_ptr(), col);
+        }
+    }
+
+    data
+}
@@ -0,0 +1,111 @@
+//! The scalar implementations, used for `f64` and on targets without a SIMD backend.
+
+use crate::core::maths::Scalar;
+
+pub fn mat4_mul<S>(lhs: &[S; 16], rhs: &[S; 16]) -> [S; 16]
+where
+    S: Scalar,
+{
+    let mut data = [S::ZERO; 16];
+
+    for col in 0..4 {
+        for row in 0..4 {
+            let mut sum = S::ZERO;
+
+            for i in 0..4 {
+                sum += lhs[i * 4 + row] * rhs[col * 4 + i];
+            }
+
+            data[col * 4 + row] = sum;
+        }
+    }
+
+    data
+}
+
+#[rustfmt::skip]
+pub fn mat4_inverse<S>(matrix: &[S; 16]) -> [S; 16]
+where
+    S: Scalar,
+{
+    let [
+        m00, m01, m02, m03,
+        m10, m11, m12, m13,
+        m20, m21, m22, m23,
+        m30, m31, m32, m33,
+    ] = *matrix;
+
+    // Upper 2D matrix determinants.
+    let b00 = m00 * m11 - m01 * m10;
+    let b01 = m00 * m12 - m02 * m10;
+    let b02 = m00 * m13 - m03 * m10;
+    let b03 = m01 * m12 - m02 * m11;
+    let b04 = m01 * m13 - m03 * m11;
+    let b05 = m02 * m13 - m03 * m12;
+
+    // Lower 2D matrix determinants.
+    let b06 = m20 * m31 - m21 * m30;
+    let b07 = m20 * m32 - m22 * m30;
+    let b08 = m20 * m33 - m23 * m30;
+    let b09 = m21 * m32 - m22 * m31;
+    let b10 = m21 * m33 - m23 * m31;
+    let b11 = m22 * m33 - m23 * m32;
+
+    let determinant = b00 * b11
+                    - b01 * b10
+                    + b02 * b09
+                    + b03 * b08
+                    - b04 * b07
+                    + b05 * b06;
+
+    let reciprocal = determinant.recip();
+
+    [
+        (m11 * b11 - m12 * b10 + m13 * b09) * reciprocal,
+        (m02 * b10 - m01 * b11 - m03 * b09) * reciprocal,
+        (m31 * b05 - m32 * b04 + m33 * b03) * reciprocal,
+        (m22 * b04 - m21 * b05 - m23 * b03) * reciprocal,
+        (m12 * b08 - m10 * b11 - m13 * b07) * reciprocal,
+        (m00 * b11 - m02 * b08 + m03 * b07) * reciprocal,
+        (m32 * b02 - m30 * b05 - m33 * b01) * reciprocal,
+        (m20 * b05 - m22 * b02 + m23 * b01) * reciprocal,
+        (m10 * b10 - m11 * b08 + m13 * b06) * reciprocal,
+        (m01 * b08 - m00 * b10 - m03 * b06) * reciprocal,
+        (m30 * b04 - m31 * b02 + m33 * b00) * reciprocal,
+        (m21 * b02 - m20 * b04 - m23 * b00) * reciprocal,
+        (m11 * b07 - m10 * b09 - m12 * b06) * reciprocal,
+        (m00 * b09 - m01 * b07 + m02 * b06) * reciprocal,
+        (m31 * b01 - m30 * b03 - m32 * b00) * reciprocal,
+        (m20 * b03 - m21 * b01 + m22 * b00) * reciprocal,
+    ]
+}
+
+pub fn mat4_transform<S>(matrix: &[S; 16], vector: &[S; 4]) -> [S; 4]
+where
+    S: Scalar,
+{
+    let mut data = [S::ZERO; 4];
+
+    for row in 0..4 {
+        for i in 0..4 {
+            data[row] += matrix[i * 4 + row] * vector[i];
+        }
+    }
+
+    data
+}
+
+pub fn quat_mul<S>(lhs: &[S; 4], rhs: &[S; 4]) -> [S; 4]
+where
+    S: Scalar,
+{
+    let [ax, ay, az, aw] = *lhs;
+    let [bx, by, bz, bw] = *rhs;
+
+    [
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+        aw * bw - ax * bx - ay * by - az * bz,
+    ]
+}
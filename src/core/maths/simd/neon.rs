@@ -0,0 +1,87 @@
+//! NEON, which every aarch64 processor has.
+
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn load(data: &[f32]) -> float32x4_t {
+    debug_assert!(data.len() >= 4);
+
+    vld1q_f32(data.as_ptr())
+}
+
+#[inline(always)]
+unsafe fn store(data: &mut [f32], value: float32x4_t) {
+    debug_assert!(data.len() >= 4);
+
+    vst1q_f32(data.as_mut_ptr(), value)
+}
+
+#[inline(always)]
+unsafe fn combine(cols: &[float32x4_t; 4], weights: &[f32]) -> float32x4_t {
+    let sum = vmulq_f32(cols[0], vdupq_n_f32(weights[0]));
+    let sum = vfmaq_f32(sum, cols[1], vdupq_n_f32(weights[1]));
+    let sum = vfmaq_f32(sum, cols[2], vdupq_n_f32(weights[2]));
+
+    vfmaq_f32(sum, cols[3], vdupq_n_f32(weights[3]))
+}
+
+#[inline(always)]
+unsafe fn cols(matrix: &[f32; 16]) -> [float32x4_t; 4] {
+    [
+        load(&matrix[0..]),
+        load(&matrix[4..]),
+        load(&matrix[8..]),
+        load(&matrix[12..]),
+    ]
+}
+
+#[inline]
+pub fn mat4_mul(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+    let mut data = [0.0; 16];
+
+    unsafe {
+        let cols = cols(lhs);
+
+        for i in (0..16).step_by(4) {
+            store(&mut data[i..], combine(&cols, &rhs[i..]));
+        }
+    }
+
+    data
+}
+
+#[inline]
+pub fn mat4_transform(matrix: &[f32; 16], vector: &[f32; 4]) -> [f32; 4] {
+    let mut data = [0.0; 4];
+
+    unsafe {
+        store(&mut data, combine(&cols(matrix), vector));
+    }
+
+    data
+}
+
+// NOTE:
+// NEON has no cheap arbitrary shuffle like SSE's, which the blockwise inverse is built on, and the
+// scalar version vectorises well enough on its own.
+#[inline]
+pub fn mat4_inverse(matrix: &[f32; 16]) -> [f32; 16] {
+    super::fallback::mat4_inverse(matrix)
+}
+
+#[inline]
+pub fn quat_mul(lhs: &[f32; 4], rhs: &[f32; 4]) -> [f32; 4] {
+    let [bx, by, bz, bw] = *rhs;
+    let mut data = [0.0; 4];
+
+    unsafe {
+        let sum = vmulq_f32(vdupq_n_f32(lhs[3]), load(rhs));
+        let sum = vfmaq_f32(sum, vdupq_n_f32(lhs[0]), load(&[bw, -bz, by, -bx]));
+        let sum = vfmaq_f32(sum, vdupq_n_f32(lhs[1]), load(&[bz, bw, -bx, -by]));
+        let sum = vfmaq_f32(sum, vdupq_n_f32(lhs[2]), load(&[-by, bx, bw, -bz]));
+
+        store(&mut data, sum);
+    }
+
+    data
+}
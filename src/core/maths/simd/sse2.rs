@@ -0,0 +1,200 @@
+//! SSE2, which every x86_64 processor has.
+
+use std::arch::x86_64::*;
+
+/// `_mm_shuffle_ps` taking its lanes in order, the first two from `$a` and the last two from `$b`.
+macro_rules! shuffle {
+    ($a:expr, $b:expr, $x:literal, $y:literal, $z:literal, $w:literal) => {
+        _mm_shuffle_ps($a, $b, $x | ($y << 2) | ($z << 4) | ($w << 6))
+    };
+}
+
+/// Rearranges the lanes of a single register.
+macro_rules! swizzle {
+    ($a:expr, $x:literal, $y:literal, $z:literal, $w:literal) => {{
+        let a = $a;
+        shuffle!(a, a, $x, $y, $z, $w)
+    }};
+}
+
+#[inline(always)]
+unsafe fn load(data: &[f32]) -> __m128 {
+    debug_assert!(data.len() >= 4);
+
+    _mm_loadu_ps(data.as_ptr())
+}
+
+#[inline(always)]
+unsafe fn store(data: &mut [f32], value: __m128) {
+    debug_assert!(data.len() >= 4);
+
+    _mm_storeu_ps(data.as_mut_ptr(), value)
+}
+
+#[inline(always)]
+unsafe fn combine(cols: &[__m128; 4], weights: __m128) -> __m128 {
+    let x = _mm_mul_ps(cols[0], swizzle!(weights, 0, 0, 0, 0));
+    let y = _mm_mul_ps(cols[1], swizzle!(weights, 1, 1, 1, 1));
+    let z = _mm_mul_ps(cols[2], swizzle!(weights, 2, 2, 2, 2));
+    let w = _mm_mul_ps(cols[3], swizzle!(weights, 3, 3, 3, 3));
+
+    _mm_add_ps(_mm_add_ps(x, y), _mm_add_ps(z, w))
+}
+
+#[inline(always)]
+unsafe fn cols(matrix: &[f32; 16]) -> [__m128; 4] {
+    [
+        load(&matrix[0..]),
+        load(&matrix[4..]),
+        load(&matrix[8..]),
+        load(&matrix[12..]),
+    ]
+}
+
+#[inline]
+pub fn mat4_mul(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+    let mut data = [0.0; 16];
+
+    unsafe {
+        let cols = cols(lhs);
+
+        for i in (0..16).step_by(4) {
+            store(&mut data[i..], combine(&cols, load(&rhs[i..])));
+        }
+    }
+
+    data
+}
+
+#[inline]
+pub fn mat4_transform(matrix: &[f32; 16], vector: &[f32; 4]) -> [f32; 4] {
+    let mut data = [0.0; 4];
+
+    unsafe {
+        store(&mut data, combine(&cols(matrix), load(vector)));
+    }
+
+    data
+}
+
+/// `a * b` for 2x2 matrices packed row-major into a register.
+#[inline(always)]
+unsafe fn mat2_mul(a: __m128, b: __m128) -> __m128 {
+    _mm_add_ps(
+        _mm_mul_ps(a, swizzle!(b, 0, 3, 0, 3)),
+        _mm_mul_ps(swizzle!(a, 1, 0, 3, 2), swizzle!(b, 2, 1, 2, 1)),
+    )
+}
+
+/// `adjugate(a) * b` for 2x2 matrices packed row-major into a register.
+#[inline(always)]
+unsafe fn mat2_adjugate_mul(a: __m128, b: __m128) -> __m128 {
+    _mm_sub_ps(
+        _mm_mul_ps(swizzle!(a, 3, 3, 0, 0), b),
+        _mm_mul_ps(swizzle!(a, 1, 1, 2, 2), swizzle!(b, 2, 3, 0, 1)),
+    )
+}
+
+/// `a * adjugate(b)` for 2x2 matrices packed row-major into a register.
+#[inline(always)]
+unsafe fn mat2_mul_adjugate(a: __m128, b: __m128) -> __m128 {
+    _mm_sub_ps(
+        _mm_mul_ps(a, swizzle!(b, 3, 0, 3, 0)),
+        _mm_mul_ps(swizzle!(a, 1, 0, 3, 2), swizzle!(b, 2, 1, 2, 1)),
+    )
+}
+
+/// Inverts the matrix blockwise, as four 2x2 matrices. See
+/// https://lxjk.github.io/2017/09/03/Fast-4x4-Matrix-Inverse-with-SSE-SIMD-Explained.html
+#[inline]
+pub fn mat4_inverse(matrix: &[f32; 16]) -> [f32; 16] {
+    let mut data = [0.0; 16];
+
+    unsafe {
+        let [c0, c1, c2, c3] = cols(matrix);
+
+        // NOTE:
+        // The inverse of the transpose is the transpose of the inverse, so it doesn't matter that
+        // the blocks are read from columns rather than rows.
+        let a = _mm_movelh_ps(c0, c1);
+        let b = _mm_movehl_ps(c1, c0);
+        let c = _mm_movelh_ps(c2, c3);
+        let d = _mm_movehl_ps(c3, c2);
+
+        // The determinants of a, b, c and d.
+        let determinants = _mm_sub_ps(
+            _mm_mul_ps(shuffle!(c0, c2, 0, 2, 0, 2), shuffle!(c1, c3, 1, 3, 1, 3)),
+            _mm_mul_ps(shuffle!(c0, c2, 1, 3, 1, 3), shuffle!(c1, c3, 0, 2, 0, 2)),
+        );
+
+        let determinant_a = swizzle!(determinants, 0, 0, 0, 0);
+        let determinant_b = swizzle!(determinants, 1, 1, 1, 1);
+        let determinant_c = swizzle!(determinants, 2, 2, 2, 2);
+        let determinant_d = swizzle!(determinants, 3, 3, 3, 3);
+
+        let d_c = mat2_adjugate_mul(d, c);
+        let a_b = mat2_adjugate_mul(a, b);
+
+        // The adjugates of the blocks of the inverse, before dividing by the determinant.
+        let x = _mm_sub_ps(_mm_mul_ps(determinant_d, a), mat2_mul(b, d_c));
+        let w = _mm_sub_ps(_mm_mul_ps(determinant_a, d), mat2_mul(c, a_b));
+        let y = _mm_sub_ps(_mm_mul_ps(determinant_b, c), mat2_mul_adjugate(d, a_b));
+        let z = _mm_sub_ps(_mm_mul_ps(determinant_c, b), mat2_mul_adjugate(a, d_c));
+
+        // |M| = |A||D| + |B||C| - tr((A#B)(D#C))
+        let trace = _mm_mul_ps(a_b, swizzle!(d_c, 0, 2, 1, 3));
+        let trace = _mm_add_ps(trace, swizzle!(trace, 2, 3, 0, 1));
+        let trace = _mm_add_ps(trace, swizzle!(trace, 1, 0, 3, 2));
+
+        let determinant = _mm_sub_ps(
+            _mm_add_ps(
+                _mm_mul_ps(determinant_a, determinant_d),
+                _mm_mul_ps(determinant_b, determinant_c),
+            ),
+            trace,
+        );
+
+        let reciprocal = _mm_div_ps(_mm_setr_ps(1.0, -1.0, -1.0, 1.0), determinant);
+
+        let x = _mm_mul_ps(x, reciprocal);
+        let y = _mm_mul_ps(y, reciprocal);
+        let z = _mm_mul_ps(z, reciprocal);
+        let w = _mm_mul_ps(w, reciprocal);
+
+        // Taking the adjugates of the blocks and putting them back in place in one shuffle each.
+        store(&mut data[0..], shuffle!(x, y, 3, 1, 3, 1));
+        store(&mut data[4..], shuffle!(x, y, 2, 0, 2, 0));
+        store(&mut data[8..], shuffle!(z, w, 3, 1, 3, 1));
+        store(&mut data[12..], shuffle!(z, w, 2, 0, 2, 0));
+    }
+
+    data
+}
+
+#[inline]
+pub fn quat_mul(lhs: &[f32; 4], rhs: &[f32; 4]) -> [f32; 4] {
+    let mut data = [0.0; 4];
+
+    unsafe {
+        let a = load(lhs);
+        let b = load(rhs);
+
+        let w = _mm_mul_ps(swizzle!(a, 3, 3, 3, 3), b);
+        let x = _mm_mul_ps(
+            _mm_mul_ps(swizzle!(a, 0, 0, 0, 0), swizzle!(b, 3, 2, 1, 0)),
+            _mm_setr_ps(1.0, -1.0, 1.0, -1.0),
+        );
+        let y = _mm_mul_ps(
+            _mm_mul_ps(swizzle!(a, 1, 1, 1, 1), swizzle!(b, 2, 3, 0, 1)),
+            _mm_setr_ps(1.0, 1.0, -1.0, -1.0),
+        );
+        let z = _mm_mul_ps(
+            _mm_mul_ps(swizzle!(a, 2, 2, 2, 2), swizzle!(b, 1, 0, 3, 2)),
+            _mm_setr_ps(-1.0, 1.0, 1.0, -1.0),
+        );
+
+        store(&mut data, _mm_add_ps(_mm_add_ps(w, x), _mm_add_ps(y, z)));
+    }
+
+    data
+}
@@ -0,0 +1,59 @@
+use crate::core::maths::{Normed, Point3};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    pub centre: Point3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub const fn new(centre: Point3, radius: f32) -> Self {
+        Self { centre, radius }
+    }
+
+    pub fn contains_point(&self, point: &Point3) -> bool {
+        self.centre.vector_to(point).norm_squared() <= self.radius * self.radius
+    }
+
+    pub fn intersects(&self, rhs: &Sphere) -> bool {
+        let radii = self.radius + rhs.radius;
+
+        self.centre.vector_to(&rhs.centre).norm_squared() <= radii * radii
+    }
+
+    /// The point on the sphere closest to `point`, the centre is equally close to all of them so
+    /// the one furthest along +X is picked for it.
+    pub fn closest_point(&self, point: &Point3) -> Point3 {
+        let offset = self.centre.vector_to(point);
+        let distance = offset.norm();
+
+        if distance == 0.0 {
+            return Point3::new(self.centre.x + self.radius, self.centre.y, self.centre.z);
+        }
+
+        self.centre + offset * (self.radius / distance)
+    }
+
+    /// The distance from the sphere to `point`, negative inside it.
+    pub fn signed_distance(&self, point: &Point3) -> f32 {
+        self.centre.vector_to(point).norm() - self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_point() {
+        let sphere = Sphere::new(Point3::new(1.0, 0.0, 0.0), 2.0);
+        let Point3 { x, y, z } = sphere.closest_point(&Point3::new(1.0, 5.0, 0.0));
+
+        assert_eq!([x, y, z], [1.0, 2.0, 0.0]);
+        assert_eq!(sphere.signed_distance(&Point3::new(1.0, 5.0, 0.0)), 3.0);
+        assert_eq!(sphere.signed_distance(&Point3::new(1.0, 0.5, 0.0)), -1.5);
+        assert!(sphere.intersects(&Sphere::new(Point3::new(4.0, 0.0, 0.0), 1.0)));
+        assert!(!sphere.intersects(&Sphere::new(Point3::new(4.5, 0.0, 0.0), 1.0)));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::core::maths::{Angle, Normed, Vec3};
+
+/// A 3D vector in spherical coordinates: its length, its angle down from +Y, and its angle about
+/// the Y axis from +Z towards +X.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SphericalVec3 {
+    pub radius:      f32,
+    pub inclination: Angle,
+    pub azimuth:     Angle,
+}
+
+impl SphericalVec3 {
+    pub const fn new(radius: f32, inclination: Angle, azimuth: Angle) -> Self {
+        Self {
+            radius,
+            inclination,
+            azimuth,
+        }
+    }
+}
+
+impl From<Vec3> for SphericalVec3 {
+    fn from(vec: Vec3) -> Self {
+        let radius = vec.norm();
+
+        // NOTE:
+        // The zero vector has no direction, it's given an inclination of zero rather than NaN.
+        let inclination = if radius == 0.0 {
+            0.0
+        } else {
+            (vec.y / radius).clamp(-1.0, 1.0).acos()
+        };
+
+        Self {
+            radius,
+            inclination: Angle::radians(inclination),
+            azimuth: Angle::atan2(vec.x, vec.z),
+        }
+    }
+}
+
+impl From<SphericalVec3> for Vec3 {
+    fn from(
+        SphericalVec3 {
+            radius,
+            inclination,
+            azimuth,
+        }: SphericalVec3,
+    ) -> Self {
+        let (inclination_sin, inclination_cos) = inclination.sin_cos();
+        let (azimuth_sin, azimuth_cos) = azimuth.sin_cos();
+
+        Vec3::new(
+            radius * inclination_sin * azimuth_sin,
+            radius * inclination_cos,
+            radius * inclination_sin * azimuth_cos,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::core::maths::fuzzy;
+
+    use super::*;
+
+    #[test]
+    fn axes() {
+        let up = SphericalVec3::from(Vec3::new(0.0, 2.0, 0.0));
+        let forward = SphericalVec3::from(Vec3::new(0.0, 0.0, 1.0));
+        let right = SphericalVec3::from(Vec3::new(1.0, 0.0, 0.0));
+
+        assert_eq!(up.radius, 2.0);
+        assert_eq!(up.inclination.into_radians(), 0.0);
+        assert!(fuzzy::eq(forward.inclination.into_radians(), FRAC_PI_2));
+        assert_eq!(forward.azimuth.into_radians(), 0.0);
+        assert!(fuzzy::eq(right.azimuth.into_radians(), FRAC_PI_2));
+    }
+
+    #[test]
+    fn round_trip() {
+        let vec = Vec3::new(-1.0, 2.0, -3.0);
+        let Vec3 { x, y, z } = Vec3::from(SphericalVec3::from(vec));
+
+        assert!(fuzzy::eq_with_epsilon(x, vec.x, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(y, vec.y, 1e-5));
+        assert!(fuzzy::eq_with_epsilon(z, vec.z, 1e-5));
+    }
+}
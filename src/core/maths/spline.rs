@@ -0,0 +1,15 @@
+mod arc_length;
+mod b_spline;
+mod bezier;
+mod catmull_rom;
+mod curve;
+mod hermite;
+
+pub use crate::core::maths::spline::{
+    arc_length::*,
+    b_spline::*,
+    bezier::*,
+    catmull_rom::*,
+    curve::*,
+    hermite::*,
+};
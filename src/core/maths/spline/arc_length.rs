@@ -0,0 +1,117 @@
+use crate::core::maths::{Curve, CurvePoint};
+
+/// The distance along a curve at evenly spaced values of `t`, for converting distances back into
+/// `t`. Curves speed up and slow down along their length, so moving along one at a constant
+/// speed, e.g. a camera on a rail, means moving at a constant distance per second instead.
+#[derive(Debug, Clone)]
+pub struct ArcLengthTable {
+    lengths: Vec<f32>,
+}
+
+impl ArcLengthTable {
+    /// Measures the curve `samples` times per segment, more samples give more accurate distances.
+    pub fn new<V, C>(curve: &C, samples: usize) -> Self
+    where
+        V: CurvePoint,
+        C: Curve<V> + ?Sized,
+    {
+        assert!(samples > 0, "An arc length table needs at least 1 sample per segment");
+
+        let step = 1.0 / samples as f32;
+        let mut lengths = vec![0.0];
+        let mut length = 0.0;
+
+        for index in 0..curve.segment_count() {
+            let segment = curve.segment(index);
+
+            for sample in 0..samples {
+                let t = sample as f32 * step;
+
+                length += segment.length_between(t, t + step);
+                lengths.push(length);
+            }
+        }
+
+        Self { lengths }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// The `t` that is `distance` along the curve.
+    pub fn parameter_at(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0.0, self.length());
+        let index = self.lengths.partition_point(|length| *length < distance).max(1);
+        let (before, after) = (self.lengths[index - 1], self.lengths[index]);
+
+        let fraction = if after > before {
+            (distance - before) / (after - before)
+        } else {
+            0.0
+        };
+
+        (index as f32 - 1.0 + fraction) / (self.lengths.len() - 1) as f32
+    }
+
+    /// How far along the curve `t` is.
+    pub fn distance_at(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0) * (self.lengths.len() - 1) as f32;
+        let index = (t as usize).min(self.lengths.len() - 2);
+        let (before, after) = (self.lengths[index], self.lengths[index + 1]);
+
+        before + (after - before) * (t - index as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_approx_eq,
+        core::maths::{CatmullRom, CubicBezier, Vec2},
+    };
+
+    use super::*;
+
+    #[test]
+    fn evenly_spaces_points() {
+        // The control points are bunched up at the start, so it's much slower there.
+        let line = CubicBezier::new(
+            Vec2::zero(),
+            Vec2::zero(),
+            Vec2::new(0.1, 0.0),
+            Vec2::new(4.0, 0.0),
+        );
+        let table = ArcLengthTable::new(&line, 64);
+
+        assert_approx_eq!(table.length(), 4.0, epsilon = 1e-4);
+
+        for step in 0..=8 {
+            let distance = step as f32 * 0.5;
+            let point = line.point_at(table.parameter_at(distance));
+
+            assert_approx_eq!(point.x, distance, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn distances_round_trip() {
+        let path = CatmullRom::new(vec![
+            Vec2::zero(),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(3.0, 2.0),
+            Vec2::new(4.0, 0.0),
+        ]);
+        let table = ArcLengthTable::new(&path, 32);
+
+        assert_approx_eq!(table.length(), path.length(), epsilon = 1e-4);
+        assert_approx_eq!(table.parameter_at(-1.0), 0.0);
+        assert_approx_eq!(table.parameter_at(100.0), 1.0);
+
+        for step in 0..=10 {
+            let t = step as f32 / 10.0;
+
+            assert_approx_eq!(table.parameter_at(table.distance_at(t)), t, epsilon = 1e-4);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::core::maths::{spline::curve::control_point, CubicBezier, Curve, CurvePoint};
+
+/// A uniform cubic B-spline, which is pulled towards its points rather than going through them.
+/// It's smoother than a Catmull-Rom spline, curving just as much either side of every point, so
+/// it suits things that shouldn't bend sharply, like roads.
+///
+/// Open splines still start on their first point and end on their last.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BSpline<V> {
+    points: Vec<V>,
+    closed: bool,
+}
+
+impl<V> BSpline<V>
+where
+    V: CurvePoint,
+{
+    pub fn new(points: Vec<V>) -> Self {
+        assert!(points.len() >= 2, "A B-spline needs at least 2 points");
+
+        Self {
+            points,
+            closed: false,
+        }
+    }
+
+    /// A loop, which goes from the last point back round to the first.
+    pub fn closed(points: Vec<V>) -> Self {
+        Self {
+            closed: true,
+            ..Self::new(points)
+        }
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+impl<V> Curve<V> for BSpline<V>
+where
+    V: CurvePoint,
+{
+    fn segment_count(&self) -> usize {
+        if self.closed {
+            self.points.len()
+        } else {
+            self.points.len() - 1
+        }
+    }
+
+    fn segment(&self, index: usize) -> CubicBezier<V> {
+        let index = index as isize;
+        let a = control_point(&self.points, index - 1, self.closed);
+        let b = control_point(&self.points, index, self.closed);
+        let c = control_point(&self.points, index + 1, self.closed);
+        let d = control_point(&self.points, index + 2, self.closed);
+
+        CubicBezier::new(
+            (a + b * 4.0 + c) * (1.0 / 6.0),
+            (b * 2.0 + c) * (1.0 / 3.0),
+            (b + c * 2.0) * (1.0 / 3.0),
+            (b + c * 4.0 + d) * (1.0 / 6.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_approx_eq, core::maths::Vec2};
+
+    use super::*;
+
+    fn points() -> Vec<Vec2> {
+        vec![
+            Vec2::zero(),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(3.0, 2.0),
+            Vec2::new(4.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn starts_and_ends_on_its_ends() {
+        let spline = BSpline::new(points());
+
+        assert_approx_eq!(spline.point_at(0.0), points()[0], epsilon = 1e-5);
+        assert_approx_eq!(spline.point_at(1.0), points()[3], epsilon = 1e-5);
+    }
+
+    #[test]
+    fn is_pulled_towards_its_points() {
+        let spline = BSpline::new(points());
+        let point = spline.point_at(1.0 / 3.0);
+
+        // Short of the second point, but pulled up towards it.
+        assert!(point.y > 1.0 && point.y < 2.0);
+    }
+
+    #[test]
+    fn curvature_is_continuous() {
+        let spline = BSpline::closed(points());
+
+        for index in 0..4 {
+            let t = index as f32 / 4.0;
+
+            assert_approx_eq!(
+                spline.second_derivative_at(t - 1e-4),
+                spline.second_derivative_at(t + 1e-4),
+                epsilon = 1e-1
+            );
+        }
+
+        assert_approx_eq!(spline.point_at(0.0), spline.point_at(1.0), epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::core::maths::{Curve, CurvePoint};
+
+// NOTE:
+// Lengths are integrated with Gauss-Legendre quadrature, which is exact for polynomials up to
+// degree 9. The speed along a curve isn't a polynomial, so each stretch is split up a few times.
+const LENGTH_SUBDIVISIONS: usize = 4;
+
+#[rustfmt::skip]
+const GAUSS_LEGENDRE: [(f32, f32); 5] = [
+    ( 0.0,          0.568_888_9),
+    (-0.538_469_3,  0.478_628_67),
+    ( 0.538_469_3,  0.478_628_67),
+    (-0.906_179_85, 0.236_926_88),
+    ( 0.906_179_85, 0.236_926_88),
+];
+
+/// A cubic Bezier curve, which starts heading towards `start_control` and ends coming from
+/// `end_control`, without going through either.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier<V> {
+    pub start:         V,
+    pub start_control: V,
+    pub end_control:   V,
+    pub end:           V,
+}
+
+impl<V> CubicBezier<V>
+where
+    V: CurvePoint,
+{
+    pub const fn new(start: V, start_control: V, end_control: V, end: V) -> Self {
+        Self {
+            start,
+            start_control,
+            end_control,
+            end,
+        }
+    }
+
+    /// The curve from `start` to `end` with the given velocities at either end.
+    pub fn from_hermite(start: V, start_tangent: V, end: V, end_tangent: V) -> Self {
+        Self::new(start, start + start_tangent * (1.0 / 3.0), end - end_tangent * (1.0 / 3.0), end)
+    }
+
+    pub fn point_at(&self, t: f32) -> V {
+        let inverse = 1.0 - t;
+
+        self.start * (inverse * inverse * inverse)
+            + self.start_control * (3.0 * inverse * inverse * t)
+            + self.end_control * (3.0 * inverse * t * t)
+            + self.end * (t * t * t)
+    }
+
+    /// The velocity at `t`.
+    pub fn derivative_at(&self, t: f32) -> V {
+        let inverse = 1.0 - t;
+
+        (self.start_control - self.start) * (3.0 * inverse * inverse)
+            + (self.end_control - self.start_control) * (6.0 * inverse * t)
+            + (self.end - self.end_control) * (3.0 * t * t)
+    }
+
+    /// The acceleration at `t`.
+    pub fn second_derivative_at(&self, t: f32) -> V {
+        let start = self.start + self.end_control - self.start_control * 2.0;
+        let end = self.start_control + self.end - self.end_control * 2.0;
+
+        start * (6.0 * (1.0 - t)) + end * (6.0 * t)
+    }
+
+    /// The length of the curve between `from` and `to`.
+    pub fn length_between(&self, from: f32, to: f32) -> f32 {
+        let step = (to - from) / LENGTH_SUBDIVISIONS as f32;
+
+        (0..LENGTH_SUBDIVISIONS)
+            .map(|subdivision| {
+                let middle = from + (subdivision as f32 + 0.5) * step;
+
+                GAUSS_LEGENDRE
+                    .iter()
+                    .map(|(node, weight)| {
+                        weight * self.derivative_at(middle + node * step / 2.0).norm()
+                    })
+                    .sum::<f32>()
+                    * step
+                    / 2.0
+            })
+            .sum()
+    }
+
+    /// Splits the curve in two at `t`, into one curve covering everything before it and another
+    /// covering everything after.
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        // Source:
+        // De Casteljau's algorithm, each point is found by lerping between the ones before.
+        let lerp = |start: V, end: V| start + (end - start) * t;
+
+        let a = lerp(self.start, self.start_control);
+        let b = lerp(self.start_control, self.end_control);
+        let c = lerp(self.end_control, self.end);
+        let d = lerp(a, b);
+        let e = lerp(b, c);
+        let middle = lerp(d, e);
+
+        (Self::new(self.start, a, d, middle), Self::new(middle, e, c, self.end))
+    }
+}
+
+impl<V> Curve<V> for CubicBezier<V>
+where
+    V: CurvePoint,
+{
+    fn segment_count(&self) -> usize {
+        1
+    }
+
+    fn segment(&self, _: usize) -> CubicBezier<V> {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_approx_eq,
+        core::maths::{Vec2, Vec3},
+    };
+
+    use super::*;
+
+    fn curve() -> CubicBezier<Vec3> {
+        CubicBezier::new(
+            Vec3::zero(),
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec3::new(3.0, 2.0, 1.0),
+            Vec3::new(4.0, 0.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn goes_from_start_to_end() {
+        let curve = curve();
+
+        assert_approx_eq!(curve.point_at(0.0), curve.start);
+        assert_approx_eq!(curve.point_at(1.0), curve.end);
+        assert_approx_eq!(curve.derivative_at(0.0), (curve.start_control - curve.start) * 3.0);
+        assert_approx_eq!(curve.derivative_at(1.0), (curve.end - curve.end_control) * 3.0);
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let curve = curve();
+        let h = 1e-3;
+
+        for step in 1..10 {
+            let t = step as f32 / 10.0;
+            let velocity = (curve.point_at(t + h) - curve.point_at(t - h)) * (0.5 / h);
+            let acceleration =
+                (curve.derivative_at(t + h) - curve.derivative_at(t - h)) * (0.5 / h);
+
+            assert_approx_eq!(curve.derivative_at(t), velocity, epsilon = 1e-2);
+            assert_approx_eq!(curve.second_derivative_at(t), acceleration, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn splits_into_the_same_curve() {
+        let curve = curve();
+        let (before, after) = curve.split(0.25);
+
+        assert_approx_eq!(before.point_at(0.5), curve.point_at(0.125), epsilon = 1e-5);
+        assert_approx_eq!(after.point_at(0.5), curve.point_at(0.625), epsilon = 1e-5);
+        assert_approx_eq!(before.length() + after.length(), curve.length(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn hermite_curves_have_their_tangents() {
+        let start_tangent = Vec2::new(3.0, 0.0);
+        let end_tangent = Vec2::new(0.0, -6.0);
+        let curve =
+            CubicBezier::from_hermite(Vec2::zero(), start_tangent, Vec2::unit_x(), end_tangent);
+
+        assert_approx_eq!(curve.derivative_at(0.0), start_tangent);
+        assert_approx_eq!(curve.derivative_at(1.0), end_tangent);
+    }
+}
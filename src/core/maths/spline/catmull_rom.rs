@@ -0,0 +1,132 @@
+use crate::core::maths::{spline::curve::control_point, CubicBezier, Curve, CurvePoint};
+
+/// A smooth curve through every point, heading from the point before each one towards the point
+/// after it. Handy for paths that have to hit their waypoints, such as camera rails.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CatmullRom<V> {
+    points: Vec<V>,
+    closed: bool,
+}
+
+impl<V> CatmullRom<V>
+where
+    V: CurvePoint,
+{
+    pub fn new(points: Vec<V>) -> Self {
+        assert!(points.len() >= 2, "A Catmull-Rom spline needs at least 2 points");
+
+        Self {
+            points,
+            closed: false,
+        }
+    }
+
+    /// A loop, which goes from the last point back round to the first.
+    pub fn closed(points: Vec<V>) -> Self {
+        Self {
+            closed: true,
+            ..Self::new(points)
+        }
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn tangent(&self, index: isize) -> V {
+        let before = control_point(&self.points, index - 1, self.closed);
+        let after = control_point(&self.points, index + 1, self.closed);
+
+        (after - before) * 0.5
+    }
+}
+
+impl<V> Curve<V> for CatmullRom<V>
+where
+    V: CurvePoint,
+{
+    fn segment_count(&self) -> usize {
+        if self.closed {
+            self.points.len()
+        } else {
+            self.points.len() - 1
+        }
+    }
+
+    fn segment(&self, index: usize) -> CubicBezier<V> {
+        let index = index as isize;
+
+        CubicBezier::from_hermite(
+            control_point(&self.points, index, self.closed),
+            self.tangent(index),
+            control_point(&self.points, index + 1, self.closed),
+            self.tangent(index + 1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_approx_eq, core::maths::Vec3};
+
+    use super::*;
+
+    fn points() -> Vec<Vec3> {
+        vec![
+            Vec3::zero(),
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec3::new(3.0, 2.0, 1.0),
+            Vec3::new(4.0, 0.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn goes_through_every_point() {
+        let spline = CatmullRom::new(points());
+
+        for (index, point) in points().iter().enumerate() {
+            assert_approx_eq!(spline.point_at(index as f32 / 3.0), *point, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn is_smooth_where_segments_meet() {
+        let spline = CatmullRom::new(points());
+        let t = 1.0 / 3.0;
+
+        assert_approx_eq!(
+            spline.derivative_at(t - 1e-4),
+            spline.derivative_at(t + 1e-4),
+            epsilon = 1e-2
+        );
+
+        // Through the second point, heading from the first to the third.
+        assert_approx_eq!(
+            spline.derivative_at(t),
+            (points()[2] - points()[0]) * 0.5 * 3.0,
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn closed_splines_loop() {
+        let spline = CatmullRom::closed(points());
+
+        assert_eq!(spline.segment_count(), 4);
+        assert_approx_eq!(spline.point_at(1.0), points()[0], epsilon = 1e-5);
+        assert_approx_eq!(spline.derivative_at(0.0), spline.derivative_at(1.0), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn straight_lines_stay_straight() {
+        let spline = CatmullRom::new(vec![Vec3::zero(), Vec3::new(2.0, 0.0, 0.0)]);
+
+        assert_approx_eq!(spline.point_at(0.25), Vec3::new(0.5, 0.0, 0.0), epsilon = 1e-5);
+        assert_approx_eq!(spline.length(), 2.0, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,247 @@
+use std::ops::{Add, DivAssign, Mul, MulAssign, Sub};
+
+use crate::core::maths::{CubicBezier, Normed, Unit, Vec2, Vec3};
+
+// NOTE:
+// Closest points are found by sampling every segment for a starting guess and then refining it
+// with Newton's method. Too few samples and the guess can land near the wrong bend of the curve.
+const CLOSEST_SAMPLES:    usize = 16;
+const CLOSEST_ITERATIONS: usize = 8;
+
+/// Vectors a curve can be drawn through, i.e. `Vec2` and `Vec3`.
+pub trait CurvePoint:
+    Copy
+    + Normed<Scalar = f32>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<f32, Output = Self>
+    + MulAssign<f32>
+    + DivAssign<f32>
+{
+    fn dot(&self, rhs: &Self) -> f32;
+}
+
+impl CurvePoint for Vec2 {
+    fn dot(&self, rhs: &Self) -> f32 {
+        Vec2::dot(self, rhs)
+    }
+}
+
+impl CurvePoint for Vec3 {
+    fn dot(&self, rhs: &Self) -> f32 {
+        Vec3::dot(self, rhs)
+    }
+}
+
+/// A curve made of cubic segments, parameterised by `t` from 0 at its start to 1 at its end.
+/// Every segment takes up an equal share of `t`, however long it is, so use an `ArcLengthTable`
+/// to move along a curve at a constant speed.
+pub trait Curve<V>
+where
+    V: CurvePoint,
+{
+    fn segment_count(&self) -> usize;
+
+    /// The segment at `index` as a Bezier curve, which every cubic segment can be written as.
+    fn segment(&self, index: usize) -> CubicBezier<V>;
+
+    fn point_at(&self, t: f32) -> V {
+        let (index, t) = locate(t, self.segment_count());
+
+        self.segment(index).point_at(t)
+    }
+
+    /// The velocity at `t`.
+    fn derivative_at(&self, t: f32) -> V {
+        let segments = self.segment_count();
+        let (index, t) = locate(t, segments);
+
+        self.segment(index).derivative_at(t) * segments as f32
+    }
+
+    /// The acceleration at `t`.
+    fn second_derivative_at(&self, t: f32) -> V {
+        let segments = self.segment_count();
+        let (index, t) = locate(t, segments);
+
+        self.segment(index).second_derivative_at(t) * (segments * segments) as f32
+    }
+
+    /// The direction of the curve at `t`. Not a number where the curve stops, e.g. where a
+    /// control point sits on top of an end.
+    fn tangent_at(&self, t: f32) -> Unit<V> {
+        let derivative = self.derivative_at(t);
+
+        Unit::from_normalised(derivative * derivative.norm().recip())
+    }
+
+    fn length(&self) -> f32 {
+        (0..self.segment_count())
+            .map(|index| self.segment(index).length_between(0.0, 1.0))
+            .sum()
+    }
+
+    /// The `t` of the point on the curve closest to `point`.
+    fn project(&self, point: &V) -> f32 {
+        let segments = self.segment_count();
+        let mut closest = (0, 0.0, f32::INFINITY);
+
+        for index in 0..segments {
+            let segment = self.segment(index);
+
+            for sample in 0..=CLOSEST_SAMPLES {
+                let t = sample as f32 / CLOSEST_SAMPLES as f32;
+                let distance_squared = (segment.point_at(t) - *point).norm_squared();
+
+                if distance_squared < closest.2 {
+                    closest = (index, t, distance_squared);
+                }
+            }
+        }
+
+        let (index, t, _) = closest;
+
+        (index as f32 + refine(&self.segment(index), point, t)) / segments as f32
+    }
+
+    fn closest_point(&self, point: &V) -> V {
+        self.point_at(self.project(point))
+    }
+
+    fn distance_to(&self, point: &V) -> f32 {
+        (self.closest_point(point) - *point).norm()
+    }
+}
+
+/// Splits `t` into the segment it falls in and how far through that segment it is.
+fn locate(t: f32, segments: usize) -> (usize, f32) {
+    let t = t.clamp(0.0, 1.0) * segments as f32;
+    let index = (t as usize).min(segments - 1);
+
+    (index, t - index as f32)
+}
+
+/// The point at `index`, wrapping round when the curve is closed. Open curves are extended past
+/// their ends by mirroring the next point along through the end.
+pub(in crate::core::maths::spline) fn control_point<V>(
+    points: &[V],
+    index: isize,
+    closed: bool,
+) -> V
+where
+    V: CurvePoint,
+{
+    let last = points.len() as isize - 1;
+
+    if closed {
+        points[index.rem_euclid(points.len() as isize) as usize]
+    } else if index < 0 {
+        points[0] * 2.0 - points[(-index).min(last) as usize]
+    } else if index > last {
+        points[last as usize] * 2.0 - points[(2 * last - index).max(0) as usize]
+    } else {
+        points[index as usize]
+    }
+}
+
+/// Moves `t` towards where the segment is closest to `point`, which is where the vector to the
+/// point is perpendicular to the curve.
+fn refine<V>(segment: &CubicBezier<V>, point: &V, mut t: f32) -> f32
+where
+    V: CurvePoint,
+{
+    for _ in 0..CLOSEST_ITERATIONS {
+        let offset = segment.point_at(t) - *point;
+        let derivative = segment.derivative_at(t);
+        let slope = derivative.dot(&derivative) + offset.dot(&segment.second_derivative_at(t));
+
+        // NOTE:
+        // A slope that isn't positive means `t` is nearer a furthest point than a closest one,
+        // where Newton's method would head the wrong way, so the sampled guess is kept.
+        if slope <= 0.0 {
+            break;
+        }
+
+        t = (t - offset.dot(&derivative) / slope).clamp(0.0, 1.0);
+    }
+
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::{assert_approx_eq, core::maths::CatmullRom};
+
+    use super::*;
+
+    /// The usual Bezier approximation of a quarter of a unit circle.
+    fn quarter_circle() -> CubicBezier<Vec2> {
+        const K: f32 = 0.552_284_8;
+
+        CubicBezier::new(
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, K),
+            Vec2::new(K, 1.0),
+            Vec2::new(0.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn lengths() {
+        let line = CubicBezier::new(
+            Vec3::zero(),
+            Vec3::new(0.5, 0.0, 0.0),
+            Vec3::new(2.5, 0.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        );
+
+        assert_approx_eq!(line.length(), 3.0, epsilon = 1e-5);
+        assert_approx_eq!(quarter_circle().length(), FRAC_PI_2, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn closest_points() {
+        let arc = quarter_circle();
+        let point = Vec2::new(2.0, 2.0);
+
+        assert_approx_eq!(arc.project(&point), 0.5, epsilon = 1e-4);
+        assert_approx_eq!(arc.distance_to(&point), 8f32.sqrt() - 1.0, epsilon = 1e-3);
+
+        // Points beyond the ends are closest to the ends.
+        assert_approx_eq!(arc.project(&Vec2::new(2.0, -1.0)), 0.0);
+        assert_approx_eq!(arc.project(&Vec2::new(-1.0, 2.0)), 1.0);
+    }
+
+    #[test]
+    fn closest_points_across_segments() {
+        let path = CatmullRom::new(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(3.0, 1.0),
+        ]);
+
+        for point in [Vec2::new(1.0, 0.5), Vec2::new(2.0, 2.0), Vec2::new(1.5, -1.0)].iter() {
+            let distance = path.distance_to(point);
+
+            // Nowhere else on the path is any closer.
+            for sample in 0..=1000 {
+                let other = path.point_at(sample as f32 / 1000.0);
+
+                assert!((other - *point).norm() > distance - 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn derivatives_are_over_the_whole_curve() {
+        let path = CatmullRom::new(vec![Vec2::zero(), Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)]);
+
+        // Two segments, each one unit long, so the curve is traced at two units per unit of `t`.
+        assert_approx_eq!(path.derivative_at(0.5), Vec2::new(2.0, 0.0), epsilon = 1e-5);
+        assert_approx_eq!(*path.tangent_at(0.25), Vec2::new(1.0, 0.0), epsilon = 1e-5);
+        assert_approx_eq!(path.second_derivative_at(0.25), Vec2::zero(), epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::core::maths::{CubicBezier, Curve, CurvePoint};
+
+/// A curve through every point, leaving each one with its tangent.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Hermite<V> {
+    points:   Vec<V>,
+    tangents: Vec<V>,
+}
+
+impl<V> Hermite<V>
+where
+    V: CurvePoint,
+{
+    /// The tangents are the velocities at each point, relative to a segment's own `t`, so longer
+    /// tangents pull the curve further out along them.
+    pub fn new(points: Vec<V>, tangents: Vec<V>) -> Self {
+        assert!(points.len() >= 2, "A Hermite spline needs at least 2 points");
+        assert_eq!(points.len(), tangents.len(), "Every point needs a tangent");
+
+        Self { points, tangents }
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn tangents(&self) -> &[V] {
+        &self.tangents
+    }
+}
+
+impl<V> Curve<V> for Hermite<V>
+where
+    V: CurvePoint,
+{
+    fn segment_count(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    fn segment(&self, index: usize) -> CubicBezier<V> {
+        CubicBezier::from_hermite(
+            self.points[index],
+            self.tangents[index],
+            self.points[index + 1],
+            self.tangents[index + 1],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_approx_eq, core::maths::Vec2};
+
+    use super::*;
+
+    fn spline() -> Hermite<Vec2> {
+        Hermite::new(
+            vec![Vec2::zero(), Vec2::new(1.0, 1.0), Vec2::new(2.0, 0.0)],
+            vec![Vec2::new(1.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, -1.0)],
+        )
+    }
+
+    #[test]
+    fn goes_through_every_point() {
+        let spline = spline();
+
+        assert_approx_eq!(spline.point_at(0.0), Vec2::zero());
+        assert_approx_eq!(spline.point_at(0.5), Vec2::new(1.0, 1.0));
+        assert_approx_eq!(spline.point_at(1.0), Vec2::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn leaves_every_point_along_its_tangent() {
+        let spline = spline();
+
+        for (index, tangent) in spline.tangents().iter().enumerate() {
+            let t = index as f32 / 2.0;
+
+            assert_approx_eq!(*spline.tangent_at(t), *tangent, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn every_point_needs_a_tangent() {
+        Hermite::new(vec![Vec2::zero(), Vec2::unit_x()], vec![Vec2::unit_x()]);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::core::maths::{Normed, Plane, Point3, Unit, Vec3};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle {
+    pub a: Point3,
+    pub b: Point3,
+    pub c: Point3,
+}
+
+impl Triangle {
+    pub const fn new(a: Point3, b: Point3, c: Point3) -> Self {
+        Self { a, b, c }
+    }
+
+    /// The direction the front face points in, the side from which the corners appear clockwise.
+    pub fn normal(&self) -> Unit<Vec3> {
+        self.plane().normal
+    }
+
+    pub fn plane(&self) -> Plane {
+        Plane::from_points(&self.a, &self.b, &self.c)
+    }
+
+    pub fn area(&self) -> f32 {
+        self.a.vector_to(&self.b).cross(&self.a.vector_to(&self.c)).norm() * 0.5
+    }
+
+    pub fn centroid(&self) -> Point3 {
+        let sum = self.a.vector_from_origin()
+            + self.b.vector_from_origin()
+            + self.c.vector_from_origin();
+
+        Point3::identity() + sum / 3.0
+    }
+
+    /// The weights of `a`, `b` and `c` that add up to `point`, once it's projected onto the
+    /// triangle's plane. All three are between 0 and 1 for points inside the triangle.
+    pub fn barycentric(&self, point: &Point3) -> Vec3 {
+        let ab = self.a.vector_to(&self.b);
+        let ac = self.a.vector_to(&self.c);
+        let ap = self.a.vector_to(point);
+
+        let d00 = ab.dot(&ab);
+        let d01 = ab.dot(&ac);
+        let d11 = ac.dot(&ac);
+        let d20 = ap.dot(&ab);
+        let d21 = ap.dot(&ac);
+        let denominator = d00 * d11 - d01 * d01;
+
+        let v = (d11 * d20 - d01 * d21) / denominator;
+        let w = (d00 * d21 - d01 * d20) / denominator;
+
+        Vec3::new(1.0 - v - w, v, w)
+    }
+
+    /// The point on or inside the triangle closest to `point`.
+    pub fn closest_point(&self, point: &Point3) -> Point3 {
+        // Source:
+        // Real-Time Collision Detection, Christer Ericson, 5.1.5
+        let Self { a, b, c } = *self;
+        let ab = a.vector_to(&b);
+        let ac = a.vector_to(&c);
+
+        let ap = a.vector_to(point);
+        let d1 = ab.dot(&ap);
+        let d2 = ac.dot(&ap);
+
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = b.vector_to(point);
+        let d3 = ab.dot(&bp);
+        let d4 = ac.dot(&bp);
+
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = c.vector_to(point);
+        let d5 = ab.dot(&cp);
+        let d6 = ac.dot(&cp);
+
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + b.vector_to(&c) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denominator = (va + vb + vc).recip();
+
+        a + ab * (vb * denominator) + ac * (vc * denominator)
+    }
+
+    pub fn distance_to(&self, point: &Point3) -> f32 {
+        self.closest_point(point).vector_to(point).norm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, 2.0),
+            Point3::new(2.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn normal_and_area() {
+        let normal = triangle().normal();
+
+        assert_eq!([normal.x, normal.y, normal.z], [0.0, 1.0, 0.0]);
+        assert_eq!(triangle().area(), 2.0);
+    }
+
+    #[test]
+    fn closest_point() {
+        let inside = triangle().closest_point(&Point3::new(0.5, 3.0, 0.5));
+        let edge = triangle().closest_point(&Point3::new(2.0, -1.0, 2.0));
+        let corner = triangle().closest_point(&Point3::new(-1.0, 0.0, -1.0));
+
+        assert_eq!([inside.x, inside.y, inside.z], [0.5, 0.0, 0.5]);
+        assert_eq!([edge.x, edge.y, edge.z], [1.0, 0.0, 1.0]);
+        assert_eq!([corner.x, corner.y, corner.z], [0.0, 0.0, 0.0]);
+        assert_eq!(triangle().distance_to(&Point3::new(0.5, 3.0, 0.5)), 3.0);
+    }
+
+    #[test]
+    fn barycentric() {
+        let Vec3 { x, y, z } = triangle().barycentric(&Point3::new(1.0, 5.0, 0.5));
+
+        assert_eq!([x, y, z], [0.25, 0.25, 0.5]);
+    }
+}
@@ -2,11 +2,15 @@ use std::ops::{Deref, DerefMut, DivAssign, MulAssign};
 
 use bytemuck::{Pod, Zeroable};
 
-pub trait Normed: MulAssign<f32> + DivAssign<f32> {
-    fn norm_squared(&self) -> f32;
+use crate::core::maths::Scalar;
 
-    fn norm(&self) -> f32 {
-        f32::sqrt(self.norm_squared())
+pub trait Normed {
+    type Scalar: Scalar;
+
+    fn norm_squared(&self) -> Self::Scalar;
+
+    fn norm(&self) -> Self::Scalar {
+        self.norm_squared().sqrt()
     }
 }
 
@@ -18,6 +22,16 @@ where
 impl<T> Unit<T>
 where
     T: Normed,
+{
+    /// Assumes that the value is already normalised.
+    pub const fn from_normalised(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Unit<T>
+where
+    T: Normed + MulAssign<T::Scalar> + DivAssign<T::Scalar>,
 {
     pub fn new(value: T) -> Self {
         let mut unit = Self(value);
@@ -26,11 +40,6 @@ where
         unit
     }
 
-    /// Assumes that the value is already normalised.
-    pub const fn from_normalised(value: T) -> Self {
-        Self(value)
-    }
-
     /// Normalizes this vector again. This is useful when repeated computations
     /// might cause a drift in the norm because of float inaccuracies.
     ///
@@ -45,7 +54,7 @@ where
     /// This is useful when repeated computations might cause a drift in the norm
     /// because of float inaccuracies.
     pub fn normalise_fast(&mut self) {
-        self.0 *= 0.5 * (3.0 - self.0.norm_squared());
+        self.0 *= T::Scalar::HALF * (T::Scalar::from_f32(3.0) - self.0.norm_squared());
     }
 }
 
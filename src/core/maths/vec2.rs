@@ -2,62 +2,66 @@ use std::borrow::Borrow;
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::core::maths::{fuzzy, Angle, Mat4, Normed, Unit};
+use crate::core::maths::{Angle, Normed, Scalar, TMat4, Unit};
+
+pub type Vec2 = TVec2<f32>;
+pub type DVec2 = TVec2<f64>;
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Vec2 {
-    pub x: f32,
-    pub y: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TVec2<S> {
+    pub x: S,
+    pub y: S,
 }
 
-impl Vec2 {
-    pub const fn new(x: f32, y: f32) -> Self {
+impl<S> TVec2<S>
+where
+    S: Scalar,
+{
+    pub const fn new(x: S, y: S) -> Self {
         Self { x, y }
     }
 
-    pub fn lerp(start: &Self, end: &Self, t: f32) -> Unit<Self> {
-        let mid = (1.0 - t) * start + t * end;
+    pub fn lerp(start: &Self, end: &Self, t: S) -> Unit<Self> {
+        let mid = start * (S::ONE - t) + end * t;
         let mid = mid / mid.norm();
 
         Unit::new(mid)
     }
 
-    pub const fn splat(value: f32) -> Self {
+    pub const fn splat(value: S) -> Self {
         Self::new(value, value)
     }
 
     pub const fn zero() -> Self {
-        Self::splat(0.0)
+        Self::splat(S::ZERO)
     }
 
     pub const fn positive_one() -> Self {
-        Self::splat(1.0)
+        Self::splat(S::ONE)
     }
 
     pub const fn negative_one() -> Self {
-        Self::splat(-1.0)
+        Self::splat(S::NEG_ONE)
     }
 
     pub const fn unit_x() -> Self {
-        Self::new(1.0, 0.0)
+        Self::new(S::ONE, S::ZERO)
     }
 
     pub const fn unit_y() -> Self {
-        Self::new(0.0, 1.0)
+        Self::new(S::ZERO, S::ONE)
     }
 
     pub const fn positive_infinity() -> Self {
-        Self::splat(f32::INFINITY)
+        Self::splat(S::INFINITY)
     }
 
     pub const fn negative_infinity() -> Self {
-        Self::splat(f32::NEG_INFINITY)
+        Self::splat(S::NEG_INFINITY)
     }
 
-    pub fn dot(&self, rhs: &Self) -> f32 {
-        let rhs = rhs.borrow();
-
+    pub fn dot(&self, rhs: &Self) -> S {
         self.x * rhs.x + self.y * rhs.y
     }
 
@@ -72,7 +76,7 @@ impl Vec2 {
         Self::new(-self.y, self.x)
     }
 
-    pub fn left_perpendicular_dot(&self, rhs: &Self) -> f32 {
+    pub fn left_perpendicular_dot(&self, rhs: &Self) -> S {
         self.left_perpendicular().dot(rhs)
     }
 
@@ -80,12 +84,13 @@ impl Vec2 {
         Self::new(self.y, -self.x)
     }
 
-    pub fn right_perpendicular_dot(&self, rhs: &Self) -> f32 {
+    pub fn right_perpendicular_dot(&self, rhs: &Self) -> S {
         self.right_perpendicular().dot(rhs)
     }
 
     pub fn rotate(&self, angle: Angle) -> Self {
         let (sin, cos) = angle.sin_cos();
+        let (sin, cos) = (S::from_f32(sin), S::from_f32(cos));
 
         Self {
             x: self.x * cos - self.y * sin,
@@ -97,41 +102,57 @@ impl Vec2 {
         *self = self.rotate(angle)
     }
 
-    pub fn transform(&self, matrix: &Mat4) -> Self {
-        let matrix = matrix.borrow();
-
+    pub fn transform(&self, matrix: &TMat4<S>) -> Self {
         let x = self.x * matrix[0] + self.y * matrix[4] + matrix[12];
         let y = self.x * matrix[1] + self.y * matrix[5] + matrix[13];
 
         Self { x, y }
     }
 
-    pub fn transform_by(&mut self, matrix: &Mat4) {
+    pub fn transform_by(&mut self, matrix: &TMat4<S>) {
         *self = self.transform(matrix);
     }
+
+    /// Converts to another scalar type, e.g. an `f64` simulation position to `f32` for the GPU.
+    pub fn cast<T>(&self) -> TVec2<T>
+    where
+        T: Scalar,
+    {
+        TVec2::new(T::from_f64(self.x.to_f64()), T::from_f64(self.y.to_f64()))
+    }
 }
 
-impl Normed for Vec2 {
-    fn norm_squared(&self) -> f32 {
+impl<S> Normed for TVec2<S>
+where
+    S: Scalar,
+{
+    type Scalar = S;
+
+    fn norm_squared(&self) -> S {
         self.dot(self)
     }
 }
 
-impl From<[f32; 2]> for Vec2 {
-    fn from([x, y]: [f32; 2]) -> Self {
+unsafe impl<S: Scalar> Pod for TVec2<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TVec2<S> {}
+
+impl<S> From<[S; 2]> for TVec2<S> {
+    fn from([x, y]: [S; 2]) -> Self {
         Self { x, y }
     }
 }
 
-impl From<(f32, f32)> for Vec2 {
-    fn from((x, y): (f32, f32)) -> Self {
+impl<S> From<(S, S)> for TVec2<S> {
+    fn from((x, y): (S, S)) -> Self {
         Self { x, y }
     }
 }
 
-impl<V> From<V> for Unit<Vec2>
+impl<S, V> From<V> for Unit<TVec2<S>>
 where
-    V: Borrow<Vec2>,
+    S: Scalar,
+    V: Borrow<TVec2<S>>,
 {
     fn from(vec: V) -> Self {
         Self::new(*vec.borrow())
@@ -140,7 +161,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::core::maths::Vec3;
+    use crate::core::maths::{fuzzy, Mat4, Vec3};
 
     use super::*;
 
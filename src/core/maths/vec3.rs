@@ -2,65 +2,71 @@ use std::borrow::Borrow;
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::core::maths::{fuzzy, Mat4, Normed, Point3, Quat, Unit};
+use crate::core::maths::{Normed, Scalar, TMat4, TPoint3, TQuat, TVec4, Unit};
+
+pub type Vec3 = TVec3<f32>;
+pub type DVec3 = TVec3<f64>;
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct Vec3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct TVec3<S> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
 }
 
-impl Vec3 {
-    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+impl<S> TVec3<S>
+where
+    S: Scalar,
+{
+    pub const fn new(x: S, y: S, z: S) -> Self {
         Self { x, y, z }
     }
 
-    pub fn lerp(start: &Self, end: &Self, t: f32) -> Unit<Self> {
-        let mid = (1.0 - t) * start + t * end;
+    pub fn lerp(start: &Self, end: &Self, t: S) -> Unit<Self> {
+        let mid = start * (S::ONE - t) + end * t;
         let mid = mid / mid.norm();
 
         Unit::new(mid)
     }
 
-    pub const fn splat(value: f32) -> Self {
+    pub const fn splat(value: S) -> Self {
         Self::new(value, value, value)
     }
 
     pub const fn zero() -> Self {
-        Self::splat(0.0)
+        Self::splat(S::ZERO)
     }
 
     pub const fn positive_one() -> Self {
-        Self::splat(1.0)
+        Self::splat(S::ONE)
     }
 
     pub const fn negative_one() -> Self {
-        Self::splat(-1.0)
+        Self::splat(S::NEG_ONE)
     }
 
     pub const fn unit_x() -> Unit<Self> {
-        Unit::from_normalised(Self::new(1.0, 0.0, 0.0))
+        Unit::from_normalised(Self::new(S::ONE, S::ZERO, S::ZERO))
     }
 
     pub const fn unit_y() -> Unit<Self> {
-        Unit::from_normalised(Self::new(0.0, 1.0, 0.0))
+        Unit::from_normalised(Self::new(S::ZERO, S::ONE, S::ZERO))
     }
 
     pub const fn unit_z() -> Unit<Self> {
-        Unit::from_normalised(Self::new(0.0, 0.0, 1.0))
+        Unit::from_normalised(Self::new(S::ZERO, S::ZERO, S::ONE))
     }
 
     pub const fn positive_infinity() -> Self {
-        Self::splat(f32::INFINITY)
+        Self::splat(S::INFINITY)
     }
 
     pub const fn negative_infinity() -> Self {
-        Self::splat(f32::NEG_INFINITY)
+        Self::splat(S::NEG_INFINITY)
     }
 
-    pub fn dot(&self, rhs: &Self) -> f32 {
+    pub fn dot(&self, rhs: &Self) -> S {
         self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
     }
 
@@ -80,61 +86,82 @@ impl Vec3 {
         )
     }
 
-    pub fn rotate(&self, quat: &Quat) -> Self {
-        (quat * Quat::new(0.0, *self) * quat.inverse()).v
+    pub const fn extend(&self, w: S) -> TVec4<S> {
+        TVec4::new(self.x, self.y, self.z, w)
     }
 
-    pub fn rotate_by(&mut self, quat: &Quat) {
-        *self = self.rotate(quat);
+    pub fn rotate(&self, quat: &TQuat<S>) -> Self {
+        (quat * TQuat::new(S::ZERO, *self) * quat.inverse()).v
     }
 
-    pub fn transform(&self, matrix: &Mat4) -> Self {
-        let matrix = matrix.borrow();
-
-        let x = self.x * matrix[0] + self.y * matrix[4] + self.z * matrix[8] + matrix[12];
-        let y = self.x * matrix[1] + self.y * matrix[5] + self.z * matrix[9] + matrix[13];
-        let z = self.x * matrix[2] + self.y * matrix[6] + self.z * matrix[10] + matrix[14];
+    pub fn rotate_by(&mut self, quat: &TQuat<S>) {
+        *self = self.rotate(quat);
+    }
 
-        Self { x, y, z }
+    pub fn transform(&self, matrix: &TMat4<S>) -> Self {
+        (*matrix * self.extend(S::ONE)).truncate()
     }
 
-    pub fn transform_by(&mut self, matrix: &Mat4) {
+    pub fn transform_by(&mut self, matrix: &TMat4<S>) {
         *self = self.transform(matrix);
     }
+
+    /// Converts to another scalar type, e.g. an `f64` simulation position to `f32` for the GPU.
+    pub fn cast<T>(&self) -> TVec3<T>
+    where
+        T: Scalar,
+    {
+        TVec3::new(
+            T::from_f64(self.x.to_f64()),
+            T::from_f64(self.y.to_f64()),
+            T::from_f64(self.z.to_f64()),
+        )
+    }
 }
 
-impl Normed for Vec3 {
-    fn norm_squared(&self) -> f32 {
+impl<S> Normed for TVec3<S>
+where
+    S: Scalar,
+{
+    type Scalar = S;
+
+    fn norm_squared(&self) -> S {
         self.dot(self)
     }
 }
 
-impl From<[f32; 3]> for Vec3 {
-    fn from([x, y, z]: [f32; 3]) -> Self {
+unsafe impl<S: Scalar> Pod for TVec3<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TVec3<S> {}
+
+impl<S> From<[S; 3]> for TVec3<S> {
+    fn from([x, y, z]: [S; 3]) -> Self {
         Self { x, y, z }
     }
 }
 
-impl From<(f32, f32, f32)> for Vec3 {
-    fn from((x, y, z): (f32, f32, f32)) -> Self {
+impl<S> From<(S, S, S)> for TVec3<S> {
+    fn from((x, y, z): (S, S, S)) -> Self {
         Self { x, y, z }
     }
 }
 
-impl<P> From<P> for Vec3
+impl<S, P> From<P> for TVec3<S>
 where
-    P: Borrow<Point3>,
+    S: Scalar,
+    P: Borrow<TPoint3<S>>,
 {
     fn from(point: P) -> Self {
-        let Point3 { x, y, z } = *point.borrow();
+        let TPoint3 { x, y, z } = *point.borrow();
 
         Self { x, y, z }
     }
 }
 
-impl<V> From<V> for Unit<Vec3>
+impl<S, V> From<V> for Unit<TVec3<S>>
 where
-    V: Borrow<Vec3>,
+    S: Scalar,
+    V: Borrow<TVec3<S>>,
 {
     fn from(vec: V) -> Self {
         Self::new(*vec.borrow())
@@ -143,7 +170,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::core::maths::Angle;
+    use crate::{
+        assert_approx_eq,
+        core::maths::{Angle, Mat4, Quat},
+    };
 
     use super::*;
 
@@ -152,7 +182,7 @@ mod tests {
         assert_eq!(Vec3::unit_x().dot(&Vec3::unit_x()), 1.0);
         assert_eq!(Vec3::unit_x().dot(&Vec3::unit_y()), 0.0);
         assert_eq!(Vec3::unit_y().dot(&Vec3::unit_x()), 0.0);
-        assert_eq!(Vec3::unit_x().dot(&-Vec3::unit_x()), -1.0);
+        assert_eq!(Vec3::unit_x().dot(&-*Vec3::unit_x()), -1.0);
     }
 
     #[test]
@@ -198,4 +228,19 @@ mod tests {
         assert_eq!(y, 2.0);
         assert_eq!(z, 0.0);
     }
+
+    #[test]
+    fn doubles_keep_precision_far_from_the_origin() {
+        let far = DVec3::new(1e9, -1e9, 1e9);
+        let step = DVec3::new(1e-3, 1e-3, 1e-3);
+
+        assert_approx_eq!((far + step) - far, step, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn cast() {
+        let TVec3 { x, y, z } = DVec3::new(0.5, -2.0, 1e10).cast::<f32>();
+
+        assert_eq!([x, y, z], [0.5, -2.0, 1e10]);
+    }
 }
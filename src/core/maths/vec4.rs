@@ -0,0 +1,181 @@
+use std::borrow::Borrow;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::maths::{Normed, Scalar, TVec3, Unit};
+
+pub type Vec4 = TVec4<f32>;
+pub type DVec4 = TVec4<f64>;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TVec4<S> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
+    pub w: S,
+}
+
+impl<S> TVec4<S>
+where
+    S: Scalar,
+{
+    pub const fn new(x: S, y: S, z: S, w: S) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn lerp(start: &Self, end: &Self, t: S) -> Unit<Self> {
+        let mid = start * (S::ONE - t) + end * t;
+        let mid = mid / mid.norm();
+
+        Unit::new(mid)
+    }
+
+    pub const fn splat(value: S) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    pub const fn zero() -> Self {
+        Self::splat(S::ZERO)
+    }
+
+    pub const fn positive_one() -> Self {
+        Self::splat(S::ONE)
+    }
+
+    pub const fn negative_one() -> Self {
+        Self::splat(S::NEG_ONE)
+    }
+
+    pub const fn unit_x() -> Unit<Self> {
+        Unit::from_normalised(Self::new(S::ONE, S::ZERO, S::ZERO, S::ZERO))
+    }
+
+    pub const fn unit_y() -> Unit<Self> {
+        Unit::from_normalised(Self::new(S::ZERO, S::ONE, S::ZERO, S::ZERO))
+    }
+
+    pub const fn unit_z() -> Unit<Self> {
+        Unit::from_normalised(Self::new(S::ZERO, S::ZERO, S::ONE, S::ZERO))
+    }
+
+    pub const fn unit_w() -> Unit<Self> {
+        Unit::from_normalised(Self::new(S::ZERO, S::ZERO, S::ZERO, S::ONE))
+    }
+
+    pub const fn positive_infinity() -> Self {
+        Self::splat(S::INFINITY)
+    }
+
+    pub const fn negative_infinity() -> Self {
+        Self::splat(S::NEG_INFINITY)
+    }
+
+    pub fn dot(&self, rhs: &Self) -> S {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+
+    pub fn reciprocal(&self) -> Self {
+        Self {
+            x: self.x.recip(),
+            y: self.y.recip(),
+            z: self.z.recip(),
+            w: self.w.recip(),
+        }
+    }
+
+    /// Drops the w component.
+    pub const fn truncate(&self) -> TVec3<S> {
+        TVec3::new(self.x, self.y, self.z)
+    }
+
+    /// Divides x, y and z by w, turning homogeneous coordinates back into a position.
+    pub fn project(&self) -> TVec3<S> {
+        self.truncate() / self.w
+    }
+
+    /// Converts to another scalar type, e.g. an `f64` simulation position to `f32` for the GPU.
+    pub fn cast<T>(&self) -> TVec4<T>
+    where
+        T: Scalar,
+    {
+        TVec4::new(
+            T::from_f64(self.x.to_f64()),
+            T::from_f64(self.y.to_f64()),
+            T::from_f64(self.z.to_f64()),
+            T::from_f64(self.w.to_f64()),
+        )
+    }
+}
+
+impl<S> Normed for TVec4<S>
+where
+    S: Scalar,
+{
+    type Scalar = S;
+
+    fn norm_squared(&self) -> S {
+        self.dot(self)
+    }
+}
+
+unsafe impl<S: Scalar> Pod for TVec4<S> {}
+
+unsafe impl<S: Scalar> Zeroable for TVec4<S> {}
+
+impl<S> From<[S; 4]> for TVec4<S> {
+    fn from([x, y, z, w]: [S; 4]) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl<S> From<(S, S, S, S)> for TVec4<S> {
+    fn from((x, y, z, w): (S, S, S, S)) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl<S, V> From<V> for Unit<TVec4<S>>
+where
+    S: Scalar,
+    V: Borrow<TVec4<S>>,
+{
+    fn from(vec: V) -> Self {
+        Self::new(*vec.borrow())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::maths::Vec3;
+
+    use super::*;
+
+    #[test]
+    fn dot() {
+        assert_eq!(Vec4::unit_x().dot(&Vec4::unit_x()), 1.0);
+        assert_eq!(Vec4::unit_x().dot(&Vec4::unit_w()), 0.0);
+        assert_eq!(Vec4::new(1.0, 2.0, 3.0, 4.0).dot(&Vec4::positive_one()), 10.0);
+    }
+
+    #[test]
+    fn reciprocal() {
+        let Vec4 { x, y, z, w } = Vec4::new(2.0, 4.0, 5.0, 8.0).reciprocal();
+
+        assert_eq!([x, y, z, w], [0.5, 0.25, 0.2, 0.125]);
+    }
+
+    #[test]
+    fn extend_and_truncate() {
+        let Vec3 { x, y, z } = Vec3::new(1.0, 2.0, 3.0).extend(4.0).truncate();
+
+        assert_eq!([x, y, z], [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn project() {
+        let Vec3 { x, y, z } = Vec4::new(2.0, 4.0, 6.0, 2.0).project();
+
+        assert_eq!([x, y, z], [1.0, 2.0, 3.0]);
+    }
+}
@@ -1,72 +1,225 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{self, Display},
+    path::PathBuf,
+};
+
 use bumpalo::Bump;
-use wgpu::PowerPreference;
 use winit::{
+    error::{ExternalError, OsError},
     event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
-    window::{Window, WindowBuilder},
+    event_loop::{ControlFlow, EventLoop, EventLoopProxy},
+    window::{BadIcon, WindowId},
+};
+
+use crate::{
+    app::{App, LoopState},
+    graphics::{AdapterSettings, FrameError, PowerPreference, PresentMode, Renderer, RendererError},
+    keyboard::Keyboard,
+    mouse::Mouse,
+    timer::{FixedClock, FrameLimiter},
+    window::{OpenWindow, WindowSettings, Windows},
 };
 
-use crate::{app::App, graphics::Renderer, keyboard::Keyboard, mouse::Mouse};
+pub use winit::event_loop::EventLoopClosed;
+
+#[derive(Debug)]
+pub enum EngineError {
+    WindowCreation(OsError),
+    /// The platform refused a request made of a window, such as grabbing the cursor.
+    Window(ExternalError),
+    Icon(BadIcon),
+    Renderer(RendererError),
+    /// A frame failed in a way rendering can't recover from, such as running out of memory.
+    Frame(FrameError),
+}
+
+impl Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use EngineError::*;
+
+        match self {
+            WindowCreation(os_error) => write!(f, "Unable to create a window, {}", os_error),
+            Window(external_error) => write!(f, "{}", external_error),
+            Icon(bad_icon) => write!(f, "Invalid window icon, {}", bad_icon),
+            Renderer(renderer_error) => write!(f, "{}", renderer_error),
+            Frame(frame_error) => write!(f, "{}", frame_error),
+        }
+    }
+}
+
+impl Error for EngineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EngineError::WindowCreation(os_error) => Some(os_error),
+            EngineError::Window(external_error) => Some(external_error),
+            EngineError::Icon(bad_icon) => Some(bad_icon),
+            EngineError::Renderer(renderer_error) => Some(renderer_error),
+            EngineError::Frame(frame_error) => Some(frame_error),
+        }
+    }
+}
+
+impl From<OsError> for EngineError {
+    fn from(os_error: OsError) -> Self {
+        EngineError::WindowCreation(os_error)
+    }
+}
+
+impl From<ExternalError> for EngineError {
+    fn from(external_error: ExternalError) -> Self {
+        EngineError::Window(external_error)
+    }
+}
+
+impl From<BadIcon> for EngineError {
+    fn from(bad_icon: BadIcon) -> Self {
+        EngineError::Icon(bad_icon)
+    }
+}
+
+impl From<RendererError> for EngineError {
+    fn from(renderer_error: RendererError) -> Self {
+        EngineError::Renderer(renderer_error)
+    }
+}
+
+impl From<FrameError> for EngineError {
+    fn from(frame_error: FrameError) -> Self {
+        EngineError::Frame(frame_error)
+    }
+}
 
 pub struct EngineSettings {
-    pub power_preference: PowerPreference,
-    pub control_flow:     ControlFlow,
-    pub resizable_window: bool,
+    /// Which adapter is used and the features and limits requested of it.
+    pub adapter:              AdapterSettings,
+    pub control_flow:         ControlFlow,
+    /// The primary window's settings, unless others were saved at `window_settings_path`.
+    pub window:               WindowSettings,
+    /// Where the primary window's settings are loaded from at startup and saved to on exit, so
+    /// the window comes back the way the user left it.
+    pub window_settings_path: Option<PathBuf>,
+    pub present_mode:         PresentMode,
+    /// Caps the frame rate when set, on top of any limit imposed by the present mode.
+    pub target_fps:           Option<u32>,
+    /// Samples per pixel used for MSAA, one of `SAMPLE_COUNTS`. A count of 1 disables it, any
+    /// other count falls back to 4.
+    pub sample_count:         u32,
+    /// How many times a second `App::fixed_update` is called.
+    pub fixed_update_rate:    u32,
 }
 
 impl EngineSettings {
     pub fn default_game() -> Self {
         Self {
-            power_preference: PowerPreference::HighPerformance,
-            control_flow:     ControlFlow::Poll,
-            resizable_window: false,
+            adapter:              AdapterSettings::new(PowerPreference::HighPerformance),
+            control_flow:         ControlFlow::Poll,
+            window:               WindowSettings {
+                resizable: false,
+                ..WindowSettings::new(1280.0, 720.0)
+            },
+            window_settings_path: None,
+            present_mode:         PresentMode::Mailbox,
+            target_fps:           None,
+            sample_count:         4,
+            fixed_update_rate:    60,
         }
     }
 
     pub fn default_app() -> Self {
         Self {
-            power_preference: PowerPreference::LowPower,
-            control_flow:     ControlFlow::Wait,
-            resizable_window: true,
+            adapter:              AdapterSettings::new(PowerPreference::LowPower),
+            control_flow:         ControlFlow::Wait,
+            window:               WindowSettings::new(1280.0, 720.0),
+            window_settings_path: None,
+            present_mode:         PresentMode::Fifo,
+            target_fps:           None,
+            sample_count:         1,
+            fixed_update_rate:    60,
         }
     }
 }
 
+/// Sends user events to the engine's event loop from any thread, waking it if it's waiting. The
+/// events are delivered to `App::user_event`, the send fails once the loop has exited.
+pub type EventProxy<UserEvent> = EventLoopProxy<UserEvent>;
+
 pub struct Engine<UserEvent = ()>
 where
     UserEvent: 'static,
 {
     event_loop:           EventLoop<UserEvent>,
-    window:               Window,
+    proxy:                EventProxy<UserEvent>,
+    windows:              HashMap<WindowId, OpenWindow>,
+    primary_window:       WindowId,
+    window_settings_path: Option<PathBuf>,
     renderer:             Renderer,
     default_control_flow: ControlFlow,
+    frame_limiter:        Option<FrameLimiter>,
+    fixed_clock:          FixedClock,
 }
 
 impl<UserEvent> Engine<UserEvent>
 where
     UserEvent: 'static,
 {
-    pub async fn new(title: impl AsRef<str>, settings: EngineSettings) -> Result<Self, ()> {
+    pub async fn new(
+        title: impl AsRef<str>,
+        settings: EngineSettings,
+    ) -> Result<Self, EngineError> {
         let event_loop = EventLoop::<UserEvent>::with_user_event();
-        let window = WindowBuilder::new()
-            .with_title(title.as_ref())
-            .with_resizable(settings.resizable_window)
-            .build(&event_loop)
-            .unwrap();
+        let window_settings = settings
+            .window_settings_path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| match WindowSettings::load(path) {
+                Ok(window_settings) => Some(window_settings),
+                Err(settings_error) => {
+                    log::warn!("{}", settings_error);
+                    None
+                },
+            })
+            .unwrap_or(settings.window);
 
-        // window.map(|window| (event_loop, window))
-        let renderer = Renderer::new(&window, settings.power_preference)
-            .await
-            .unwrap();
+        let primary = window_settings.build(title.as_ref(), &event_loop)?;
+
+        let renderer = Renderer::new(
+            &primary.window,
+            settings.adapter,
+            settings.present_mode,
+            settings.sample_count,
+        )
+        .await?;
+
+        let frame_limiter = settings
+            .target_fps
+            .filter(|&target_fps| target_fps > 0)
+            .map(FrameLimiter::new);
+
+        let primary_window = primary.window.id();
+        let mut windows = HashMap::new();
+        windows.insert(primary_window, primary);
 
         Ok(Self {
+            proxy: event_loop.create_proxy(),
             event_loop,
-            window,
+            windows,
+            primary_window,
+            window_settings_path: settings.window_settings_path,
             renderer,
             default_control_flow: settings.control_flow,
+            frame_limiter,
+            fixed_clock: FixedClock::new(settings.fixed_update_rate),
         })
     }
 
+    /// Creates a proxy for background tasks, such as asset loads or `Http` requests, to deliver
+    /// their results with. More can be created while running through `Windows::create_proxy`.
+    pub fn create_proxy(&self) -> EventProxy<UserEvent> {
+        self.proxy.clone()
+    }
+
     #[inline(always)]
     pub fn run<A, I>(self, init: I) -> !
     where
@@ -74,47 +227,132 @@ where
         I: FnOnce(&mut Renderer) -> A,
     {
         let Engine {
-            window,
+            mut windows,
+            primary_window,
+            window_settings_path,
             event_loop,
+            proxy,
             default_control_flow,
             mut renderer,
+            mut frame_limiter,
+            mut fixed_clock,
         } = self;
 
         let mut game = init(&mut renderer);
-        let mut keyboard = Keyboard;
-        let mut mouse = Mouse;
+        let keyboard = Keyboard;
+        let mouse = Mouse;
         let mut frame_memory = Bump::with_capacity(A::INITIAL_FRAME_MEMORY);
+        let mut minimised = HashSet::new();
+        let mut suspended = false;
 
-        event_loop.run(move |event, _, control_flow| {
-            let _ = window;
+        event_loop.run(move |event, target, control_flow| {
+            let mut windows = Windows {
+                target,
+                proxy: &proxy,
+                windows: &mut windows,
+                primary: primary_window,
+            };
 
             *control_flow = default_control_flow;
 
             match event {
-                Event::NewEvents(cause) => {
+                Event::NewEvents(_cause) => {
                     // Update timers etc...
                 },
-                Event::WindowEvent { window_id, event } => match event {
-                    WindowEvent::CloseRequested if A::EXIT_ON_CLOSE => {
-                        *control_flow = ControlFlow::Exit
-                    },
-                    WindowEvent::Resized(new_size) => renderer.resize(new_size),
-                    WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
-                        renderer.resize(*new_inner_size)
-                    },
-                    _ => {},
+                Event::WindowEvent { window_id, event } => {
+                    if windows.get(window_id).is_none() {
+                        return;
+                    }
+
+                    windows.track(window_id, &event);
+
+                    match &event {
+                        WindowEvent::CloseRequested => {
+                            let close = A::EXIT_ON_CLOSE
+                                || game.close_requested(window_id, &mut windows, &mut renderer);
+
+                            if close && window_id == primary_window {
+                                *control_flow = ControlFlow::Exit;
+                            } else if close {
+                                windows.close(&mut renderer, window_id);
+                                minimised.remove(&window_id);
+                                game.window_closed(window_id, &mut renderer);
+                                return;
+                            }
+                        },
+                        WindowEvent::Resized(new_size) => {
+                            // NOTE:
+                            // winit has no minimise event, minimised windows are resized to 0x0
+                            // instead and back to their previous size when restored.
+                            if new_size.width == 0 || new_size.height == 0 {
+                                if minimised.insert(window_id) {
+                                    game.minimised(window_id);
+                                }
+                            } else if minimised.remove(&window_id) {
+                                game.restored(window_id);
+                            }
+
+                            renderer.resize(window_id, *new_size)
+                        },
+                        WindowEvent::Focused(focused) => game.focus_changed(window_id, *focused),
+                        WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                            renderer.resize(window_id, **new_inner_size)
+                        },
+                        _ => {},
+                    }
+
+                    game.window_event(window_id, &event, &mut windows, &mut renderer);
+                },
+                Event::DeviceEvent { .. } => {},
+                Event::UserEvent(event) => game.user_event(event, &mut windows, &mut renderer),
+                Event::Suspended => {
+                    suspended = true;
+                    game.suspended();
+                },
+                Event::Resumed => {
+                    suspended = false;
+                    game.resumed();
                 },
-                Event::DeviceEvent { device_id, event } => {},
-                Event::UserEvent(event) => {},
-                Event::Suspended => {},
-                Event::Resumed => {},
                 Event::MainEventsCleared => {
-                    game.update_and_render(&keyboard, &mouse, &mut frame_memory, &mut renderer);
+                    if suspended || windows.ids().all(|id| minimised.contains(&id)) {
+                        fixed_clock.pause();
+                    } else {
+                        fixed_clock.resume();
+                    }
+
+                    for _ in 0..fixed_clock.tick() {
+                        game.fixed_update(fixed_clock.step());
+                    }
+
+                    let loop_state = game.update_and_render(
+                        &mut windows,
+                        &keyboard,
+                        &mouse,
+                        &mut frame_memory,
+                        &mut renderer,
+                    );
                     frame_memory.reset();
+
+                    if let LoopState::Exit = loop_state {
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+
+                    if let Some(frame_limiter) = &mut frame_limiter {
+                        frame_limiter.wait();
+                    }
                 },
                 Event::RedrawRequested(_) => {},
                 Event::RedrawEventsCleared => {},
-                Event::LoopDestroyed => game.clean_up(),
+                Event::LoopDestroyed => {
+                    game.clean_up();
+
+                    if let Some(path) = &window_settings_path {
+                        if let Err(settings_error) = windows.settings(primary_window).save(path) {
+                            log::error!("{}", settings_error);
+                        }
+                    }
+                },
             }
         })
     }
@@ -1,15 +1,71 @@
+mod adapter;
 mod buffer;
 mod camera;
+mod compute_pipeline;
+mod cubemap;
+mod environment;
+mod frame_stats;
+mod globals;
+mod gpu_timer;
+mod instance;
+mod material;
+mod mesh;
+mod particles;
 mod pipeline;
+mod post_processing;
+mod render_frame;
+mod render_graph;
+mod render_target;
 mod renderer;
+mod stats_overlay;
+mod texture;
 mod transform;
 mod vertex;
+mod waker;
+mod window_surface;
 
+pub use wgpu::{
+    AdapterInfo,
+    Backend,
+    BackendBit,
+    Color as Colour,
+    DeviceType,
+    Features,
+    Limits,
+    PowerPreference,
+    PresentMode,
+};
+
+pub(in crate::graphics) use crate::graphics::{
+    adapter::request_device,
+    frame_stats::FrameCounters,
+    environment::Skybox,
+    globals::Globals,
+    gpu_timer::GpuTimer,
+    render_target::RenderTarget,
+    stats_overlay::StatsOverlay,
+    waker::noop_waker,
+    window_surface::WindowSurface,
+};
 pub use crate::graphics::{
+    adapter::{AdapterCapabilities, AdapterSettings},
     buffer::*,
     camera::*,
+    compute_pipeline::*,
+    cubemap::*,
+    environment::Environment,
+    frame_stats::{FrameStats, PassTiming},
+    globals::Lighting,
+    instance::*,
+    material::*,
+    mesh::*,
+    particles::*,
     pipeline::*,
+    post_processing::*,
+    render_frame::*,
+    render_graph::*,
     renderer::*,
+    texture::*,
     transform::*,
     vertex::*,
 };
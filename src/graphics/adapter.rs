@@ -0,0 +1,153 @@
+use wgpu::{
+    Adapter,
+    AdapterInfo,
+    BackendBit,
+    Device,
+    DeviceDescriptor,
+    DeviceType,
+    Features,
+    Instance,
+    Limits,
+    PowerPreference,
+    Queue,
+    RequestAdapterOptions,
+    Surface,
+    TextureFormat,
+};
+
+use crate::graphics::RendererError;
+
+/// How the renderer chooses its graphics adapter and what it asks of the device.
+#[derive(Debug, Clone)]
+pub struct AdapterSettings {
+    /// The graphics APIs adapters may come from, e.g. `BackendBit::VULKAN | BackendBit::GL`.
+    pub backends:               BackendBit,
+    pub power_preference:       PowerPreference,
+    /// Uses a software adapter instead of the hardware ones, fails when there is none.
+    pub force_fallback_adapter: bool,
+    /// Features the device must support.
+    pub required_features:      Features,
+    /// Features enabled when the adapter supports them, see `Renderer::features`.
+    pub optional_features:      Features,
+    /// Limits the device must support. Anything over the default limits is not guaranteed to be
+    /// available everywhere.
+    pub limits:                 Limits,
+}
+
+impl AdapterSettings {
+    pub fn new(power_preference: PowerPreference) -> Self {
+        Self {
+            backends: BackendBit::all(),
+            power_preference,
+            force_fallback_adapter: false,
+            required_features: Features::empty(),
+            optional_features: Features::empty(),
+            limits: Limits::default(),
+        }
+    }
+}
+
+/// What an adapter is and what it supports.
+#[derive(Debug, Clone)]
+pub struct AdapterCapabilities {
+    pub info:     AdapterInfo,
+    pub features: Features,
+    pub limits:   Limits,
+}
+
+impl AdapterCapabilities {
+    /// Lists every adapter available through `backends`, whether or not it can present to a
+    /// window.
+    pub fn enumerate(backends: BackendBit) -> Vec<Self> {
+        Instance::new(backends)
+            .enumerate_adapters(backends)
+            .map(|adapter| Self::of(&adapter))
+            .collect()
+    }
+
+    pub(in crate::graphics) fn of(adapter: &Adapter) -> Self {
+        Self {
+            info:     adapter.get_info(),
+            features: adapter.features(),
+            limits:   adapter.limits(),
+        }
+    }
+}
+
+pub(in crate::graphics) async fn request_device(
+    instance: &Instance,
+    surface: &Surface,
+    settings: &AdapterSettings,
+) -> Result<(Adapter, Device, Queue, TextureFormat), RendererError> {
+    let adapter = if settings.force_fallback_adapter {
+        instance
+            .enumerate_adapters(settings.backends)
+            .find(|adapter| {
+                adapter.get_info().device_type == DeviceType::Cpu
+                    && adapter.get_swap_chain_preferred_format(surface).is_some()
+            })
+            .ok_or(RendererError::NoFallbackAdapter)?
+    } else {
+        instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference:   settings.power_preference,
+                compatible_surface: Some(surface),
+            })
+            .await
+            .ok_or(RendererError::NoAvailableGraphicsAdapter)?
+    };
+
+    let missing_features = settings.required_features - adapter.features();
+
+    if !missing_features.is_empty() {
+        return Err(RendererError::MissingFeatures(missing_features));
+    }
+
+    // NOTE:
+    // Timestamp queries are only used for profiling, so they're always requested when available
+    // rather than required.
+    let optional_features = settings.optional_features | Features::TIMESTAMP_QUERY;
+
+    let (device, queue) = adapter
+        .request_device(
+            &DeviceDescriptor {
+                label:    None,
+                features: settings.required_features | (adapter.features() & optional_features),
+                limits:   settings.limits.clone(),
+            },
+            None,
+        )
+        .await
+        .map_err(RendererError::NoAvailableGraphicsDevice)?;
+
+    let swap_chain_format = adapter
+        .get_swap_chain_preferred_format(surface)
+        .ok_or(RendererError::NoPreferredSwapChainFormat)?;
+
+    log_capabilities(&adapter, &device);
+
+    Ok((adapter, device, queue, swap_chain_format))
+}
+
+fn log_capabilities(adapter: &Adapter, device: &Device) {
+    let AdapterInfo {
+        name,
+        device_type,
+        backend,
+        ..
+    } = adapter.get_info();
+
+    let limits = device.limits();
+
+    log::info!("Using {} ({:?}, {:?})", name, device_type, backend);
+    log::info!("Enabled features: {:?}", device.features());
+    log::info!(
+        "Limits: {}px 2D textures, {} bind groups, {} byte uniform bindings, {} byte storage \
+         bindings, {} vertex buffers",
+        limits.max_texture_dimension_2d,
+        limits.max_bind_groups,
+        limits.max_uniform_buffer_binding_size,
+        limits.max_storage_buffer_binding_size,
+        limits.max_vertex_buffers,
+    );
+}
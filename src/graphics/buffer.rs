@@ -1,5 +1,8 @@
 use wgpu::Buffer as WgpuBuffer;
 
+// NOTE:
+// Nothing creates these yet, meshes and post processing hold onto wgpu's buffers directly.
+#[allow(dead_code)]
 pub struct Buffer {
     pub(in crate::graphics) buffer: WgpuBuffer,
     pub(in crate::graphics) size:   u32,
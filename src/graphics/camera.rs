@@ -1,5 +1,6 @@
 use crate::core::maths::{Angle, Mat4, Point3, Quat, Vec3};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Camera {
     pub position: Point3,
     pub rotation: Quat,
@@ -28,11 +29,25 @@ impl Camera {
     }
 
     pub fn view_projection(&self) -> Mat4 {
-        let forward = Vec3::unit_z().rotate(&self.rotation);
-        let up = Vec3::unit_y().rotate(&self.rotation);
-        let right = Vec3::unit_x().rotate(&self.rotation);
+        self.projection * Mat4::view(self.forward(), self.up(), self.right(), self.position)
+    }
+
+    /// The view projection with the camera at the origin, so only its rotation is applied. Used to
+    /// draw things infinitely far away, such as the skybox.
+    pub fn rotation_view_projection(&self) -> Mat4 {
+        self.projection * Mat4::view(self.forward(), self.up(), self.right(), Point3::identity())
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        Vec3::unit_z().rotate(&self.rotation)
+    }
+
+    pub fn up(&self) -> Vec3 {
+        Vec3::unit_y().rotate(&self.rotation)
+    }
 
-        Mat4::view(forward, up, right, self.position)
+    pub fn right(&self) -> Vec3 {
+        Vec3::unit_x().rotate(&self.rotation)
     }
 }
 
@@ -69,3 +84,34 @@ impl CameraBuilder {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let mut builder = CameraBuilder::new(Mat4::perspective(
+            16.0 / 9.0,
+            Angle::degrees(70.0),
+            0.1,
+            100.0,
+        ));
+
+        builder
+            .with_position(Point3::new(1.0, 2.0, -5.0))
+            .with_rotation(Quat::from_axis_angle(
+                &Vec3::unit_x(),
+                Angle::degrees(-20.0),
+            ));
+
+        let camera = builder.build();
+        let json = serde_json::to_string(&camera).unwrap();
+        let loaded = serde_json::from_str::<Camera>(&json).unwrap();
+
+        assert_eq!(loaded.position, camera.position);
+        assert_eq!(loaded.rotation, camera.rotation);
+        assert_eq!(loaded.projection, camera.projection);
+        assert_eq!(loaded.view_projection(), camera.view_projection());
+    }
+}
@@ -0,0 +1,211 @@
+use std::mem;
+
+use bytemuck::Pod;
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupEntry,
+    BindGroupLayout,
+    BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry,
+    BindingType,
+    Buffer as WgpuBuffer,
+    BufferAddress,
+    BufferBindingType,
+    BufferUsage,
+    ComputePipeline as WgpuComputePipeline,
+    ComputePipelineDescriptor as WgpuComputePipelineDescriptor,
+    PipelineLayoutDescriptor,
+    ShaderFlags,
+    ShaderModuleDescriptor,
+    ShaderSource,
+    ShaderStage,
+};
+
+use crate::graphics::Renderer;
+
+/// How a compute shader accesses one of its buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeBinding {
+    Uniform,
+    Storage { read_only: bool },
+}
+
+/// A buffer that can be bound to a compute pipeline.
+pub struct ComputeBuffer {
+    pub(in crate::graphics) buffer: WgpuBuffer,
+    pub(in crate::graphics) size:   BufferAddress,
+}
+
+impl ComputeBuffer {
+    /// Creates a storage buffer holding `contents`. Storage buffers can also be bound as vertex
+    /// buffers, so whatever a compute shader writes into them can be drawn directly.
+    pub fn storage<T>(Renderer { device, .. }: &Renderer, label: &str, contents: &[T]) -> Self
+    where
+        T: Pod,
+    {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some(label),
+            contents: bytemuck::cast_slice(contents),
+            usage:    BufferUsage::STORAGE
+                | BufferUsage::VERTEX
+                | BufferUsage::COPY_SRC
+                | BufferUsage::COPY_DST,
+        });
+
+        Self {
+            buffer,
+            size: mem::size_of_val(contents) as BufferAddress,
+        }
+    }
+
+    pub fn uniform<T>(Renderer { device, .. }: &Renderer, label: &str, contents: &T) -> Self
+    where
+        T: Pod,
+    {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some(label),
+            contents: bytemuck::bytes_of(contents),
+            usage:    BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        });
+
+        Self {
+            buffer,
+            size: mem::size_of::<T>() as BufferAddress,
+        }
+    }
+
+    /// Overwrites part of the buffer starting `offset` bytes in.
+    pub fn write<T>(&self, renderer: &Renderer, offset: BufferAddress, data: &[T])
+    where
+        T: Pod,
+    {
+        assert!(
+            offset + mem::size_of_val(data) as BufferAddress <= self.size,
+            "Write of {} bytes at {} overflows a buffer of {} bytes",
+            mem::size_of_val(data),
+            offset,
+            self.size
+        );
+
+        renderer
+            .queue
+            .write_buffer(&self.buffer, offset, bytemuck::cast_slice(data));
+        renderer.counters.record_upload(mem::size_of_val(data));
+    }
+
+    pub fn size(&self) -> BufferAddress {
+        self.size
+    }
+}
+
+pub struct ComputePipelineDescriptor<'a> {
+    pub label:       &'a str,
+    /// WGSL source containing the compute entry point.
+    pub source:      &'a str,
+    pub entry_point: &'a str,
+    /// The buffers of bind group 0, binding `n` is described by the `n`th entry.
+    pub bindings:    &'a [ComputeBinding],
+}
+
+pub struct ComputePipeline {
+    pub(in crate::graphics) bind_group_layout: BindGroupLayout,
+    pub(in crate::graphics) compute_pipeline:  WgpuComputePipeline,
+    pub(in crate::graphics) binding_count:     usize,
+}
+
+impl ComputePipeline {
+    pub fn new(Renderer { device, .. }: &Renderer, descriptor: &ComputePipelineDescriptor) -> Self {
+        let entries = descriptor
+            .bindings
+            .iter()
+            .enumerate()
+            .map(|(binding, compute_binding)| {
+                let ty = match *compute_binding {
+                    ComputeBinding::Uniform => BufferBindingType::Uniform,
+                    ComputeBinding::Storage { read_only } => {
+                        BufferBindingType::Storage { read_only }
+                    },
+                };
+
+                BindGroupLayoutEntry {
+                    binding:    binding as u32,
+                    visibility: ShaderStage::COMPUTE,
+                    ty:         BindingType::Buffer {
+                        ty,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count:      None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some(descriptor.label),
+            entries: &entries,
+        });
+
+        let shader_module = device.create_shader_module(&ShaderModuleDescriptor {
+            label:  Some(descriptor.label),
+            flags:  ShaderFlags::all(),
+            source: ShaderSource::Wgsl(descriptor.source.into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label:                Some(descriptor.label),
+            bind_group_layouts:   &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let compute_pipeline = device.create_compute_pipeline(&WgpuComputePipelineDescriptor {
+            label:       Some(descriptor.label),
+            layout:      Some(&pipeline_layout),
+            module:      &shader_module,
+            entry_point: descriptor.entry_point,
+        });
+
+        Self {
+            bind_group_layout,
+            compute_pipeline,
+            binding_count: entries.len(),
+        }
+    }
+
+    /// Binds `buffers` to the pipeline's bindings, in order.
+    pub fn bind(
+        &self,
+        Renderer { device, .. }: &Renderer,
+        buffers: &[&ComputeBuffer],
+    ) -> ComputeBindings {
+        assert_eq!(
+            buffers.len(),
+            self.binding_count,
+            "Compute pipeline expects {} buffers",
+            self.binding_count
+        );
+
+        let entries = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| BindGroupEntry {
+                binding:  binding as u32,
+                resource: buffer.buffer.as_entire_binding(),
+            })
+            .collect::<Vec<_>>();
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label:   None,
+            layout:  &self.bind_group_layout,
+            entries: &entries,
+        });
+
+        ComputeBindings { bind_group }
+    }
+}
+
+/// The buffers a `ComputePipeline` reads and writes, see `RenderFrame::dispatch`.
+pub struct ComputeBindings {
+    pub(in crate::graphics) bind_group: BindGroup,
+}
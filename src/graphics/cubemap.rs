@@ -0,0 +1,380 @@
+use std::num::NonZeroU32;
+
+use wgpu::{
+    AddressMode,
+    Device,
+    Extent3d,
+    FilterMode,
+    ImageCopyTexture,
+    ImageDataLayout,
+    Origin3d,
+    Queue,
+    Sampler,
+    SamplerDescriptor,
+    TextureDescriptor,
+    TextureDimension,
+    TextureUsage,
+    TextureView,
+    TextureViewDescriptor,
+    TextureViewDimension,
+};
+
+use crate::{
+    core::maths::{Normed, Vec3},
+    graphics::{Colour, ColourSpace, Renderer},
+};
+
+/// Six square faces sampled by direction, used for skyboxes and environment lighting.
+pub struct Cubemap {
+    pub(in crate::graphics) view:            TextureView,
+    pub(in crate::graphics) sampler:         Sampler,
+    pub(in crate::graphics) size:            u32,
+    pub(in crate::graphics) mip_level_count: u32,
+}
+
+impl Cubemap {
+    /// Creates a cubemap from tightly packed RGBA8 faces of `size` by `size` texels, given in
+    /// +X, -X, +Y, -Y, +Z, -Z order. `size` must be a power of two, a full mip chain is generated
+    /// so rough surfaces can reflect blurrier versions of the environment.
+    pub fn from_rgba8(
+        renderer: &Renderer,
+        size: u32,
+        faces: [&[u8]; 6],
+        colour_space: ColourSpace,
+    ) -> Self {
+        Self::from_rgba8_with_device(&renderer.device, &renderer.queue, size, faces, colour_space)
+    }
+
+    pub(in crate::graphics) fn from_rgba8_with_device(
+        device: &Device,
+        queue: &Queue,
+        size: u32,
+        faces: [&[u8]; 6],
+        colour_space: ColourSpace,
+    ) -> Self {
+        assert!(size.is_power_of_two(), "Cubemap size {} is not a power of two", size);
+
+        for face in &faces {
+            assert_eq!(
+                face.len(),
+                (size * size * 4) as usize,
+                "Texel data does not match a {}x{} RGBA8 cubemap face",
+                size,
+                size
+            );
+        }
+
+        let mip_level_count = 32 - size.leading_zeros();
+
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("cubemap"),
+            size: Extent3d {
+                width:                 size,
+                height:                size,
+                depth_or_array_layers: 6,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: colour_space.rgba8_format(),
+            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+        });
+
+        for (layer, face) in faces.iter().enumerate() {
+            let mut texels = face.to_vec();
+            let mut level_size = size;
+
+            for mip_level in 0..mip_level_count {
+                queue.write_texture(
+                    ImageCopyTexture {
+                        texture: &texture,
+                        mip_level,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: layer as u32,
+                        },
+                    },
+                    &texels,
+                    ImageDataLayout {
+                        offset:         0,
+                        bytes_per_row:  NonZeroU32::new(4 * level_size),
+                        rows_per_image: NonZeroU32::new(level_size),
+                    },
+                    Extent3d {
+                        width:                 level_size,
+                        height:                level_size,
+                        depth_or_array_layers: 1,
+                    },
+                );
+
+                if level_size > 1 {
+                    texels = downsample(&texels, level_size, colour_space);
+                    level_size /= 2;
+                }
+            }
+        }
+
+        // NOTE:
+        // The texture itself isn't kept, the cube view holds on to it for as long as the view is
+        // alive.
+        let view = texture.create_view(&TextureViewDescriptor {
+            label: Some("cubemap"),
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("cubemap"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            view,
+            sampler,
+            size,
+            mip_level_count,
+        }
+    }
+
+    /// A procedural sky fading from `zenith` overhead, through `horizon`, to `ground` below.
+    pub fn gradient(
+        renderer: &Renderer,
+        size: u32,
+        zenith: Colour,
+        horizon: Colour,
+        ground: Colour,
+    ) -> Self {
+        let faces = (0..6)
+            .map(|face| {
+                let mut texels = Vec::with_capacity((size * size * 4) as usize);
+
+                for y in 0..size {
+                    for x in 0..size {
+                        let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                        let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                        let direction = face_direction(face, u, v);
+                        let height = direction.y / direction.norm();
+
+                        let (to, amount) = if height >= 0.0 {
+                            (zenith, height)
+                        } else {
+                            (ground, -height)
+                        };
+
+                        texels.extend_from_slice(&lerp_srgb(horizon, to, amount.sqrt()));
+                    }
+                }
+
+                texels
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_rgba8(
+            renderer,
+            size,
+            [
+                &faces[0], &faces[1], &faces[2], &faces[3], &faces[4], &faces[5],
+            ],
+            ColourSpace::Srgb,
+        )
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
+}
+
+// The direction through a texel of a face, with `u` and `v` in [-1, 1] across the face from its
+// top left corner.
+fn face_direction(face: usize, u: f32, v: f32) -> Vec3 {
+    match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    }
+}
+
+// Halves a square RGBA8 image by averaging each 2x2 block. sRGB colours are averaged in linear
+// space, otherwise the smaller mips come out darker.
+fn downsample(texels: &[u8], size: u32, colour_space: ColourSpace) -> Vec<u8> {
+    let half = (size / 2) as usize;
+    let size = size as usize;
+    let mut result = Vec::with_capacity(half * half * 4);
+
+    for y in 0..half {
+        for x in 0..half {
+            for channel in 0..4 {
+                let srgb = colour_space == ColourSpace::Srgb && channel < 3;
+                let sum: f32 = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                    .iter()
+                    .map(|(dx, dy)| {
+                        let texel = texels[((y * 2 + dy) * size + x * 2 + dx) * 4 + channel];
+
+                        if srgb {
+                            srgb_to_linear(texel)
+                        } else {
+                            texel as f32 / 255.0
+                        }
+                    })
+                    .sum();
+
+                let average = sum / 4.0;
+
+                result.push(if srgb {
+                    linear_to_srgb(average)
+                } else {
+                    (average * 255.0).round() as u8
+                });
+            }
+        }
+    }
+
+    result
+}
+
+fn lerp_srgb(from: Colour, to: Colour, amount: f32) -> [u8; 4] {
+    let amount = amount as f64;
+    let lerp = |from: f64, to: f64| (from + (to - from) * amount) as f32;
+
+    [
+        linear_to_srgb(lerp(from.r, to.r)),
+        linear_to_srgb(lerp(from.g, to.g)),
+        linear_to_srgb(lerp(from.b, to.b)),
+        (lerp(from.a, to.a).clamp(0.0, 1.0) * 255.0).round() as u8,
+    ]
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let value = value as f32 / 255.0;
+
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u8 {
+    let value = value.clamp(0.0, 1.0);
+    let encoded = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+
+    (encoded * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_direction(face: usize, u: f32, v: f32, expected: Vec3) {
+        let direction = face_direction(face, u, v);
+
+        assert!(
+            (direction - expected).norm() < 1e-6,
+            "Face {} at ({}, {}) points along {:?}, expected {:?}",
+            face,
+            u,
+            v,
+            direction,
+            expected
+        );
+    }
+
+    #[test]
+    fn faces_point_along_their_axes() {
+        assert_direction(0, 0.0, 0.0, Vec3::new(1.0, 0.0, 0.0));
+        assert_direction(1, 0.0, 0.0, Vec3::new(-1.0, 0.0, 0.0));
+        assert_direction(2, 0.0, 0.0, Vec3::new(0.0, 1.0, 0.0));
+        assert_direction(3, 0.0, 0.0, Vec3::new(0.0, -1.0, 0.0));
+        assert_direction(4, 0.0, 0.0, Vec3::new(0.0, 0.0, 1.0));
+        assert_direction(5, 0.0, 0.0, Vec3::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn side_faces_are_upright() {
+        // The top row of every side face looks upwards, and the bottom row downwards.
+        for face in [0, 1, 4, 5].iter().copied() {
+            assert!(face_direction(face, 0.0, -1.0).y > 0.0);
+            assert!(face_direction(face, 0.0, 1.0).y < 0.0);
+        }
+    }
+
+    #[test]
+    fn faces_share_edges() {
+        // The right edge of +Z meets the left edge of +X, and the top of +Z meets the bottom of +Y.
+        assert_direction(4, 1.0, 0.0, face_direction(0, -1.0, 0.0));
+        assert_direction(4, 0.0, -1.0, face_direction(2, 0.0, 1.0));
+        assert_direction(3, 0.0, -1.0, face_direction(4, 0.0, 1.0));
+        assert_direction(5, 1.0, 0.0, face_direction(1, -1.0, 0.0));
+    }
+
+    #[test]
+    fn srgb_conversions_round_trip() {
+        for value in 0..=255 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+
+        assert_eq!(linear_to_srgb(-1.0), 0);
+        assert_eq!(linear_to_srgb(2.0), 255);
+    }
+
+    #[test]
+    fn downsample_averages_srgb_in_linear_space() {
+        let texels = [
+            0, 0, 0, 0, 255, 255, 255, 255, //
+            0, 0, 0, 0, 255, 255, 255, 255,
+        ];
+
+        // Half black and half white is mid grey in linear space, which is 188 in sRGB rather
+        // than 128. Alpha is always averaged as is.
+        assert_eq!(downsample(&texels, 2, ColourSpace::Srgb), vec![188, 188, 188, 128]);
+        assert_eq!(downsample(&texels, 2, ColourSpace::Linear), vec![128, 128, 128, 128]);
+    }
+
+    #[test]
+    fn downsample_halves_each_side() {
+        let texels = (0..4 * 4).flat_map(|texel| vec![texel as u8 * 16; 4]).collect::<Vec<_>>();
+        let half = downsample(&texels, 4, ColourSpace::Linear);
+
+        assert_eq!(half.len(), 2 * 2 * 4);
+        assert_eq!(&half[..4], &[40, 40, 40, 40]);
+        assert_eq!(&half[12..], &[200, 200, 200, 200]);
+    }
+
+    #[test]
+    fn lerp_srgb_blends_in_linear_space() {
+        let black = Colour {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.0,
+        };
+        let white = Colour {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+            a: 1.0,
+        };
+
+        assert_eq!(lerp_srgb(black, white, 0.0), [0, 0, 0, 0]);
+        assert_eq!(lerp_srgb(black, white, 1.0), [255, 255, 255, 255]);
+        assert_eq!(lerp_srgb(black, white, 0.5), [188, 188, 188, 128]);
+    }
+}
@@ -0,0 +1,87 @@
+use wgpu::{
+    BindGroupLayout,
+    Device,
+    FragmentState,
+    MultisampleState,
+    PipelineLayoutDescriptor,
+    PrimitiveState,
+    RenderPipeline,
+    RenderPipelineDescriptor,
+    ShaderFlags,
+    ShaderModuleDescriptor,
+    ShaderSource,
+    VertexState,
+};
+
+use crate::graphics::{Cubemap, HDR_FORMAT};
+
+/// The world surrounding the scene, drawn behind it and lighting it.
+pub struct Environment {
+    pub(in crate::graphics) cubemap: Cubemap,
+    /// Scales the ambient light and reflections lit materials receive from the environment.
+    pub intensity:                   f32,
+    /// Whether the cubemap is drawn behind the scene, otherwise it only lights the scene.
+    pub draw_skybox:                 bool,
+}
+
+impl Environment {
+    pub fn new(cubemap: Cubemap) -> Self {
+        Self {
+            cubemap,
+            intensity: 1.0,
+            draw_skybox: true,
+        }
+    }
+
+    pub fn cubemap(&self) -> &Cubemap {
+        &self.cubemap
+    }
+}
+
+/// Draws the environment's cubemap over the whole screen, seen through the camera's rotation.
+pub(in crate::graphics) struct Skybox {
+    pub(in crate::graphics) render_pipeline: RenderPipeline,
+}
+
+impl Skybox {
+    pub(in crate::graphics) fn new(
+        device: &Device,
+        globals_layout: &BindGroupLayout,
+        sample_count: u32,
+    ) -> Self {
+        let shader_module = device.create_shader_module(&ShaderModuleDescriptor {
+            label:  Some("skybox"),
+            flags:  ShaderFlags::all(),
+            source: ShaderSource::Wgsl(include_str!("./shaders/skybox.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label:                Some("skybox"),
+            bind_group_layouts:   &[globals_layout],
+            push_constant_ranges: &[],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label:         Some("skybox"),
+            layout:        Some(&pipeline_layout),
+            vertex:        VertexState {
+                module:      &shader_module,
+                entry_point: "main",
+                buffers:     &[],
+            },
+            fragment:      Some(FragmentState {
+                module:      &shader_module,
+                entry_point: "main",
+                targets:     &[HDR_FORMAT.into()],
+            }),
+            primitive:     PrimitiveState::default(),
+            depth_stencil: None,
+            multisample:   MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+        });
+
+        Self { render_pipeline }
+    }
+}
@@ -0,0 +1,93 @@
+use std::{cell::Cell, time::Duration};
+
+/// How long the GPU spent on a single pass of a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassTiming {
+    pub label:    String,
+    pub duration: Duration,
+}
+
+/// Timings and counters for the most recently finished frame, see `Renderer::frame_stats`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameStats {
+    /// Time between the start of this frame and the start of the frame before it.
+    pub frame_time:     Duration,
+    /// Time spent on the CPU from `begin_frame` until the frame was submitted.
+    pub cpu_time:       Duration,
+    /// Total GPU time, `None` when the adapter does not support timestamp queries. GPU results
+    /// are read back asynchronously so they trail the CPU timings by a few frames.
+    pub gpu_time:       Option<Duration>,
+    /// GPU time of the scene, each render graph pass and post processing, in execution order.
+    pub gpu_passes:     Vec<PassTiming>,
+    /// Draws recorded through the `RenderFrame` and by post processing. Render graph passes
+    /// record their own commands and aren't counted.
+    pub draw_calls:     u32,
+    pub triangles:      u64,
+    pub dispatches:     u32,
+    /// Buffer writes made through the renderer since the previous frame finished.
+    pub buffer_uploads: u32,
+    pub uploaded_bytes: u64,
+}
+
+impl FrameStats {
+    /// Frames per second if every frame took as long as this one.
+    pub fn fps(&self) -> f32 {
+        let seconds = self.frame_time.as_secs_f32();
+
+        if seconds > 0.0 {
+            1.0 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+// NOTE:
+// Uploads are made through `&Renderer`, so the counters use cells rather than requiring a mutable
+// borrow for every buffer write.
+#[derive(Debug, Default)]
+pub(in crate::graphics) struct FrameCounters {
+    draw_calls:     Cell<u32>,
+    triangles:      Cell<u64>,
+    dispatches:     Cell<u32>,
+    buffer_uploads: Cell<u32>,
+    uploaded_bytes: Cell<u64>,
+}
+
+impl FrameCounters {
+    pub(in crate::graphics) fn record_draw(&self, vertex_count: u32, instance_count: u32) {
+        self.draw_calls.set(self.draw_calls.get() + 1);
+        self.triangles
+            .set(self.triangles.get() + (vertex_count / 3) as u64 * instance_count as u64);
+    }
+
+    pub(in crate::graphics) fn record_dispatch(&self) {
+        self.dispatches.set(self.dispatches.get() + 1);
+    }
+
+    pub(in crate::graphics) fn record_upload(&self, bytes: usize) {
+        self.buffer_uploads.set(self.buffer_uploads.get() + 1);
+        self.uploaded_bytes
+            .set(self.uploaded_bytes.get() + bytes as u64);
+    }
+
+    /// Moves the counts of `other` into these counters.
+    pub(in crate::graphics) fn add(&self, other: &FrameCounters) {
+        self.draw_calls.set(self.draw_calls.get() + other.draw_calls.take());
+        self.triangles.set(self.triangles.get() + other.triangles.take());
+        self.dispatches.set(self.dispatches.get() + other.dispatches.take());
+        self.buffer_uploads
+            .set(self.buffer_uploads.get() + other.buffer_uploads.take());
+        self.uploaded_bytes
+            .set(self.uploaded_bytes.get() + other.uploaded_bytes.take());
+    }
+
+    /// Adds the counts to `stats` and resets them.
+    pub(in crate::graphics) fn drain_into(&self, stats: &mut FrameStats) {
+        stats.draw_calls += self.draw_calls.take();
+        stats.triangles += self.triangles.take();
+        stats.dispatches += self.dispatches.take();
+        stats.buffer_uploads += self.buffer_uploads.take();
+        stats.uploaded_bytes += self.uploaded_bytes.take();
+    }
+}
@@ -0,0 +1,211 @@
+use std::{mem, rc::Rc};
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupEntry,
+    BindGroupLayout,
+    BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry,
+    BindingResource,
+    BindingType,
+    Buffer as WgpuBuffer,
+    BufferBindingType,
+    BufferUsage,
+    Device,
+    Queue,
+    ShaderStage,
+    TextureSampleType,
+    TextureViewDimension,
+};
+
+use crate::{
+    core::maths::{Mat4, Unit, Vec3},
+    graphics::{Camera, Cubemap, Environment, FrameCounters},
+};
+
+/// A single directional light plus a constant ambient term.
+#[derive(Debug, Clone, Copy)]
+pub struct Lighting {
+    pub direction: Unit<Vec3>,
+    pub colour:    Vec3,
+    pub intensity: f32,
+    pub ambient:   Vec3,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        let mut direction = Unit::from_normalised(Vec3::new(-0.3, -1.0, -0.2));
+        direction.normalise();
+
+        Self {
+            direction,
+            colour: Vec3::positive_one(),
+            intensity: 3.0,
+            ambient: Vec3::splat(0.03),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct GlobalsUniform {
+    view_projection: Mat4,
+    camera_position: Vec3,
+    _padding_0:      f32,
+    light_direction: Vec3,
+    _padding_1:      f32,
+    light_colour:    Vec3,
+    light_intensity: f32,
+    ambient_colour:  Vec3,
+    _padding_2:      f32,
+    camera_right:    Vec3,
+    _padding_3:      f32,
+    camera_up:       Vec3,
+    _padding_4:      f32,
+
+    inverse_sky_view_projection: Mat4,
+    // Intensity and the index of the smallest mip of the environment.
+    environment:                 [f32; 4],
+}
+
+impl GlobalsUniform {
+    fn new(camera: &Camera, lighting: &Lighting, environment: Option<&Environment>) -> Self {
+        let environment = environment.map_or([0.0; 4], |environment| {
+            let max_mip = (environment.cubemap.mip_level_count - 1) as f32;
+
+            [environment.intensity, max_mip, 0.0, 0.0]
+        });
+
+        Self {
+            view_projection: camera.view_projection(),
+            camera_position: camera.position.into(),
+            _padding_0:      0.0,
+            light_direction: *lighting.direction,
+            _padding_1:      0.0,
+            light_colour:    lighting.colour,
+            light_intensity: lighting.intensity,
+            ambient_colour:  lighting.ambient,
+            _padding_2:      0.0,
+            camera_right:    camera.right(),
+            _padding_3:      0.0,
+            camera_up:       camera.up(),
+            _padding_4:      0.0,
+
+            inverse_sky_view_projection: camera.rotation_view_projection().inverse(),
+            environment,
+        }
+    }
+}
+
+/// Per frame uniforms shared by every lit pipeline through bind group 0.
+///
+/// The environment's cubemap is bound alongside the uniform so lit surfaces can reflect it.
+pub(in crate::graphics) struct Globals {
+    pub(in crate::graphics) buffer:            WgpuBuffer,
+    pub(in crate::graphics) bind_group_layout: BindGroupLayout,
+    pub(in crate::graphics) bind_group:        Rc<BindGroup>,
+}
+
+impl Globals {
+    pub(in crate::graphics) fn new(device: &Device, environment: &Cubemap) -> Self {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some("globals"),
+            contents: bytemuck::bytes_of(&GlobalsUniform::zeroed()),
+            usage:    BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some("globals"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding:    0,
+                    visibility: ShaderStage::VERTEX | ShaderStage::FRAGMENT,
+                    ty:         BindingType::Buffer {
+                        ty:                 BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size:   None,
+                    },
+                    count:      None,
+                },
+                BindGroupLayoutEntry {
+                    binding:    1,
+                    visibility: ShaderStage::FRAGMENT,
+                    ty:         BindingType::Texture {
+                        sample_type:    TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::Cube,
+                        multisampled:   false,
+                    },
+                    count:      None,
+                },
+                BindGroupLayoutEntry {
+                    binding:    2,
+                    visibility: ShaderStage::FRAGMENT,
+                    ty:         BindingType::Sampler {
+                        filtering:  true,
+                        comparison: false,
+                    },
+                    count:      None,
+                },
+            ],
+        });
+
+        let bind_group = create_bind_group(device, &bind_group_layout, &buffer, environment);
+
+        Self {
+            buffer,
+            bind_group_layout,
+            bind_group: Rc::new(bind_group),
+        }
+    }
+
+    /// Binds a new environment cubemap. Frames that have already begun keep the previous one.
+    pub(in crate::graphics) fn set_environment(&mut self, device: &Device, environment: &Cubemap) {
+        let bind_group =
+            create_bind_group(device, &self.bind_group_layout, &self.buffer, environment);
+
+        self.bind_group = Rc::new(bind_group);
+    }
+
+    pub(in crate::graphics) fn update(
+        &self,
+        queue: &Queue,
+        counters: &FrameCounters,
+        camera: &Camera,
+        lighting: &Lighting,
+        environment: Option<&Environment>,
+    ) {
+        let uniform = GlobalsUniform::new(camera, lighting, environment);
+
+        queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(&uniform));
+        counters.record_upload(mem::size_of::<GlobalsUniform>());
+    }
+}
+
+fn create_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    buffer: &WgpuBuffer,
+    environment: &Cubemap,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("globals"),
+        layout,
+        entries: &[
+            BindGroupEntry {
+                binding:  0,
+                resource: buffer.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding:  1,
+                resource: BindingResource::TextureView(&environment.view),
+            },
+            BindGroupEntry {
+                binding:  2,
+                resource: BindingResource::Sampler(&environment.sampler),
+            },
+        ],
+    })
+}
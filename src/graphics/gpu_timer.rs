@@ -0,0 +1,218 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use wgpu::{
+    Buffer as WgpuBuffer,
+    BufferAsyncError,
+    BufferDescriptor,
+    BufferUsage,
+    CommandEncoder,
+    Device,
+    Features,
+    Maintain,
+    MapMode,
+    QuerySet,
+    QuerySetDescriptor,
+    QueryType,
+    Queue,
+    QUERY_SIZE,
+};
+
+use crate::graphics::{noop_waker, PassTiming};
+
+// Each span takes a timestamp at its start and end.
+const MAX_SPANS: u32 = 32;
+// Results are read back asynchronously, so a few frames can be waiting on the GPU at once.
+const FRAMES_IN_FLIGHT: usize = 3;
+
+type MapFuture = Pin<Box<dyn Future<Output = Result<(), BufferAsyncError>> + Send>>;
+
+struct TimerFrame {
+    readback: WgpuBuffer,
+    labels:   Vec<String>,
+    mapping:  Option<MapFuture>,
+    in_use:   bool,
+    sequence: u64,
+}
+
+/// Measures labelled spans of a frame on the GPU with timestamp queries.
+pub(in crate::graphics) struct GpuTimer {
+    query_set:        QuerySet,
+    timestamp_period: f32,
+    frames:           Vec<TimerFrame>,
+    current:          Option<usize>,
+    open_span:        bool,
+    submitted:        u64,
+}
+
+impl GpuTimer {
+    /// Returns `None` if the device was not created with `Features::TIMESTAMP_QUERY`.
+    pub(in crate::graphics) fn new(device: &Device, queue: &Queue) -> Option<Self> {
+        if !device.features().contains(Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+
+        let query_set = device.create_query_set(&QuerySetDescriptor {
+            ty:    QueryType::Timestamp,
+            count: MAX_SPANS * 2,
+        });
+
+        let frames = (0..FRAMES_IN_FLIGHT)
+            .map(|_| TimerFrame {
+                readback: device.create_buffer(&BufferDescriptor {
+                    label:              Some("gpu timer readback"),
+                    size:               (MAX_SPANS * 2 * QUERY_SIZE) as u64,
+                    usage:              BufferUsage::MAP_READ | BufferUsage::COPY_DST,
+                    mapped_at_creation: false,
+                }),
+                labels:   Vec::new(),
+                mapping:  None,
+                in_use:   false,
+                sequence: 0,
+            })
+            .collect();
+
+        Some(Self {
+            query_set,
+            timestamp_period: queue.get_timestamp_period(),
+            frames,
+            current: None,
+            open_span: false,
+            submitted: 0,
+        })
+    }
+
+    /// Picks a readback buffer for the frame. If every buffer is still waiting on the GPU the
+    /// frame simply isn't timed.
+    pub(in crate::graphics) fn begin_frame(&mut self) {
+        self.current = self.frames.iter().position(|frame| !frame.in_use);
+        self.open_span = false;
+
+        if let Some(current) = self.current {
+            self.frames[current].labels.clear();
+        }
+    }
+
+    pub(in crate::graphics) fn begin_span(&mut self, encoder: &mut CommandEncoder, label: &str) {
+        if let Some(current) = self.current {
+            let frame = &mut self.frames[current];
+            let index = frame.labels.len() as u32;
+
+            if !self.open_span && index < MAX_SPANS {
+                encoder.write_timestamp(&self.query_set, index * 2);
+                frame.labels.push(label.to_owned());
+                self.open_span = true;
+            }
+        }
+    }
+
+    pub(in crate::graphics) fn end_span(&mut self, encoder: &mut CommandEncoder) {
+        if let Some(current) = self.current {
+            if self.open_span {
+                let index = self.frames[current].labels.len() as u32 - 1;
+
+                encoder.write_timestamp(&self.query_set, index * 2 + 1);
+                self.open_span = false;
+            }
+        }
+    }
+
+    /// Copies the frame's timestamps into its readback buffer, must be recorded after the last
+    /// span of the frame.
+    pub(in crate::graphics) fn resolve(&mut self, encoder: &mut CommandEncoder) {
+        self.end_span(encoder);
+
+        if let Some(current) = self.current {
+            let frame = &mut self.frames[current];
+
+            if !frame.labels.is_empty() {
+                let query_count = frame.labels.len() as u32 * 2;
+
+                encoder.resolve_query_set(&self.query_set, 0..query_count, &frame.readback, 0);
+                frame.in_use = true;
+            }
+        }
+    }
+
+    /// Starts reading back the frame's timestamps, call once the frame has been submitted.
+    pub(in crate::graphics) fn finish_frame(&mut self) {
+        if let Some(current) = self.current.take() {
+            let frame = &mut self.frames[current];
+
+            if frame.in_use {
+                frame.mapping = Some(Box::pin(frame.readback.slice(..).map_async(MapMode::Read)));
+                frame.sequence = self.submitted;
+                self.submitted += 1;
+            }
+        }
+    }
+
+    /// Returns the span timings of the most recent frame the GPU has finished, if any finished
+    /// since the last call.
+    pub(in crate::graphics) fn collect(&mut self, device: &Device) -> Option<Vec<PassTiming>> {
+        device.poll(Maintain::Poll);
+
+        let mut latest: Option<(u64, Vec<PassTiming>)> = None;
+
+        for frame in &mut self.frames {
+            let ready = match frame.mapping.as_mut().map(poll_now) {
+                Some(Poll::Ready(result)) => result,
+                Some(Poll::Pending) | None => continue,
+            };
+
+            frame.mapping = None;
+            frame.in_use = false;
+
+            if ready.is_err() {
+                continue;
+            }
+
+            let timestamps = frame
+                .readback
+                .slice(..)
+                .get_mapped_range()
+                .chunks_exact(QUERY_SIZE as usize)
+                .take(frame.labels.len() * 2)
+                .map(|bytes| {
+                    let mut timestamp = [0; 8];
+                    timestamp.copy_from_slice(bytes);
+                    u64::from_ne_bytes(timestamp)
+                })
+                .collect::<Vec<_>>();
+
+            frame.readback.unmap();
+
+            let period = self.timestamp_period as f64;
+            let timings = frame
+                .labels
+                .iter()
+                .zip(timestamps.chunks_exact(2))
+                .map(|(label, span)| PassTiming {
+                    label:    label.clone(),
+                    duration: Duration::from_nanos(
+                        (span[1].saturating_sub(span[0]) as f64 * period) as u64,
+                    ),
+                })
+                .collect();
+
+            if latest.as_ref().is_none_or(|(sequence, _)| frame.sequence > *sequence) {
+                latest = Some((frame.sequence, timings));
+            }
+        }
+
+        latest.map(|(_, timings)| timings)
+    }
+}
+
+// NOTE:
+// wgpu resolves buffer mappings while the device is polled, so the futures can just be checked
+// once per frame.
+fn poll_now(future: &mut MapFuture) -> Poll<Result<(), BufferAsyncError>> {
+    let waker = noop_waker();
+
+    future.as_mut().poll(&mut Context::from_waker(&waker))
+}
@@ -0,0 +1,137 @@
+use std::mem;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    Buffer as WgpuBuffer,
+    BufferAddress,
+    BufferUsage,
+    InputStepMode,
+    VertexAttribute,
+    VertexBufferLayout,
+    VertexFormat,
+};
+
+use crate::{
+    core::maths::{Mat3, Mat4},
+    graphics::{Renderer, Transform},
+};
+
+/// Per instance data streamed alongside a mesh's vertices.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct Instance {
+    pub model:  Mat4,
+    /// Transforms normals, see `Mat3::normal`.
+    pub normal: Mat3,
+}
+
+impl Instance {
+    pub fn new(transform: &Transform) -> Self {
+        Self::from_model(transform.transformation())
+    }
+
+    pub fn from_model(model: Mat4) -> Self {
+        Self {
+            model,
+            normal: Mat3::normal(&model),
+        }
+    }
+
+    pub fn buffer_descriptor() -> VertexBufferLayout<'static> {
+        // NOTE:
+        // Instance attributes start at location 4 so they can follow any of the vertex layouts.
+
+        const COLUMN: BufferAddress = mem::size_of::<[f32; 4]>() as BufferAddress;
+        const NORMAL_COLUMN: BufferAddress = mem::size_of::<[f32; 3]>() as BufferAddress;
+
+        VertexBufferLayout {
+            array_stride: mem::size_of::<Instance>() as BufferAddress,
+            step_mode:    InputStepMode::Instance,
+            attributes:   &[
+                VertexAttribute {
+                    offset:          0,
+                    shader_location: 4,
+                    format:          VertexFormat::Float32x4,
+                },
+                VertexAttribute {
+                    offset:          COLUMN,
+                    shader_location: 5,
+                    format:          VertexFormat::Float32x4,
+                },
+                VertexAttribute {
+                    offset:          COLUMN * 2,
+                    shader_location: 6,
+                    format:          VertexFormat::Float32x4,
+                },
+                VertexAttribute {
+                    offset:          COLUMN * 3,
+                    shader_location: 7,
+                    format:          VertexFormat::Float32x4,
+                },
+                VertexAttribute {
+                    offset:          COLUMN * 4,
+                    shader_location: 8,
+                    format:          VertexFormat::Float32x3,
+                },
+                VertexAttribute {
+                    offset:          COLUMN * 4 + NORMAL_COLUMN,
+                    shader_location: 9,
+                    format:          VertexFormat::Float32x3,
+                },
+                VertexAttribute {
+                    offset:          COLUMN * 4 + NORMAL_COLUMN * 2,
+                    shader_location: 10,
+                    format:          VertexFormat::Float32x3,
+                },
+            ],
+        }
+    }
+}
+
+pub struct InstanceBuffer {
+    pub(in crate::graphics) buffer:   WgpuBuffer,
+    pub(in crate::graphics) capacity: u32,
+    pub(in crate::graphics) count:    u32,
+}
+
+impl InstanceBuffer {
+    pub fn new(Renderer { device, .. }: &Renderer, instances: &[Instance]) -> Self {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label:    None,
+            contents: bytemuck::cast_slice(instances),
+            usage:    BufferUsage::VERTEX | BufferUsage::COPY_DST,
+        });
+
+        Self {
+            buffer,
+            capacity: instances.len() as u32,
+            count: instances.len() as u32,
+        }
+    }
+
+    /// Replaces the instances, growing the buffer if there are more than it can hold.
+    pub fn update(&mut self, renderer: &Renderer, instances: &[Instance]) {
+        renderer
+            .counters
+            .record_upload(mem::size_of_val(instances));
+
+        if instances.len() as u32 > self.capacity {
+            *self = Self::new(renderer, instances);
+        } else {
+            renderer
+                .queue
+                .write_buffer(&self.buffer, 0, bytemuck::cast_slice(instances));
+
+            self.count = instances.len() as u32;
+        }
+    }
+
+    pub fn len(&self) -> u32 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
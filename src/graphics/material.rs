@@ -0,0 +1,313 @@
+use std::{mem, rc::Rc};
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupEntry,
+    BindGroupLayout,
+    BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry,
+    BindingResource,
+    BindingType,
+    Buffer as WgpuBuffer,
+    BufferBindingType,
+    BufferUsage,
+    Device,
+    Queue,
+    ShaderStage,
+    TextureSampleType,
+    TextureViewDimension,
+};
+
+use crate::{
+    core::maths::Vec3,
+    graphics::{Colour, ColourSpace, Cubemap, Pipeline, Renderer, Texture},
+};
+
+/// How the alpha channel of the base colour is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlphaMode {
+    /// Alpha is ignored and the surface is fully opaque.
+    Opaque,
+    /// Fragments with an alpha below the material's cutoff are discarded.
+    Mask,
+    /// The surface is alpha blended over whatever is behind it.
+    Blend,
+}
+
+/// Everything about a material that affects the pipeline it is drawn with. Materials with equal
+/// layouts share a single pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaterialLayout {
+    pub alpha_mode:   AlphaMode,
+    pub double_sided: bool,
+}
+
+pub struct MaterialDescriptor<'a> {
+    pub base_colour:                Colour,
+    pub base_colour_texture:        Option<&'a Texture>,
+    pub metallic:                   f32,
+    pub roughness:                  f32,
+    pub metallic_roughness_texture: Option<&'a Texture>,
+    pub normal_scale:               f32,
+    pub normal_texture:             Option<&'a Texture>,
+    pub emissive:                   Vec3,
+    pub emissive_texture:           Option<&'a Texture>,
+    pub alpha_mode:                 AlphaMode,
+    pub alpha_cutoff:               f32,
+    pub double_sided:               bool,
+}
+
+impl<'a> MaterialDescriptor<'a> {
+    pub fn layout(&self) -> MaterialLayout {
+        MaterialLayout {
+            alpha_mode:   self.alpha_mode,
+            double_sided: self.double_sided,
+        }
+    }
+}
+
+impl<'a> Default for MaterialDescriptor<'a> {
+    fn default() -> Self {
+        Self {
+            base_colour:                Colour::WHITE,
+            base_colour_texture:        None,
+            metallic:                   1.0,
+            roughness:                  1.0,
+            metallic_roughness_texture: None,
+            normal_scale:               1.0,
+            normal_texture:             None,
+            emissive:                   Vec3::zero(),
+            emissive_texture:           None,
+            alpha_mode:                 AlphaMode::Opaque,
+            alpha_cutoff:               0.5,
+            double_sided:               false,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MaterialUniform {
+    base_colour:  [f32; 4],
+    emissive:     Vec3,
+    _padding:     f32,
+    metallic:     f32,
+    roughness:    f32,
+    normal_scale: f32,
+    alpha_cutoff: f32,
+}
+
+impl MaterialUniform {
+    fn new(descriptor: &MaterialDescriptor) -> Self {
+        let Colour { r, g, b, a } = descriptor.base_colour;
+
+        // Only masked materials discard, a cutoff of 0 never rejects a fragment.
+        let alpha_cutoff = match descriptor.alpha_mode {
+            AlphaMode::Mask => descriptor.alpha_cutoff,
+            AlphaMode::Opaque | AlphaMode::Blend => 0.0,
+        };
+
+        Self {
+            base_colour: [r as f32, g as f32, b as f32, a as f32],
+            emissive: descriptor.emissive,
+            _padding: 0.0,
+            metallic: descriptor.metallic,
+            roughness: descriptor.roughness,
+            normal_scale: descriptor.normal_scale,
+            alpha_cutoff,
+        }
+    }
+}
+
+pub struct Material {
+    pub(in crate::graphics) pipeline:   Rc<Pipeline>,
+    pub(in crate::graphics) uniform:    WgpuBuffer,
+    pub(in crate::graphics) bind_group: BindGroup,
+    pub(in crate::graphics) layout:     MaterialLayout,
+}
+
+impl Material {
+    pub fn new(renderer: &mut Renderer, descriptor: &MaterialDescriptor) -> Self {
+        let layout = descriptor.layout();
+        let pipeline = renderer.material_pipeline(layout);
+
+        let Renderer {
+            device,
+            material_bind_group_layout,
+            default_textures,
+            ..
+        } = renderer;
+
+        let uniform = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some("material"),
+            contents: bytemuck::bytes_of(&MaterialUniform::new(descriptor)),
+            usage:    BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        });
+
+        let base_colour = descriptor
+            .base_colour_texture
+            .unwrap_or(&default_textures.white);
+        let metallic_roughness = descriptor
+            .metallic_roughness_texture
+            .unwrap_or(&default_textures.white);
+        let normal = descriptor
+            .normal_texture
+            .unwrap_or(&default_textures.flat_normal);
+        let emissive = descriptor
+            .emissive_texture
+            .unwrap_or(&default_textures.white);
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label:   Some("material"),
+            layout:  material_bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding:  0,
+                    resource: uniform.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding:  1,
+                    resource: BindingResource::TextureView(&base_colour.view),
+                },
+                BindGroupEntry {
+                    binding:  2,
+                    resource: BindingResource::Sampler(&base_colour.sampler),
+                },
+                BindGroupEntry {
+                    binding:  3,
+                    resource: BindingResource::TextureView(&metallic_roughness.view),
+                },
+                BindGroupEntry {
+                    binding:  4,
+                    resource: BindingResource::Sampler(&metallic_roughness.sampler),
+                },
+                BindGroupEntry {
+                    binding:  5,
+                    resource: BindingResource::TextureView(&normal.view),
+                },
+                BindGroupEntry {
+                    binding:  6,
+                    resource: BindingResource::Sampler(&normal.sampler),
+                },
+                BindGroupEntry {
+                    binding:  7,
+                    resource: BindingResource::TextureView(&emissive.view),
+                },
+                BindGroupEntry {
+                    binding:  8,
+                    resource: BindingResource::Sampler(&emissive.sampler),
+                },
+            ],
+        });
+
+        Self {
+            pipeline,
+            uniform,
+            bind_group,
+            layout,
+        }
+    }
+
+    /// Updates the material's factors. The textures and layout are fixed at creation, changing
+    /// those requires a new material.
+    pub fn update_factors(&self, renderer: &Renderer, descriptor: &MaterialDescriptor) {
+        renderer.queue.write_buffer(
+            &self.uniform,
+            0,
+            bytemuck::bytes_of(&MaterialUniform::new(descriptor)),
+        );
+        renderer
+            .counters
+            .record_upload(mem::size_of::<MaterialUniform>());
+    }
+
+    pub fn layout(&self) -> MaterialLayout {
+        self.layout
+    }
+
+    /// Returns true if both materials are drawn with the same pipeline, meaning they can be
+    /// rendered back to back without a pipeline switch.
+    pub fn shares_pipeline_with(&self, other: &Material) -> bool {
+        Rc::ptr_eq(&self.pipeline, &other.pipeline)
+    }
+
+    pub(in crate::graphics) fn create_bind_group_layout(device: &Device) -> BindGroupLayout {
+        let uniform = BindGroupLayoutEntry {
+            binding:    0,
+            visibility: ShaderStage::FRAGMENT,
+            ty:         BindingType::Buffer {
+                ty:                 BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size:   None,
+            },
+            count:      None,
+        };
+
+        let texture = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type:    TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D2,
+                multisampled:   false,
+            },
+            count: None,
+        };
+
+        let sampler = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Sampler {
+                filtering:  true,
+                comparison: false,
+            },
+            count: None,
+        };
+
+        device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some("material"),
+            entries: &[
+                uniform,
+                texture(1),
+                sampler(2),
+                texture(3),
+                sampler(4),
+                texture(5),
+                sampler(6),
+                texture(7),
+                sampler(8),
+            ],
+        })
+    }
+}
+
+/// Neutral 1x1 textures bound in place of any map a material does not provide.
+pub(in crate::graphics) struct DefaultTextures {
+    pub(in crate::graphics) white:       Texture,
+    pub(in crate::graphics) flat_normal: Texture,
+    /// Bound as the environment when none is set, so lit materials receive no image based light.
+    pub(in crate::graphics) black_cube:  Cubemap,
+}
+
+impl DefaultTextures {
+    pub(in crate::graphics) fn new(device: &Device, queue: &Queue) -> Self {
+        let solid = |texel: [u8; 4]| {
+            Texture::from_rgba8_with_device(device, queue, 1, 1, &texel, ColourSpace::Linear)
+        };
+
+        Self {
+            white:       solid([255, 255, 255, 255]),
+            flat_normal: solid([128, 128, 255, 255]),
+            black_cube:  Cubemap::from_rgba8_with_device(
+                device,
+                queue,
+                1,
+                [&[0, 0, 0, 255]; 6],
+                ColourSpace::Linear,
+            ),
+        }
+    }
+}
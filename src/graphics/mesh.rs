@@ -0,0 +1,43 @@
+use bytemuck::Pod;
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    Buffer as WgpuBuffer,
+    BufferUsage,
+};
+
+use crate::graphics::Renderer;
+
+pub struct Mesh {
+    pub(in crate::graphics) vertex_buffer: WgpuBuffer,
+    pub(in crate::graphics) index_buffer:  WgpuBuffer,
+    pub(in crate::graphics) index_count:   u32,
+}
+
+impl Mesh {
+    pub fn new<V>(Renderer { device, .. }: &Renderer, vertices: &[V], indices: &[u16]) -> Self
+    where
+        V: Pod,
+    {
+        let vertex_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label:    None,
+            contents: bytemuck::cast_slice(vertices),
+            usage:    BufferUsage::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label:    None,
+            contents: bytemuck::cast_slice(indices),
+            usage:    BufferUsage::INDEX,
+        });
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+        }
+    }
+
+    pub fn index_count(&self) -> u32 {
+        self.index_count
+    }
+}
@@ -0,0 +1,404 @@
+use std::mem;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupEntry,
+    BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry,
+    BindingType,
+    BlendState,
+    Buffer as WgpuBuffer,
+    BufferAddress,
+    BufferBindingType,
+    BufferUsage,
+    ColorTargetState as ColourTargetState,
+    ColorWrite as ColourWrite,
+    CompareFunction,
+    DepthBiasState,
+    DepthStencilState,
+    FragmentState,
+    InputStepMode,
+    MultisampleState,
+    PipelineLayoutDescriptor,
+    PrimitiveState,
+    RenderPipeline,
+    RenderPipelineDescriptor,
+    ShaderFlags,
+    ShaderModuleDescriptor,
+    ShaderSource,
+    ShaderStage,
+    StencilState,
+    VertexAttribute,
+    VertexBufferLayout,
+    VertexFormat,
+    VertexState,
+};
+
+use crate::{
+    core::maths::Vec3,
+    graphics::{
+        Colour,
+        ComputeBinding,
+        ComputeBindings,
+        ComputeBuffer,
+        ComputePipeline,
+        ComputePipelineDescriptor,
+        Renderer,
+        DEPTH_FORMAT,
+        HDR_FORMAT,
+    },
+};
+
+// Must match the workgroup size declared in the simulation shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+const CORNERS: [[f32; 2]; 6] = [
+    [0.0, 0.0],
+    [1.0, 0.0],
+    [1.0, 1.0],
+    [0.0, 0.0],
+    [1.0, 1.0],
+    [0.0, 1.0],
+];
+
+/// Where particles are spawned and how they behave over their lifetime.
+#[derive(Debug, Clone, Copy)]
+pub struct ParticleEmitter {
+    pub position:          Vec3,
+    /// Particles spawned per second.
+    pub rate:              f32,
+    /// Seconds a particle lives for.
+    pub lifetime:          f32,
+    /// The fraction of its lifetime a particle can randomly lose, between 0 and 1.
+    pub lifetime_variance: f32,
+    pub velocity:          Vec3,
+    /// The largest random change to each axis of a particle's initial velocity.
+    pub velocity_spread:   f32,
+    pub gravity:           Vec3,
+    pub start_colour:      Colour,
+    pub end_colour:        Colour,
+    pub start_size:        f32,
+    pub end_size:          f32,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            position:          Vec3::zero(),
+            rate:              100.0,
+            lifetime:          2.0,
+            lifetime_variance: 0.25,
+            velocity:          Vec3::new(0.0, 2.0, 0.0),
+            velocity_spread:   0.5,
+            gravity:           Vec3::new(0.0, -9.81, 0.0),
+            start_colour:      Colour::WHITE,
+            end_colour:        Colour::TRANSPARENT,
+            start_size:        0.1,
+            end_size:          0.05,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Particle {
+    position: Vec3,
+    age:      f32,
+    velocity: Vec3,
+    lifetime: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct SimulationUniform {
+    emitter_position:  Vec3,
+    delta_time:        f32,
+    velocity:          Vec3,
+    velocity_spread:   f32,
+    gravity:           Vec3,
+    lifetime:          f32,
+    lifetime_variance: f32,
+    spawn_start:       u32,
+    spawn_count:       u32,
+    capacity:          u32,
+    seed:              u32,
+    _padding:          [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct StyleUniform {
+    start_colour: [f32; 4],
+    end_colour:   [f32; 4],
+    start_size:   f32,
+    end_size:     f32,
+    _padding:     [f32; 2],
+}
+
+impl StyleUniform {
+    fn new(emitter: &ParticleEmitter) -> Self {
+        let colour = |Colour { r, g, b, a }: Colour| [r as f32, g as f32, b as f32, a as f32];
+
+        Self {
+            start_colour: colour(emitter.start_colour),
+            end_colour:   colour(emitter.end_colour),
+            start_size:   emitter.start_size,
+            end_size:     emitter.end_size,
+            _padding:     [0.0; 2],
+        }
+    }
+}
+
+/// A fixed number of particles simulated in a compute shader and drawn as camera facing
+/// billboards.
+pub struct ParticleSystem {
+    pub(in crate::graphics) emitter:           ParticleEmitter,
+    pub(in crate::graphics) capacity:          u32,
+    pub(in crate::graphics) particles:         ComputeBuffer,
+    pub(in crate::graphics) simulation:        ComputeBuffer,
+    pub(in crate::graphics) simulate_pipeline: ComputePipeline,
+    pub(in crate::graphics) simulate_bindings: ComputeBindings,
+    pub(in crate::graphics) style:             WgpuBuffer,
+    pub(in crate::graphics) style_bind_group:  BindGroup,
+    pub(in crate::graphics) render_pipeline:   RenderPipeline,
+    pub(in crate::graphics) corners:           WgpuBuffer,
+    pub(in crate::graphics) spawn_accumulator: f32,
+    pub(in crate::graphics) pending_burst:     u32,
+    pub(in crate::graphics) next_spawn:        u32,
+    pub(in crate::graphics) seed:              u32,
+}
+
+impl ParticleSystem {
+    /// Creates a system of `capacity` particles. Once every particle is alive the oldest are
+    /// recycled, so `capacity` should be at least the emitter's rate times its lifetime.
+    pub fn new(renderer: &Renderer, capacity: u32, emitter: ParticleEmitter) -> Self {
+        assert!(capacity > 0, "A particle system needs room for at least one particle");
+
+        // NOTE:
+        // Zeroed particles have an age equal to their lifetime, so every slot starts out dead.
+        let particles = ComputeBuffer::storage(
+            renderer,
+            "particles",
+            &vec![Particle::zeroed(); capacity as usize],
+        );
+        let simulation =
+            ComputeBuffer::uniform(renderer, "particle simulation", &SimulationUniform::zeroed());
+
+        let simulate_pipeline = ComputePipeline::new(
+            renderer,
+            &ComputePipelineDescriptor {
+                label:       "particle simulation",
+                source:      include_str!("./shaders/particles/simulate.wgsl"),
+                entry_point: "main",
+                bindings:    &[
+                    ComputeBinding::Uniform,
+                    ComputeBinding::Storage { read_only: false },
+                ],
+            },
+        );
+        let simulate_bindings = simulate_pipeline.bind(renderer, &[&simulation, &particles]);
+
+        let Renderer {
+            device,
+            globals,
+            sample_count,
+            ..
+        } = renderer;
+
+        let style = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some("particle style"),
+            contents: bytemuck::bytes_of(&StyleUniform::new(&emitter)),
+            usage:    BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        });
+
+        let corners = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some("particle corners"),
+            contents: bytemuck::cast_slice(&CORNERS),
+            usage:    BufferUsage::VERTEX,
+        });
+
+        let style_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some("particle style"),
+            entries: &[BindGroupLayoutEntry {
+                binding:    0,
+                visibility: ShaderStage::VERTEX,
+                ty:         BindingType::Buffer {
+                    ty:                 BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size:   None,
+                },
+                count:      None,
+            }],
+        });
+
+        let style_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label:   Some("particle style"),
+            layout:  &style_layout,
+            entries: &[BindGroupEntry {
+                binding:  0,
+                resource: style.as_entire_binding(),
+            }],
+        });
+
+        let shader_module = device.create_shader_module(&ShaderModuleDescriptor {
+            label:  Some("particles"),
+            flags:  ShaderFlags::all(),
+            source: ShaderSource::Wgsl(include_str!("./shaders/particles/render.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label:                Some("particles"),
+            bind_group_layouts:   &[&globals.bind_group_layout, &style_layout],
+            push_constant_ranges: &[],
+        });
+
+        const HALF: BufferAddress = mem::size_of::<[f32; 4]>() as BufferAddress;
+
+        let vertex_buffers = [
+            VertexBufferLayout {
+                array_stride: mem::size_of::<[f32; 2]>() as BufferAddress,
+                step_mode:    InputStepMode::Vertex,
+                attributes:   &[VertexAttribute {
+                    offset:          0,
+                    shader_location: 0,
+                    format:          VertexFormat::Float32x2,
+                }],
+            },
+            VertexBufferLayout {
+                array_stride: mem::size_of::<Particle>() as BufferAddress,
+                step_mode:    InputStepMode::Instance,
+                attributes:   &[
+                    VertexAttribute {
+                        offset:          0,
+                        shader_location: 1,
+                        format:          VertexFormat::Float32x4,
+                    },
+                    VertexAttribute {
+                        offset:          HALF,
+                        shader_location: 2,
+                        format:          VertexFormat::Float32x4,
+                    },
+                ],
+            },
+        ];
+
+        // Particles are blended over the scene, they're depth tested against it but never
+        // occlude each other.
+        let depth_stencil_state = DepthStencilState {
+            format:              DEPTH_FORMAT,
+            depth_write_enabled: false,
+            depth_compare:       CompareFunction::Less,
+            stencil:             StencilState::default(),
+            bias:                DepthBiasState::default(),
+        };
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label:         Some("particles"),
+            layout:        Some(&pipeline_layout),
+            vertex:        VertexState {
+                module:      &shader_module,
+                entry_point: "main",
+                buffers:     &vertex_buffers,
+            },
+            fragment:      Some(FragmentState {
+                module:      &shader_module,
+                entry_point: "main",
+                targets:     &[ColourTargetState {
+                    format:     HDR_FORMAT,
+                    blend:      Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColourWrite::ALL,
+                }],
+            }),
+            primitive:     PrimitiveState::default(),
+            depth_stencil: Some(depth_stencil_state),
+            multisample:   MultisampleState {
+                count: *sample_count,
+                ..Default::default()
+            },
+        });
+
+        Self {
+            emitter,
+            capacity,
+            particles,
+            simulation,
+            simulate_pipeline,
+            simulate_bindings,
+            style,
+            style_bind_group,
+            render_pipeline,
+            corners,
+            spawn_accumulator: 0.0,
+            pending_burst: 0,
+            next_spawn: 0,
+            seed: 0,
+        }
+    }
+
+    pub fn emitter(&self) -> &ParticleEmitter {
+        &self.emitter
+    }
+
+    /// Changes take effect on the next `update`, particles already alive keep the lifetime and
+    /// velocity they were spawned with.
+    pub fn emitter_mut(&mut self) -> &mut ParticleEmitter {
+        &mut self.emitter
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Spawns `count` particles on the next `update`, on top of the emitter's rate.
+    pub fn burst(&mut self, count: u32) {
+        self.pending_burst = self.pending_burst.saturating_add(count);
+    }
+
+    /// Works out what to spawn and uploads the emitter. Call once per frame before the system
+    /// is simulated with `RenderFrame::simulate_particles`.
+    pub fn update(&mut self, renderer: &Renderer, delta_time: f32) {
+        let emitted = self.spawn_accumulator + self.emitter.rate.max(0.0) * delta_time;
+        let spawn_count = (emitted as u32)
+            .saturating_add(self.pending_burst)
+            .min(self.capacity);
+
+        self.spawn_accumulator = emitted.fract();
+        self.pending_burst = 0;
+
+        let simulation = SimulationUniform {
+            emitter_position: self.emitter.position,
+            delta_time,
+            velocity: self.emitter.velocity,
+            velocity_spread: self.emitter.velocity_spread,
+            gravity: self.emitter.gravity,
+            lifetime: self.emitter.lifetime,
+            lifetime_variance: self.emitter.lifetime_variance.clamp(0.0, 1.0),
+            spawn_start: self.next_spawn,
+            spawn_count,
+            capacity: self.capacity,
+            seed: self.seed,
+            _padding: [0; 3],
+        };
+
+        self.next_spawn = (self.next_spawn + spawn_count) % self.capacity;
+        self.seed = self.seed.wrapping_add(1);
+
+        self.simulation.write(renderer, 0, &[simulation]);
+
+        renderer.queue.write_buffer(
+            &self.style,
+            0,
+            bytemuck::bytes_of(&StyleUniform::new(&self.emitter)),
+        );
+        renderer
+            .counters
+            .record_upload(mem::size_of::<StyleUniform>());
+    }
+
+    pub(in crate::graphics) fn workgroup_count(&self) -> u32 {
+        self.capacity.div_ceil(WORKGROUP_SIZE)
+    }
+}
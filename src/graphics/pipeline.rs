@@ -1,20 +1,35 @@
 use wgpu::{
+    BlendState,
+    ColorTargetState as ColourTargetState,
+    ColorWrite as ColourWrite,
+    CompareFunction,
+    DepthBiasState,
+    DepthStencilState,
+    Face,
     FragmentState,
     MultisampleState,
-    PipelineLayout,
     PipelineLayoutDescriptor,
     PrimitiveState,
     RenderPipeline,
     ShaderFlags,
     ShaderModuleDescriptor,
     ShaderSource,
+    StencilState,
     VertexState,
 };
 
-use crate::graphics::{Renderer, Vertex};
+use crate::graphics::{
+    AlphaMode,
+    Instance,
+    MaterialLayout,
+    PbrVertex,
+    Renderer,
+    Vertex,
+    DEPTH_FORMAT,
+    HDR_FORMAT,
+};
 
 pub struct Pipeline {
-    pub(in crate::graphics) pipeline_layout: PipelineLayout,
     pub(in crate::graphics) render_pipeline: RenderPipeline,
 }
 
@@ -22,7 +37,7 @@ impl Pipeline {
     pub fn new(
         Renderer {
             device,
-            swap_chain_format,
+            sample_count,
             ..
         }: &Renderer,
     ) -> Self {
@@ -51,7 +66,7 @@ impl Pipeline {
         let fragment_state = FragmentState {
             module:      &shader_module,
             entry_point: "main",
-            targets:     &[(*swap_chain_format).into()],
+            targets:     &[HDR_FORMAT.into()],
         };
 
         let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -61,12 +76,90 @@ impl Pipeline {
             fragment:      Some(fragment_state),
             primitive:     PrimitiveState::default(),
             depth_stencil: None,
-            multisample:   MultisampleState::default(),
+            multisample:   MultisampleState {
+                count: *sample_count,
+                ..Default::default()
+            },
+        });
+
+        Self { render_pipeline }
+    }
+
+    pub(in crate::graphics) fn pbr(
+        Renderer {
+            device,
+            globals,
+            material_bind_group_layout,
+            sample_count,
+            ..
+        }: &Renderer,
+        layout: MaterialLayout,
+    ) -> Self {
+        let shader_module = device.create_shader_module(&ShaderModuleDescriptor {
+            label:  Some("pbr"),
+            flags:  ShaderFlags::all(),
+            source: ShaderSource::Wgsl(include_str!("./shaders/pbr.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label:                Some("pbr"),
+            bind_group_layouts:   &[&globals.bind_group_layout, material_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let blend = match layout.alpha_mode {
+            AlphaMode::Opaque | AlphaMode::Mask => None,
+            AlphaMode::Blend => Some(BlendState::ALPHA_BLENDING),
+        };
+
+        let vertex_state = VertexState {
+            module:      &shader_module,
+            entry_point: "main",
+            buffers:     &[PbrVertex::buffer_descriptor(), Instance::buffer_descriptor()],
+        };
+
+        let fragment_state = FragmentState {
+            module:      &shader_module,
+            entry_point: "main",
+            targets:     &[ColourTargetState {
+                format: HDR_FORMAT,
+                blend,
+                write_mask: ColourWrite::ALL,
+            }],
+        };
+
+        let primitive_state = PrimitiveState {
+            cull_mode: if layout.double_sided {
+                None
+            } else {
+                Some(Face::Back)
+            },
+            ..Default::default()
+        };
+
+        // Blended surfaces are depth tested but must not occlude whatever is drawn behind them
+        // afterwards.
+        let depth_stencil_state = DepthStencilState {
+            format:              DEPTH_FORMAT,
+            depth_write_enabled: layout.alpha_mode != AlphaMode::Blend,
+            depth_compare:       CompareFunction::Less,
+            stencil:             StencilState::default(),
+            bias:                DepthBiasState::default(),
+        };
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label:         Some("pbr"),
+            layout:        Some(&pipeline_layout),
+            vertex:        vertex_state,
+            fragment:      Some(fragment_state),
+            primitive:     primitive_state,
+            depth_stencil: Some(depth_stencil_state),
+            multisample:   MultisampleState {
+                count: *sample_count,
+                ..Default::default()
+            },
         });
 
-        Self {
-            pipeline_layout,
-            render_pipeline,
-        }
+        Self { render_pipeline }
     }
 }
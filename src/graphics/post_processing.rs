@@ -0,0 +1,812 @@
+use std::{mem, rc::Rc};
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    AddressMode,
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupEntry,
+    BindGroupLayout,
+    BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry,
+    BindingResource,
+    BindingType,
+    Buffer as WgpuBuffer,
+    BufferBindingType,
+    BufferUsage,
+    Color as Colour,
+    CommandEncoder,
+    Device,
+    FilterMode,
+    FragmentState,
+    LoadOp,
+    MultisampleState,
+    Operations,
+    PipelineLayoutDescriptor,
+    PrimitiveState,
+    RenderPassColorAttachment as RenderPassColourAttachment,
+    RenderPassDescriptor,
+    RenderPipeline,
+    RenderPipelineDescriptor,
+    Sampler,
+    SamplerDescriptor,
+    ShaderFlags,
+    ShaderModuleDescriptor,
+    ShaderSource,
+    ShaderStage,
+    TextureFormat,
+    TextureSampleType,
+    TextureUsage,
+    TextureView,
+    TextureViewDimension,
+    VertexState,
+};
+
+use crate::graphics::{FrameCounters, RenderTarget, Renderer, Texture};
+
+/// The format of the scene and every intermediate post processing target.
+pub const HDR_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+const FULL_SCREEN_SOURCE: &str = include_str!("./shaders/post/full_screen.wgsl");
+
+/// The curve used to map HDR colours into displayable range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tonemapper {
+    Aces,
+    Reinhard,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct TonemapSettings {
+    pub exposure: f32,
+    operator:     u32,
+    _padding:     [f32; 2],
+}
+
+impl TonemapSettings {
+    pub fn new(tonemapper: Tonemapper, exposure: f32) -> Self {
+        let operator = match tonemapper {
+            Tonemapper::Aces => 0,
+            Tonemapper::Reinhard => 1,
+        };
+
+        Self {
+            exposure,
+            operator,
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct BloomSettings {
+    /// Brightness above which pixels start to bloom.
+    pub threshold: f32,
+    /// Width of the soft transition around the threshold.
+    pub knee:      f32,
+    /// How strongly the blurred highlights are added back to the scene.
+    pub intensity: f32,
+    _padding:      f32,
+}
+
+impl BloomSettings {
+    pub fn new(threshold: f32, knee: f32, intensity: f32) -> Self {
+        Self {
+            threshold,
+            knee,
+            intensity,
+            _padding: 0.0,
+        }
+    }
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self::new(1.0, 0.5, 0.3)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct VignetteSettings {
+    pub colour:     [f32; 4],
+    pub intensity:  f32,
+    pub smoothness: f32,
+    _padding:       [f32; 2],
+}
+
+impl VignetteSettings {
+    pub fn new(colour: Colour, intensity: f32, smoothness: f32) -> Self {
+        let Colour { r, g, b, a } = colour;
+
+        Self {
+            colour: [r as f32, g as f32, b as f32, a as f32],
+            intensity,
+            smoothness,
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+impl Default for VignetteSettings {
+    fn default() -> Self {
+        Self::new(Colour::BLACK, 0.5, 0.5)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct ColourGradingSettings {
+    /// Number of slices in the LUT strip, which is also the width and height of each slice.
+    pub lut_size:     f32,
+    /// Blend between the original (0.0) and graded (1.0) colour.
+    pub contribution: f32,
+    _padding:         [f32; 2],
+}
+
+impl ColourGradingSettings {
+    pub fn new(lut_size: u32, contribution: f32) -> Self {
+        Self {
+            lut_size: lut_size as f32,
+            contribution,
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+pub struct PostEffectDescriptor<'a> {
+    pub label:    &'a str,
+    /// WGSL source providing a `main` fragment entry point. The full screen vertex stage, the
+    /// `FullScreenOutput` struct and the previous result as `source_texture`/`source_sampler` in
+    /// group 0 are prepended to it.
+    pub source:   &'a str,
+    /// Initial contents of the uniform at group 1, binding 0.
+    pub params:   &'a [u8],
+    /// Bound after the uniform as texture/sampler pairs, starting at group 1, binding 1.
+    pub textures: Vec<Texture>,
+}
+
+/// A full screen draw reading the previous result through group 0, and its own uniform and
+/// textures through group 1.
+pub(in crate::graphics) struct FullScreenPass {
+    pipeline:      RenderPipeline,
+    params_layout: BindGroupLayout,
+}
+
+impl FullScreenPass {
+    fn new(
+        device: &Device,
+        source_layout: &BindGroupLayout,
+        label: &str,
+        source: &str,
+        texture_count: u32,
+        format: TextureFormat,
+    ) -> Self {
+        let mut entries = vec![BindGroupLayoutEntry {
+            binding:    0,
+            visibility: ShaderStage::FRAGMENT,
+            ty:         BindingType::Buffer {
+                ty:                 BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size:   None,
+            },
+            count:      None,
+        }];
+
+        for index in 0..texture_count {
+            entries.push(texture_entry(1 + index * 2));
+            entries.push(sampler_entry(2 + index * 2));
+        }
+
+        let params_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some(label),
+            entries: &entries,
+        });
+
+        let shader_module = device.create_shader_module(&ShaderModuleDescriptor {
+            label:  Some(label),
+            flags:  ShaderFlags::all(),
+            source: ShaderSource::Wgsl(format!("{}\n{}", FULL_SCREEN_SOURCE, source).into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label:                Some(label),
+            bind_group_layouts:   &[source_layout, &params_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label:         Some(label),
+            layout:        Some(&pipeline_layout),
+            vertex:        VertexState {
+                module:      &shader_module,
+                entry_point: "main",
+                buffers:     &[],
+            },
+            fragment:      Some(FragmentState {
+                module:      &shader_module,
+                entry_point: "main",
+                targets:     &[format.into()],
+            }),
+            primitive:     PrimitiveState::default(),
+            depth_stencil: None,
+            multisample:   MultisampleState::default(),
+        });
+
+        Self {
+            pipeline,
+            params_layout,
+        }
+    }
+
+    /// Binds the pass's uniform and textures. Bind groups are cached by whoever owns the
+    /// resources and only rebuilt when those resources change, e.g. on resize.
+    fn bind_params(
+        &self,
+        device: &Device,
+        params: &WgpuBuffer,
+        textures: &[(&TextureView, &Sampler)],
+    ) -> BindGroup {
+        let mut entries = vec![BindGroupEntry {
+            binding:  0,
+            resource: params.as_entire_binding(),
+        }];
+
+        for (index, (view, sampler)) in textures.iter().enumerate() {
+            let binding = 1 + index as u32 * 2;
+
+            entries.push(BindGroupEntry {
+                binding,
+                resource: BindingResource::TextureView(view),
+            });
+
+            entries.push(BindGroupEntry {
+                binding:  binding + 1,
+                resource: BindingResource::Sampler(sampler),
+            });
+        }
+
+        device.create_bind_group(&BindGroupDescriptor {
+            label:   None,
+            layout:  &self.params_layout,
+            entries: &entries,
+        })
+    }
+
+    fn record(
+        &self,
+        encoder: &mut CommandEncoder,
+        source: &BindGroup,
+        params: &BindGroup,
+        target: &TextureView,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label:                    None,
+            color_attachments:        &[RenderPassColourAttachment {
+                view:           target,
+                resolve_target: None,
+                ops:            Operations {
+                    load:  LoadOp::Clear(Colour::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, source, &[]);
+        render_pass.set_bind_group(1, params, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+struct Bloom {
+    threshold:        FullScreenPass,
+    blur:             FullScreenPass,
+    composite:        FullScreenPass,
+    threshold_params: BindGroup,
+    horizontal:       BindGroup,
+    vertical:         BindGroup,
+    // Half resolution, the bright pass and blurs ping-pong between these.
+    targets:          [RenderTarget; 2],
+    // These read the targets, so they're rebuilt along with them.
+    target_sources:   [BindGroup; 2],
+    composite_params: BindGroup,
+}
+
+enum PostEffectKind {
+    // NOTE:
+    // The effect's textures aren't kept, the bind group holds on to them.
+    Single {
+        pass:   FullScreenPass,
+        params: BindGroup,
+    },
+    Bloom(Box<Bloom>),
+}
+
+pub struct PostEffect {
+    label:   String,
+    enabled: bool,
+    params:  WgpuBuffer,
+    kind:    PostEffectKind,
+}
+
+impl PostEffect {
+    /// Creates an effect from user supplied WGSL, see `PostEffectDescriptor`.
+    pub fn custom(renderer: &Renderer, descriptor: PostEffectDescriptor) -> Self {
+        let device = &renderer.device;
+        let post_processing = renderer.primary_post_processing();
+
+        let pass = FullScreenPass::new(
+            device,
+            &post_processing.source_layout,
+            descriptor.label,
+            descriptor.source,
+            descriptor.textures.len() as u32,
+            HDR_FORMAT,
+        );
+
+        let params = create_params(device, descriptor.label, descriptor.params);
+        let textures = descriptor
+            .textures
+            .iter()
+            .map(|texture| (&texture.view, &texture.sampler))
+            .collect::<Vec<_>>();
+
+        Self {
+            label: descriptor.label.to_owned(),
+            enabled: true,
+            kind: PostEffectKind::Single {
+                params: pass.bind_params(device, &params, &textures),
+                pass,
+            },
+            params,
+        }
+    }
+
+    /// Maps the HDR scene into displayable range. Effects that expect colours between 0 and 1,
+    /// such as FXAA and colour grading, should come after this.
+    pub fn tonemap(renderer: &Renderer, settings: TonemapSettings) -> Self {
+        Self::built_in(
+            renderer,
+            "tonemap",
+            include_str!("./shaders/post/tonemap.wgsl"),
+            bytemuck::bytes_of(&settings),
+            Vec::new(),
+        )
+    }
+
+    pub fn fxaa(renderer: &Renderer) -> Self {
+        Self::built_in(
+            renderer,
+            "fxaa",
+            include_str!("./shaders/post/fxaa.wgsl"),
+            &[0; 16],
+            Vec::new(),
+        )
+    }
+
+    pub fn vignette(renderer: &Renderer, settings: VignetteSettings) -> Self {
+        Self::built_in(
+            renderer,
+            "vignette",
+            include_str!("./shaders/post/vignette.wgsl"),
+            bytemuck::bytes_of(&settings),
+            Vec::new(),
+        )
+    }
+
+    /// Grades colours through a lookup table laid out as a horizontal strip of `lut_size` square
+    /// slices, the common format exported by image editors. The LUT should be linear.
+    pub fn colour_grading(
+        renderer: &Renderer,
+        lut: Texture,
+        settings: ColourGradingSettings,
+    ) -> Self {
+        Self::built_in(
+            renderer,
+            "colour grading",
+            include_str!("./shaders/post/colour_grading.wgsl"),
+            bytemuck::bytes_of(&settings),
+            vec![lut],
+        )
+    }
+
+    /// Blurs the highlights above the threshold and adds them back on top of the scene. Should
+    /// come before tonemapping so it operates on HDR values.
+    pub fn bloom(renderer: &Renderer, settings: BloomSettings) -> Self {
+        let device = &renderer.device;
+        let post_processing = renderer.primary_post_processing();
+        let size = renderer.window_size(renderer.primary_window);
+
+        const HORIZONTAL: [f32; 4] = [1.0, 0.0, 0.0, 0.0];
+        const VERTICAL: [f32; 4] = [0.0, 1.0, 0.0, 0.0];
+
+        let source_layout = &post_processing.source_layout;
+        let sampler = &post_processing.sampler;
+
+        let threshold = FullScreenPass::new(
+            device,
+            source_layout,
+            "bloom threshold",
+            include_str!("./shaders/post/bloom_threshold.wgsl"),
+            0,
+            HDR_FORMAT,
+        );
+
+        let blur = FullScreenPass::new(
+            device,
+            source_layout,
+            "bloom blur",
+            include_str!("./shaders/post/bloom_blur.wgsl"),
+            0,
+            HDR_FORMAT,
+        );
+
+        let composite = FullScreenPass::new(
+            device,
+            source_layout,
+            "bloom composite",
+            include_str!("./shaders/post/bloom_composite.wgsl"),
+            1,
+            HDR_FORMAT,
+        );
+
+        let params = create_params(device, "bloom", bytemuck::bytes_of(&settings));
+        let horizontal = create_params(device, "bloom horizontal", bytemuck::bytes_of(&HORIZONTAL));
+        let vertical = create_params(device, "bloom vertical", bytemuck::bytes_of(&VERTICAL));
+        let targets = create_bloom_targets(device, size.width, size.height);
+
+        let bloom = Bloom {
+            threshold_params: threshold.bind_params(device, &params, &[]),
+            horizontal:       blur.bind_params(device, &horizontal, &[]),
+            vertical:         blur.bind_params(device, &vertical, &[]),
+            target_sources:   create_source_bind_groups(device, source_layout, sampler, &targets),
+            composite_params: composite.bind_params(
+                device,
+                &params,
+                &[(&targets[0].view, sampler)],
+            ),
+            threshold,
+            blur,
+            composite,
+            targets,
+        };
+
+        Self {
+            label: "bloom".to_owned(),
+            enabled: true,
+            params,
+            kind: PostEffectKind::Bloom(Box::new(bloom)),
+        }
+    }
+
+    fn built_in(
+        renderer: &Renderer,
+        label: &str,
+        source: &str,
+        params: &[u8],
+        textures: Vec<Texture>,
+    ) -> Self {
+        Self::custom(
+            renderer,
+            PostEffectDescriptor {
+                label,
+                source,
+                params,
+                textures,
+            },
+        )
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Disabled effects stay in the stack, in order, but are skipped.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Overwrites the effect's uniform, `params` must match the layout it was created with, e.g.
+    /// `TonemapSettings` for a tonemap effect.
+    pub fn update_params<T>(&self, renderer: &Renderer, params: &T)
+    where
+        T: Pod,
+    {
+        renderer
+            .queue
+            .write_buffer(&self.params, 0, bytemuck::bytes_of(params));
+        renderer.counters.record_upload(mem::size_of::<T>());
+    }
+
+    fn pass_count(&self) -> u32 {
+        match &self.kind {
+            PostEffectKind::Single { .. } => 1,
+            PostEffectKind::Bloom(_) => 4,
+        }
+    }
+
+    // NOTE:
+    // Effects can be created for one window and pushed onto the stack of another, so the size of
+    // the bloom targets is checked whenever the stack is applied rather than only on resize.
+    fn fit(
+        &mut self,
+        device: &Device,
+        source_layout: &BindGroupLayout,
+        sampler: &Sampler,
+        width: u32,
+        height: u32,
+    ) {
+        if let PostEffectKind::Bloom(bloom) = &mut self.kind {
+            let [target, _] = &bloom.targets;
+
+            // NOTE:
+            // Render targets are at least 1x1, so tiny windows still get bloom targets.
+            if target.width == (width / 2).max(1) && target.height == (height / 2).max(1) {
+                return;
+            }
+
+            bloom.targets = create_bloom_targets(device, width, height);
+            bloom.target_sources =
+                create_source_bind_groups(device, source_layout, sampler, &bloom.targets);
+            bloom.composite_params = bloom.composite.bind_params(
+                device,
+                &self.params,
+                &[(&bloom.targets[0].view, sampler)],
+            );
+        }
+    }
+
+    fn record(&self, encoder: &mut CommandEncoder, source: &BindGroup, target: &RenderTarget) {
+        match &self.kind {
+            PostEffectKind::Single { pass, params } => {
+                pass.record(encoder, source, params, &target.view);
+            },
+
+            PostEffectKind::Bloom(bloom) => {
+                let [first, second] = &bloom.targets;
+                let [first_source, second_source] = &bloom.target_sources;
+
+                bloom.threshold.record(encoder, source, &bloom.threshold_params, &first.view);
+                bloom.blur.record(encoder, first_source, &bloom.horizontal, &second.view);
+                bloom.blur.record(encoder, second_source, &bloom.vertical, &first.view);
+                bloom.composite.record(encoder, source, &bloom.composite_params, &target.view);
+            },
+        }
+    }
+}
+
+/// An ordered stack of full screen effects applied to the HDR scene before it is presented.
+pub struct PostProcessing {
+    pub(in crate::graphics) effects:        Vec<PostEffect>,
+    pub(in crate::graphics) source_layout:  BindGroupLayout,
+    pub(in crate::graphics) sampler:        Sampler,
+    // The scene is drawn into the first target, effects then ping-pong between the two.
+    pub(in crate::graphics) targets:        [RenderTarget; 2],
+    // Each target bound as the source of the next pass, rebuilt along with the targets.
+    pub(in crate::graphics) sources:        [BindGroup; 2],
+    pub(in crate::graphics) present:        FullScreenPass,
+    pub(in crate::graphics) present_params: BindGroup,
+}
+
+impl PostProcessing {
+    pub(in crate::graphics) fn new(
+        device: &Device,
+        swap_chain_format: TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let source_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some("post processing source"),
+            entries: &[texture_entry(0), sampler_entry(1)],
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("post processing"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let present = FullScreenPass::new(
+            device,
+            &source_layout,
+            "present",
+            include_str!("./shaders/post/blit.wgsl"),
+            0,
+            swap_chain_format,
+        );
+
+        let targets = create_hdr_targets(device, width, height);
+        let present_params = create_params(device, "present", &[0; 16]);
+
+        Self {
+            effects: Vec::new(),
+            sources: create_source_bind_groups(device, &source_layout, &sampler, &targets),
+            present_params: present.bind_params(device, &present_params, &[]),
+            targets,
+            source_layout,
+            sampler,
+            present,
+        }
+    }
+
+    pub fn push(&mut self, effect: PostEffect) {
+        self.effects.push(effect);
+    }
+
+    pub fn insert(&mut self, index: usize, effect: PostEffect) {
+        self.effects.insert(index, effect);
+    }
+
+    pub fn remove(&mut self, index: usize) -> PostEffect {
+        self.effects.remove(index)
+    }
+
+    /// Moves the effect at `from` so it ends up at `to`, shifting the effects in between.
+    pub fn move_effect(&mut self, from: usize, to: usize) {
+        let effect = self.effects.remove(from);
+        self.effects.insert(to, effect);
+    }
+
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    pub fn effects(&self) -> &[PostEffect] {
+        &self.effects
+    }
+
+    pub fn effects_mut(&mut self) -> &mut [PostEffect] {
+        &mut self.effects
+    }
+
+    pub fn find(&self, label: &str) -> Option<&PostEffect> {
+        self.effects.iter().find(|effect| effect.label == label)
+    }
+
+    pub fn find_mut(&mut self, label: &str) -> Option<&mut PostEffect> {
+        self.effects.iter_mut().find(|effect| effect.label == label)
+    }
+
+    pub(in crate::graphics) fn scene_view(&self) -> Rc<TextureView> {
+        Rc::clone(&self.targets[0].view)
+    }
+
+    pub(in crate::graphics) fn resize(&mut self, device: &Device, width: u32, height: u32) {
+        self.targets = create_hdr_targets(device, width, height);
+        self.sources =
+            create_source_bind_groups(device, &self.source_layout, &self.sampler, &self.targets);
+
+        for effect in &mut self.effects {
+            effect.fit(device, &self.source_layout, &self.sampler, width, height);
+        }
+    }
+
+    pub(in crate::graphics) fn apply(
+        &mut self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        output: &TextureView,
+        counters: &FrameCounters,
+    ) {
+        let [scene, _] = &self.targets;
+        let (width, height) = (scene.width, scene.height);
+
+        for effect in &mut self.effects {
+            effect.fit(device, &self.source_layout, &self.sampler, width, height);
+        }
+
+        let mut current = 0;
+
+        for effect in self.effects.iter().filter(|effect| effect.enabled) {
+            let next = 1 - current;
+
+            effect.record(encoder, &self.sources[current], &self.targets[next]);
+
+            for _ in 0..effect.pass_count() {
+                counters.record_draw(3, 1);
+            }
+
+            current = next;
+        }
+
+        self.present.record(encoder, &self.sources[current], &self.present_params, output);
+
+        counters.record_draw(3, 1);
+    }
+}
+
+fn create_hdr_targets(device: &Device, width: u32, height: u32) -> [RenderTarget; 2] {
+    let usage = TextureUsage::RENDER_ATTACHMENT | TextureUsage::SAMPLED;
+
+    [
+        RenderTarget::new(device, "hdr 0", width, height, HDR_FORMAT, usage),
+        RenderTarget::new(device, "hdr 1", width, height, HDR_FORMAT, usage),
+    ]
+}
+
+fn create_bloom_targets(device: &Device, width: u32, height: u32) -> [RenderTarget; 2] {
+    let usage = TextureUsage::RENDER_ATTACHMENT | TextureUsage::SAMPLED;
+
+    [
+        RenderTarget::new(device, "bloom 0", width / 2, height / 2, HDR_FORMAT, usage),
+        RenderTarget::new(device, "bloom 1", width / 2, height / 2, HDR_FORMAT, usage),
+    ]
+}
+
+fn create_source_bind_groups(
+    device: &Device,
+    layout: &BindGroupLayout,
+    sampler: &Sampler,
+    targets: &[RenderTarget; 2],
+) -> [BindGroup; 2] {
+    let create = |target: &RenderTarget| {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: None,
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding:  0,
+                    resource: BindingResource::TextureView(&target.view),
+                },
+                BindGroupEntry {
+                    binding:  1,
+                    resource: BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    };
+
+    [create(&targets[0]), create(&targets[1])]
+}
+
+fn create_params(device: &Device, label: &str, contents: &[u8]) -> WgpuBuffer {
+    // NOTE:
+    // Every pass binds a uniform, even when its shader doesn't declare one, and empty buffers
+    // can't be bound.
+    let contents = if contents.is_empty() { &[0; 16] } else { contents };
+
+    device.create_buffer_init(&BufferInitDescriptor {
+        label: Some(label),
+        contents,
+        usage: BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+    })
+}
+
+fn texture_entry(binding: u32) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStage::FRAGMENT,
+        ty: BindingType::Texture {
+            sample_type:    TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+            multisampled:   false,
+        },
+        count: None,
+    }
+}
+
+fn sampler_entry(binding: u32) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStage::FRAGMENT,
+        ty: BindingType::Sampler {
+            filtering:  true,
+            comparison: false,
+        },
+        count: None,
+    }
+}
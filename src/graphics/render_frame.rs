@@ -1,30 +1,60 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
 use wgpu::{
+    BindGroup,
     Color as Colour,
     CommandEncoder,
+    ComputePassDescriptor,
     IndexFormat,
     LoadOp,
     Operations,
     RenderPassColorAttachment as RenderPassColourAttachment,
+    RenderPassDepthStencilAttachment,
     RenderPassDescriptor,
     SwapChainFrame,
+    TextureView,
 };
+use winit::window::WindowId;
 
-use crate::graphics::{Mesh, Pipeline};
+use crate::graphics::{
+    ComputeBindings,
+    ComputePipeline,
+    FrameCounters,
+    InstanceBuffer,
+    Material,
+    Mesh,
+    ParticleSystem,
+    Pipeline,
+    Skybox,
+};
 
 pub struct RenderFrame {
-    pub(in crate::graphics) encoder: CommandEncoder,
-    pub(in crate::graphics) frame:   SwapChainFrame,
+    pub(in crate::graphics) window:         WindowId,
+    pub(in crate::graphics) encoder:        CommandEncoder,
+    pub(in crate::graphics) frame:          SwapChainFrame,
+    pub(in crate::graphics) colour_view:    Rc<TextureView>,
+    pub(in crate::graphics) resolve_view:   Option<Rc<TextureView>>,
+    pub(in crate::graphics) depth_view:     Rc<TextureView>,
+    pub(in crate::graphics) globals:        Rc<BindGroup>,
+    pub(in crate::graphics) started:        Instant,
+    pub(in crate::graphics) frame_time:     Duration,
+    pub(in crate::graphics) counters:       FrameCounters,
 }
 
 impl RenderFrame {
+    /// The window the frame will be presented to.
+    pub fn window(&self) -> WindowId {
+        self.window
+    }
+
     pub fn render_mesh(&mut self, pipeline: &Pipeline, mesh: &Mesh) {
         let colour_attachments = &[RenderPassColourAttachment {
-            view:           &self.frame.output.view,
-            resolve_target: None,
-            ops:            Operations {
-                load:  LoadOp::Clear(Colour::BLACK),
-                store: true,
-            },
+            view:           &self.colour_view,
+            resolve_target: self.resolve_view.as_deref(),
+            ops:            load(),
         }];
 
         let mut render_pass = self.encoder.begin_render_pass(&RenderPassDescriptor {
@@ -37,6 +67,167 @@ impl RenderFrame {
         render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
         render_pass.set_index_buffer(mesh.index_buffer.slice(..), IndexFormat::Uint16);
         render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+
+        self.counters.record_draw(mesh.index_count, 1);
+    }
+
+    /// Draws every instance of the mesh with the material. `Renderer::update_globals` should have
+    /// been called this frame so the camera and lighting are current.
+    pub fn render_material(
+        &mut self,
+        material: &Material,
+        mesh: &Mesh,
+        instances: &InstanceBuffer,
+    ) {
+        if instances.is_empty() {
+            return;
+        }
+
+        let colour_attachments = &[RenderPassColourAttachment {
+            view:           &self.colour_view,
+            resolve_target: self.resolve_view.as_deref(),
+            ops:            load(),
+        }];
+
+        let depth_stencil_attachment = RenderPassDepthStencilAttachment {
+            view:        &self.depth_view,
+            depth_ops:   Some(load()),
+            stencil_ops: None,
+        };
+
+        let mut render_pass = self.encoder.begin_render_pass(&RenderPassDescriptor {
+            label:                    Some("material"),
+            color_attachments:        colour_attachments,
+            depth_stencil_attachment: Some(depth_stencil_attachment),
+        });
+
+        render_pass.set_pipeline(&material.pipeline.render_pipeline);
+        render_pass.set_bind_group(0, &self.globals, &[]);
+        render_pass.set_bind_group(1, &material.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, instances.buffer.slice(..));
+        render_pass.set_index_buffer(mesh.index_buffer.slice(..), IndexFormat::Uint16);
+        render_pass.draw_indexed(0..mesh.index_count, 0, 0..instances.count);
+
+        self.counters.record_draw(mesh.index_count, instances.count);
+    }
+
+    /// Clears the colour and depth targets, run by the renderer when the frame begins so a frame
+    /// with nothing drawn doesn't show the previous one.
+    pub(in crate::graphics) fn clear(&mut self) {
+        let colour_attachments = &[RenderPassColourAttachment {
+            view:           &self.colour_view,
+            resolve_target: self.resolve_view.as_deref(),
+            ops:            Operations {
+                load:  LoadOp::Clear(Colour::BLACK),
+                store: true,
+            },
+        }];
+
+        let depth_stencil_attachment = RenderPassDepthStencilAttachment {
+            view:        &self.depth_view,
+            depth_ops:   Some(Operations {
+                load:  LoadOp::Clear(1.0),
+                store: true,
+            }),
+            stencil_ops: None,
+        };
+
+        self.encoder.begin_render_pass(&RenderPassDescriptor {
+            label:                    Some("clear"),
+            color_attachments:        colour_attachments,
+            depth_stencil_attachment: Some(depth_stencil_attachment),
+        });
+    }
+
+    /// Fills the colour target with the environment, run by the renderer before anything else is
+    /// drawn so the scene covers it.
+    pub(in crate::graphics) fn render_skybox(&mut self, skybox: &Skybox) {
+        let colour_attachments = &[RenderPassColourAttachment {
+            view:           &self.colour_view,
+            resolve_target: self.resolve_view.as_deref(),
+            ops:            load(),
+        }];
+
+        let mut render_pass = self.encoder.begin_render_pass(&RenderPassDescriptor {
+            label:                    Some("skybox"),
+            color_attachments:        colour_attachments,
+            depth_stencil_attachment: None,
+        });
+
+        render_pass.set_pipeline(&skybox.render_pipeline);
+        render_pass.set_bind_group(0, &self.globals, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        self.counters.record_draw(3, 1);
+    }
+
+    /// Runs `workgroups` workgroups of the compute pipeline, in x, y and z.
+    pub fn dispatch(
+        &mut self,
+        pipeline: &ComputePipeline,
+        bindings: &ComputeBindings,
+        workgroups: [u32; 3],
+    ) {
+        let [x, y, z] = workgroups;
+        let mut compute_pass = self
+            .encoder
+            .begin_compute_pass(&ComputePassDescriptor { label: None });
+
+        compute_pass.set_pipeline(&pipeline.compute_pipeline);
+        compute_pass.set_bind_group(0, &bindings.bind_group, &[]);
+        compute_pass.dispatch(x, y, z);
+
+        self.counters.record_dispatch();
+    }
+
+    /// Advances every particle by the time given to the system's last `update`.
+    pub fn simulate_particles(&mut self, particles: &ParticleSystem) {
+        self.dispatch(
+            &particles.simulate_pipeline,
+            &particles.simulate_bindings,
+            [particles.workgroup_count(), 1, 1],
+        );
+    }
+
+    /// Draws the particles over the scene. Particles are blended, so they should be drawn after
+    /// any opaque geometry.
+    pub fn render_particles(&mut self, particles: &ParticleSystem) {
+        let colour_attachments = &[RenderPassColourAttachment {
+            view:           &self.colour_view,
+            resolve_target: self.resolve_view.as_deref(),
+            ops:            load(),
+        }];
+
+        let depth_stencil_attachment = RenderPassDepthStencilAttachment {
+            view:        &self.depth_view,
+            depth_ops:   Some(load()),
+            stencil_ops: None,
+        };
+
+        let mut render_pass = self.encoder.begin_render_pass(&RenderPassDescriptor {
+            label:                    Some("particles"),
+            color_attachments:        colour_attachments,
+            depth_stencil_attachment: Some(depth_stencil_attachment),
+        });
+
+        render_pass.set_pipeline(&particles.render_pipeline);
+        render_pass.set_bind_group(0, &self.globals, &[]);
+        render_pass.set_bind_group(1, &particles.style_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, particles.corners.slice(..));
+        render_pass.set_vertex_buffer(1, particles.particles.buffer.slice(..));
+        render_pass.draw(0..6, 0..particles.capacity);
+
+        self.counters.record_draw(6, particles.capacity);
+    }
+}
+
+// NOTE:
+// Every target is cleared when the frame begins, so passes load what came before them.
+fn load<V>() -> Operations<V> {
+    Operations {
+        load:  LoadOp::Load,
+        store: true,
     }
 }
 
@@ -0,0 +1,639 @@
+use std::fmt::{self, Display};
+
+use wgpu::{
+    Buffer as WgpuBuffer,
+    BufferDescriptor,
+    BufferUsage,
+    CommandEncoder,
+    Device,
+    Queue,
+    TextureFormat,
+    TextureUsage,
+    TextureView,
+};
+
+use crate::graphics::{GpuTimer, RenderTarget};
+
+/// A texture or buffer that passes of a `RenderGraph` read and write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResourceId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PassId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureSize {
+    /// Matches the window and follows it when the renderer is resized.
+    Window,
+    /// The window size divided by the given factor, e.g. 2 for half resolution.
+    Divided(u32),
+    Fixed { width: u32, height: u32 },
+}
+
+impl TextureSize {
+    fn resolve(self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            TextureSize::Window => (width, height),
+            TextureSize::Divided(factor) => (width / factor.max(1), height / factor.max(1)),
+            TextureSize::Fixed { width, height } => (width, height),
+        }
+    }
+}
+
+/// A texture that only lives for the duration of a frame. Transient textures whose lifetimes
+/// don't overlap share the same memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransientTexture {
+    pub size:   TextureSize,
+    pub format: TextureFormat,
+}
+
+/// A buffer that only lives for the duration of a frame, aliased like `TransientTexture`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransientBuffer {
+    pub size:  u64,
+    pub usage: BufferUsage,
+}
+
+#[derive(Debug, Clone)]
+pub enum RenderGraphError {
+    /// The passes depend on each other in a loop, so no order satisfies all of them.
+    Cycle(Vec<String>),
+    /// The pass uses both `SCENE_COLOUR` and `SCENE_DEPTH` while MSAA is enabled, but the resolved
+    /// colour and multisampled depth can't be attached to the same render pass.
+    SampleCountMismatch(String),
+}
+
+impl Display for RenderGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RenderGraphError::*;
+
+        match self {
+            Cycle(passes) => write!(f, "Render graph passes form a cycle: {}", passes.join(", ")),
+            SampleCountMismatch(pass) => write!(
+                f,
+                "Render graph pass {} uses the scene colour and depth, whose sample counts differ",
+                pass
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceKind {
+    SceneColour,
+    SceneDepth,
+    Texture(TransientTexture),
+    Buffer(TransientBuffer),
+}
+
+impl ResourceKind {
+    fn is_imported(self) -> bool {
+        matches!(self, ResourceKind::SceneColour | ResourceKind::SceneDepth)
+    }
+}
+
+struct Resource {
+    label: String,
+    kind:  ResourceKind,
+}
+
+type Execute = Box<dyn FnMut(&mut PassContext)>;
+
+struct Pass {
+    label:   String,
+    reads:   Vec<ResourceId>,
+    writes:  Vec<ResourceId>,
+    execute: Execute,
+}
+
+impl Pass {
+    fn uses(&self, resource: ResourceId) -> bool {
+        self.reads.contains(&resource) || self.writes.contains(&resource)
+    }
+}
+
+/// The result of ordering, culling and aliasing the passes, independent of any GPU resources.
+#[derive(Debug)]
+struct CompiledGraph {
+    /// Indices of the passes to run, in order.
+    order:      Vec<usize>,
+    /// The physical slot of each transient resource, `None` for imports and unused resources.
+    slots:      Vec<Option<usize>>,
+    slot_kinds: Vec<ResourceKind>,
+}
+
+enum Allocation {
+    Texture(RenderTarget),
+    Buffer(WgpuBuffer),
+}
+
+/// The views and buffers a pass can access while it executes.
+pub struct PassResources<'a> {
+    scene_colour: &'a TextureView,
+    scene_depth:  &'a TextureView,
+    slots:        &'a [Option<usize>],
+    allocations:  &'a [Allocation],
+}
+
+impl<'a> PassResources<'a> {
+    pub fn texture(&self, id: ResourceId) -> &'a TextureView {
+        match self.slots[id.0].map(|slot| &self.allocations[slot]) {
+            Some(Allocation::Texture(target)) => &target.view,
+            Some(Allocation::Buffer(_)) => panic!("Render graph resource is a buffer"),
+            None if id == RenderGraph::SCENE_COLOUR => self.scene_colour,
+            None if id == RenderGraph::SCENE_DEPTH => self.scene_depth,
+            None => panic!("Render graph resource was not declared by this pass"),
+        }
+    }
+
+    pub fn buffer(&self, id: ResourceId) -> &'a WgpuBuffer {
+        match self.slots[id.0].map(|slot| &self.allocations[slot]) {
+            Some(Allocation::Buffer(buffer)) => buffer,
+            Some(Allocation::Texture(_)) => panic!("Render graph resource is a texture"),
+            None => panic!("Render graph resource was not declared by this pass"),
+        }
+    }
+}
+
+pub struct PassContext<'a> {
+    pub device:    &'a Device,
+    pub queue:     &'a Queue,
+    pub encoder:   &'a mut CommandEncoder,
+    pub resources: PassResources<'a>,
+}
+
+/// Orders passes by the resources they read and write, culls the ones whose results are never
+/// used and aliases the transient resources between them.
+///
+/// The graph runs after anything recorded directly through the `RenderFrame` and before post
+/// processing, so passes can draw into or read from the scene through `SCENE_COLOUR` and
+/// `SCENE_DEPTH`. With MSAA enabled `SCENE_COLOUR` is the resolved image while `SCENE_DEPTH` keeps
+/// `Renderer::sample_count` samples per pixel, so a pass can only use one of them.
+pub struct RenderGraph {
+    resources:    Vec<Resource>,
+    passes:       Vec<Option<Pass>>,
+    compiled:     Option<CompiledGraph>,
+    allocations:  Option<Vec<Allocation>>,
+    width:        u32,
+    height:       u32,
+    sample_count: u32,
+}
+
+impl RenderGraph {
+    pub const SCENE_COLOUR: ResourceId = ResourceId(0);
+    pub const SCENE_DEPTH: ResourceId = ResourceId(1);
+
+    pub(in crate::graphics) fn new(width: u32, height: u32, sample_count: u32) -> Self {
+        let resources = vec![
+            Resource {
+                label: "scene colour".to_owned(),
+                kind:  ResourceKind::SceneColour,
+            },
+            Resource {
+                label: "scene depth".to_owned(),
+                kind:  ResourceKind::SceneDepth,
+            },
+        ];
+
+        Self {
+            resources,
+            passes: Vec::new(),
+            compiled: None,
+            allocations: None,
+            width,
+            height,
+            sample_count,
+        }
+    }
+
+    pub fn create_texture(&mut self, label: &str, texture: TransientTexture) -> ResourceId {
+        self.add_resource(label, ResourceKind::Texture(texture))
+    }
+
+    pub fn create_buffer(&mut self, label: &str, buffer: TransientBuffer) -> ResourceId {
+        self.add_resource(label, ResourceKind::Buffer(buffer))
+    }
+
+    /// Adds a pass that runs after every pass writing the resources it reads. A pass that reads
+    /// and writes the same resource modifies it in place.
+    pub fn add_pass<F>(
+        &mut self,
+        label: &str,
+        reads: &[ResourceId],
+        writes: &[ResourceId],
+        execute: F,
+    ) -> PassId
+    where
+        F: FnMut(&mut PassContext) + 'static,
+    {
+        self.invalidate();
+        self.passes.push(Some(Pass {
+            label:   label.to_owned(),
+            reads:   reads.to_vec(),
+            writes:  writes.to_vec(),
+            execute: Box::new(execute),
+        }));
+
+        PassId(self.passes.len() - 1)
+    }
+
+    pub fn remove_pass(&mut self, pass: PassId) {
+        self.invalidate();
+        self.passes[pass.0] = None;
+    }
+
+    pub fn resource_label(&self, id: ResourceId) -> &str {
+        &self.resources[id.0].label
+    }
+
+    pub(in crate::graphics) fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.allocations = None;
+    }
+
+    pub(in crate::graphics) fn execute(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        scene_colour: &TextureView,
+        scene_depth: &TextureView,
+        mut timer: Option<&mut GpuTimer>,
+    ) -> Result<(), RenderGraphError> {
+        if self.compiled.is_none() {
+            self.compiled = Some(self.compile()?);
+        }
+
+        let compiled = self.compiled.as_ref().unwrap();
+
+        if self.allocations.is_none() {
+            self.allocations = Some(allocate(device, compiled, self.width, self.height));
+        }
+
+        let allocations = self.allocations.as_ref().unwrap();
+
+        for &index in &compiled.order {
+            let pass = self.passes[index].as_mut().unwrap();
+
+            if let Some(timer) = timer.as_deref_mut() {
+                timer.begin_span(encoder, &pass.label);
+            }
+
+            let mut context = PassContext {
+                device,
+                queue,
+                encoder,
+                resources: PassResources {
+                    scene_colour,
+                    scene_depth,
+                    slots: &compiled.slots,
+                    allocations,
+                },
+            };
+
+            (pass.execute)(&mut context);
+
+            if let Some(timer) = timer.as_deref_mut() {
+                timer.end_span(encoder);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_resource(&mut self, label: &str, kind: ResourceKind) -> ResourceId {
+        self.invalidate();
+        self.resources.push(Resource {
+            label: label.to_owned(),
+            kind,
+        });
+
+        ResourceId(self.resources.len() - 1)
+    }
+
+    fn invalidate(&mut self) {
+        self.compiled = None;
+        self.allocations = None;
+    }
+
+    fn compile(&self) -> Result<CompiledGraph, RenderGraphError> {
+        let pass_count = self.passes.len();
+        let resource_count = self.resources.len();
+
+        let mut writers = vec![Vec::new(); resource_count];
+        let mut readers = vec![Vec::new(); resource_count];
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            if let Some(pass) = pass {
+                let uses_scene = pass.uses(Self::SCENE_COLOUR) && pass.uses(Self::SCENE_DEPTH);
+
+                if self.sample_count > 1 && uses_scene {
+                    return Err(RenderGraphError::SampleCountMismatch(pass.label.clone()));
+                }
+
+                for write in &pass.writes {
+                    writers[write.0].push(index);
+                }
+
+                for read in pass.reads.iter().filter(|read| !pass.writes.contains(read)) {
+                    readers[read.0].push(index);
+                }
+            }
+        }
+
+        // Cull: keep passes with side effects, i.e. that write the scene or nothing at all, then
+        // everything that produces a resource a kept pass reads.
+        let mut needed = vec![false; pass_count];
+        let mut stack = Vec::new();
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            if let Some(pass) = pass {
+                let has_side_effects = pass.writes.is_empty()
+                    || pass
+                        .writes
+                        .iter()
+                        .any(|write| self.resources[write.0].kind.is_imported());
+
+                if has_side_effects {
+                    needed[index] = true;
+                    stack.push(index);
+                }
+            }
+        }
+
+        while let Some(index) = stack.pop() {
+            let pass = self.passes[index].as_ref().unwrap();
+
+            for resource in pass.reads.iter().chain(&pass.writes) {
+                for &writer in &writers[resource.0] {
+                    if !needed[writer] {
+                        needed[writer] = true;
+                        stack.push(writer);
+                    }
+                }
+            }
+        }
+
+        // Order: writers of a resource run in the order they were added, and before any of its
+        // readers.
+        let mut edges = vec![Vec::new(); pass_count];
+        let mut incoming = vec![0usize; pass_count];
+
+        for resource in 0..resource_count {
+            let resource_writers = writers[resource]
+                .iter()
+                .copied()
+                .filter(|&writer| needed[writer])
+                .collect::<Vec<_>>();
+
+            for pair in resource_writers.windows(2) {
+                edges[pair[0]].push(pair[1]);
+            }
+
+            if let Some(&last_writer) = resource_writers.last() {
+                for &reader in readers[resource].iter().filter(|&&reader| needed[reader]) {
+                    edges[last_writer].push(reader);
+                }
+            }
+        }
+
+        for targets in &edges {
+            for &target in targets {
+                incoming[target] += 1;
+            }
+        }
+
+        // Kahn's algorithm, always picking the earliest added pass that is ready so independent
+        // passes keep the order they were added in.
+        let mut ready = (0..pass_count)
+            .filter(|&index| needed[index] && incoming[index] == 0)
+            .collect::<Vec<_>>();
+        let mut order = Vec::new();
+
+        while !ready.is_empty() {
+            let (position, _) = ready
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, &index)| index)
+                .unwrap();
+            let index = ready.swap_remove(position);
+
+            order.push(index);
+
+            for &target in &edges[index] {
+                incoming[target] -= 1;
+
+                if incoming[target] == 0 {
+                    ready.push(target);
+                }
+            }
+        }
+
+        let needed_count = needed.iter().filter(|&&needed| needed).count();
+
+        if order.len() != needed_count {
+            let cycle = (0..pass_count)
+                .filter(|index| needed[*index] && !order.contains(index))
+                .map(|index| self.passes[index].as_ref().unwrap().label.clone())
+                .collect();
+
+            return Err(RenderGraphError::Cycle(cycle));
+        }
+
+        // Alias: give each transient resource the first slot of the same kind that is free for
+        // its whole lifetime.
+        let mut lifetimes = vec![None; resource_count];
+
+        for (step, &index) in order.iter().enumerate() {
+            let pass = self.passes[index].as_ref().unwrap();
+
+            for resource in pass.reads.iter().chain(&pass.writes) {
+                let lifetime = lifetimes[resource.0].get_or_insert((step, step));
+                lifetime.1 = step;
+            }
+        }
+
+        let mut by_first_use = (0..resource_count)
+            .filter(|&resource| !self.resources[resource].kind.is_imported())
+            .filter_map(|resource| lifetimes[resource].map(|lifetime| (resource, lifetime)))
+            .collect::<Vec<(usize, (usize, usize))>>();
+
+        by_first_use.sort_by_key(|&(_, (first, _))| first);
+
+        let mut slots = vec![None; resource_count];
+        let mut slot_kinds = Vec::new();
+        let mut slot_last_use: Vec<usize> = Vec::new();
+
+        for (resource, (first, last)) in by_first_use {
+            let kind = self.resources[resource].kind;
+            let free_slot = (0..slot_kinds.len())
+                .find(|&slot| slot_kinds[slot] == kind && slot_last_use[slot] < first);
+
+            let slot = match free_slot {
+                Some(slot) => slot,
+                None => {
+                    slot_kinds.push(kind);
+                    slot_last_use.push(0);
+                    slot_kinds.len() - 1
+                },
+            };
+
+            slot_last_use[slot] = last;
+            slots[resource] = Some(slot);
+        }
+
+        Ok(CompiledGraph {
+            order,
+            slots,
+            slot_kinds,
+        })
+    }
+}
+
+fn allocate(device: &Device, compiled: &CompiledGraph, width: u32, height: u32) -> Vec<Allocation> {
+    compiled
+        .slot_kinds
+        .iter()
+        .map(|kind| match kind {
+            ResourceKind::Texture(texture) => {
+                let (width, height) = texture.size.resolve(width, height);
+                let usage = TextureUsage::RENDER_ATTACHMENT
+                    | TextureUsage::SAMPLED
+                    | TextureUsage::COPY_SRC
+                    | TextureUsage::COPY_DST;
+
+                Allocation::Texture(RenderTarget::new(
+                    device,
+                    "render graph",
+                    width,
+                    height,
+                    texture.format,
+                    usage,
+                ))
+            },
+
+            ResourceKind::Buffer(buffer) => {
+                Allocation::Buffer(device.create_buffer(&BufferDescriptor {
+                    label:              Some("render graph"),
+                    size:               buffer.size,
+                    usage:              buffer.usage,
+                    mapped_at_creation: false,
+                }))
+            },
+
+            ResourceKind::SceneColour | ResourceKind::SceneDepth => {
+                unreachable!("Imported resources are never allocated")
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLOUR: TransientTexture = TransientTexture {
+        size:   TextureSize::Window,
+        format: TextureFormat::Rgba16Float,
+    };
+
+    fn labels(graph: &RenderGraph) -> Vec<String> {
+        graph
+            .compile()
+            .unwrap()
+            .order
+            .iter()
+            .map(|&index| graph.passes[index].as_ref().unwrap().label.clone())
+            .collect()
+    }
+
+    #[test]
+    fn orders_writers_before_readers() {
+        let mut graph = RenderGraph::new(1, 1, 1);
+        let shadows = graph.create_texture("shadows", COLOUR);
+
+        graph.add_pass("lighting", &[shadows], &[RenderGraph::SCENE_COLOUR], |_| {});
+        graph.add_pass("shadows", &[], &[shadows], |_| {});
+
+        assert_eq!(labels(&graph), ["shadows", "lighting"]);
+    }
+
+    #[test]
+    fn culls_unused_passes() {
+        let mut graph = RenderGraph::new(1, 1, 1);
+        let unused = graph.create_texture("unused", COLOUR);
+
+        graph.add_pass("unused", &[], &[unused], |_| {});
+        graph.add_pass("scene", &[], &[RenderGraph::SCENE_COLOUR], |_| {});
+
+        assert_eq!(labels(&graph), ["scene"]);
+    }
+
+    #[test]
+    fn aliases_resources_with_disjoint_lifetimes() {
+        let mut graph = RenderGraph::new(1, 1, 1);
+        let a = graph.create_texture("a", COLOUR);
+        let b = graph.create_texture("b", COLOUR);
+        let c = graph.create_texture("c", COLOUR);
+
+        graph.add_pass("write a", &[], &[a], |_| {});
+        graph.add_pass("a to b", &[a], &[b], |_| {});
+        graph.add_pass("b to c", &[b], &[c], |_| {});
+        graph.add_pass("c to scene", &[c], &[RenderGraph::SCENE_COLOUR], |_| {});
+
+        let compiled = graph.compile().unwrap();
+
+        assert_eq!(compiled.slot_kinds.len(), 2);
+        assert_eq!(compiled.slots[a.0], compiled.slots[c.0]);
+        assert_ne!(compiled.slots[a.0], compiled.slots[b.0]);
+    }
+
+    #[test]
+    fn does_not_alias_different_formats() {
+        let mut graph = RenderGraph::new(1, 1, 1);
+        let a = graph.create_texture("a", COLOUR);
+        let b = graph.create_texture("b", TransientTexture {
+            size:   TextureSize::Window,
+            format: TextureFormat::Depth32Float,
+        });
+
+        graph.add_pass("write a", &[], &[a], |_| {});
+        graph.add_pass("a to scene", &[a], &[RenderGraph::SCENE_COLOUR], |_| {});
+        graph.add_pass("write b", &[], &[b], |_| {});
+        graph.add_pass("b to scene", &[b], &[RenderGraph::SCENE_COLOUR], |_| {});
+
+        let compiled = graph.compile().unwrap();
+
+        assert_ne!(compiled.slots[a.0], compiled.slots[b.0]);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let mut graph = RenderGraph::new(1, 1, 1);
+        let a = graph.create_texture("a", COLOUR);
+        let b = graph.create_texture("b", COLOUR);
+
+        graph.add_pass("a to b", &[a], &[b, RenderGraph::SCENE_COLOUR], |_| {});
+        graph.add_pass("b to a", &[b], &[a], |_| {});
+
+        assert!(matches!(graph.compile(), Err(RenderGraphError::Cycle(_))));
+    }
+
+    #[test]
+    fn rejects_mismatched_sample_counts() {
+        let scene = [RenderGraph::SCENE_COLOUR, RenderGraph::SCENE_DEPTH];
+        let mut multisampled = RenderGraph::new(1, 1, 4);
+        let mut single_sampled = RenderGraph::new(1, 1, 1);
+
+        multisampled.add_pass("outline", &[], &scene, |_| {});
+        single_sampled.add_pass("outline", &[], &scene, |_| {});
+
+        assert!(matches!(
+            multisampled.compile(),
+            Err(RenderGraphError::SampleCountMismatch(_))
+        ));
+        assert!(single_sampled.compile().is_ok());
+    }
+}
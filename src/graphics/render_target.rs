@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use wgpu::{
+    Device,
+    Extent3d,
+    TextureDescriptor,
+    TextureDimension,
+    TextureFormat,
+    TextureUsage,
+    TextureView,
+    TextureViewDescriptor,
+};
+
+/// A texture the renderer draws into, sized to match the window or a fraction of it. Like
+/// `Texture`, only the view is kept, which holds on to the texture itself.
+pub(in crate::graphics) struct RenderTarget {
+    pub(in crate::graphics) view:    Rc<TextureView>,
+    pub(in crate::graphics) width:   u32,
+    pub(in crate::graphics) height:  u32,
+}
+
+impl RenderTarget {
+    pub(in crate::graphics) fn new(
+        device: &Device,
+        label: &str,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        usage: TextureUsage,
+    ) -> Self {
+        Self::multisampled(device, label, width, height, format, usage, 1)
+    }
+
+    /// Creates a target with several samples per texel. It can be drawn into but has to be
+    /// resolved into a single sampled target before it can be sampled.
+    pub(in crate::graphics) fn multisampled(
+        device: &Device,
+        label: &str,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        usage: TextureUsage,
+        sample_count: u32,
+    ) -> Self {
+        // NOTE:
+        // Minimised windows report a size of 0x0 which is not a valid texture size.
+        let width = width.max(1);
+        let height = height.max(1);
+
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: TextureDimension::D2,
+            format,
+            usage,
+        });
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        Self {
+            view: Rc::new(view),
+            width,
+            height,
+        }
+    }
+}
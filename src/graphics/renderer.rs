@@ -1,41 +1,72 @@
-use std::fmt::{self, Display};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    iter,
+    mem,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 use wgpu::{
     util::{BufferInitDescriptor, DeviceExt},
     Adapter,
-    BackendBit,
+    Backend,
+    BindGroupLayout,
     BufferUsage,
-    Color as Colour,
     CommandEncoderDescriptor,
     Device,
-    DeviceDescriptor,
     Features,
     Instance,
     Limits,
-    LoadOp,
-    Operations,
-    PowerPreference,
     PresentMode,
     Queue,
-    RenderPassColorAttachment as RenderPassColourAttachment,
-    RenderPassDescriptor,
-    RequestAdapterOptions,
     RequestDeviceError,
-    Surface,
-    SwapChain,
-    SwapChainDescriptor,
     SwapChainError,
     TextureFormat,
-    TextureUsage,
 };
-use winit::{dpi::PhysicalSize, event::WindowEvent, event_loop::ControlFlow, window::Window};
+use winit::{
+    dpi::PhysicalSize,
+    event::WindowEvent,
+    window::{Window, WindowId},
+};
+
+use crate::graphics::{
+    request_device,
+    AdapterCapabilities,
+    AdapterSettings,
+    Buffer,
+    Camera,
+    DefaultTextures,
+    Environment,
+    FrameCounters,
+    FrameStats,
+    Globals,
+    GpuTimer,
+    Lighting,
+    Material,
+    MaterialLayout,
+    Pipeline,
+    PostProcessing,
+    RenderFrame,
+    RenderGraph,
+    Skybox,
+    StatsOverlay,
+    Vertex,
+    WindowSurface,
+};
+
+pub const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
 
-use crate::graphics::{Buffer, Pipeline, Vertex};
+/// The MSAA sample counts that can be used, a count of 1 disables multisampling.
+pub const SAMPLE_COUNTS: [u32; 2] = [1, 4];
 
 #[derive(Debug, Clone)]
 pub enum RendererError {
     NoPreferredSwapChainFormat,
     NoAvailableGraphicsAdapter,
+    NoFallbackAdapter,
+    MissingFeatures(Features),
     NoAvailableGraphicsDevice(RequestDeviceError),
 }
 
@@ -46,6 +77,10 @@ impl Display for RendererError {
         match self {
             NoPreferredSwapChainFormat => write!(f, "No preferred swap chain format"),
             NoAvailableGraphicsAdapter => write!(f, "Unable to request a graphics adapter"),
+            NoFallbackAdapter => write!(f, "No fallback graphics adapter is available"),
+            MissingFeatures(features) => {
+                write!(f, "The graphics adapter does not support {:?}", features)
+            },
             NoAvailableGraphicsDevice(device_error) => {
                 write!(f, "Unable to request graphics device, {}", device_error)
             },
@@ -53,88 +88,196 @@ impl Display for RendererError {
     }
 }
 
+impl Error for RendererError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RendererError::NoAvailableGraphicsDevice(device_error) => Some(device_error),
+            _ => None,
+        }
+    }
+}
+
+/// Why `Renderer::begin_frame` could not begin a frame.
+#[derive(Debug, Clone)]
+pub enum FrameError {
+    /// The window is minimised, so there is nothing to draw into and the frame should be skipped.
+    Minimised,
+    /// Acquiring the next swap chain image timed out, the frame should be skipped.
+    Timeout,
+    /// The swap chain was outdated or lost and has been recreated, the frame should be skipped.
+    SwapChainRecreated,
+    /// There is no memory left to allocate the next swap chain image.
+    OutOfMemory,
+}
+
+impl FrameError {
+    /// Whether rendering can't continue, otherwise the frame should be skipped and the next one
+    /// attempted as usual.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, FrameError::OutOfMemory)
+    }
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FrameError::*;
+
+        match self {
+            Minimised => write!(f, "The window is minimised"),
+            Timeout => write!(f, "Timed out acquiring the next swap chain image"),
+            SwapChainRecreated => write!(f, "The swap chain was recreated"),
+            OutOfMemory => write!(f, "Out of memory acquiring the next swap chain image"),
+        }
+    }
+}
+
+impl Error for FrameError {}
+
+/// Draws into one or more windows with a single device, so everything created from the renderer
+/// can be used in any of its windows.
+///
+/// Losing the device, e.g. when the driver is updated or crashes, is fatal. wgpu panics in
+/// whichever call first notices, so there is no recovering from it.
 pub struct Renderer {
-    pub(in crate::graphics) size:                  PhysicalSize<u32>,
-    pub(in crate::graphics) instance:              Instance,
-    pub(in crate::graphics) surface:               Surface,
-    pub(in crate::graphics) adapter:               Adapter,
-    pub(in crate::graphics) device:                Device,
-    pub(in crate::graphics) queue:                 Queue,
-    pub(in crate::graphics) swap_chain:            SwapChain,
-    pub(in crate::graphics) swap_chain_descriptor: SwapChainDescriptor,
-    pub(in crate::graphics) swap_chain_format:     TextureFormat,
+    pub(in crate::graphics) instance:       Instance,
+    pub(in crate::graphics) adapter:        Adapter,
+    pub(in crate::graphics) device:         Device,
+    pub(in crate::graphics) queue:          Queue,
+    pub(in crate::graphics) present_mode:   PresentMode,
+    pub(in crate::graphics) primary_window: WindowId,
+    pub(in crate::graphics) windows:        HashMap<WindowId, WindowSurface>,
+
+    pub(in crate::graphics) sample_count:               u32,
+    pub(in crate::graphics) globals:                    Globals,
+    pub(in crate::graphics) material_bind_group_layout: BindGroupLayout,
+    pub(in crate::graphics) material_pipelines:         HashMap<MaterialLayout, Rc<Pipeline>>,
+    pub(in crate::graphics) default_textures:           DefaultTextures,
+    pub(in crate::graphics) environment:                Option<Environment>,
+    pub(in crate::graphics) skybox:                     Skybox,
+
+    pub(in crate::graphics) counters:      FrameCounters,
+    pub(in crate::graphics) gpu_timer:     Option<GpuTimer>,
+    pub(in crate::graphics) stats_overlay: Option<StatsOverlay>,
+    pub(in crate::graphics) frame_stats:   FrameStats,
 }
 
 impl Renderer {
+    /// Creates a renderer presenting to `window`, which becomes its primary window. The adapter is
+    /// chosen to be compatible with it.
     pub async fn new(
         window: &Window,
-        power_preference: PowerPreference,
+        adapter_settings: AdapterSettings,
+        present_mode: PresentMode,
+        sample_count: u32,
     ) -> Result<Self, RendererError> {
-        let size = window.inner_size();
-        let instance = Instance::new(BackendBit::all());
+        let instance = Instance::new(adapter_settings.backends);
         let surface = unsafe { instance.create_surface(window) };
 
-        let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                power_preference,
-                compatible_surface: Some(&surface),
-            })
-            .await;
-
-        if let Some(adapter) = adapter {
-            let device_request = adapter
-                .request_device(
-                    &DeviceDescriptor {
-                        label:    None,
-                        features: Features::empty(),
-                        limits:   Limits::default(),
-                    },
-                    None,
-                )
-                .await;
-
-            match device_request {
-                Ok((device, queue)) => {
-                    if let Some(swap_chain_format) =
-                        adapter.get_swap_chain_preferred_format(&surface)
-                    {
-                        let swap_chain_descriptor = SwapChainDescriptor {
-                            usage:        TextureUsage::RENDER_ATTACHMENT,
-                            format:       swap_chain_format,
-                            width:        size.width,
-                            height:       size.height,
-                            present_mode: PresentMode::Mailbox,
-                        };
-
-                        let swap_chain = device.create_swap_chain(&surface, &swap_chain_descriptor);
-
-                        Ok(Self {
-                            size,
-                            instance,
-                            surface,
-                            adapter,
-                            device,
-                            queue,
-                            swap_chain,
-                            swap_chain_descriptor,
-                            swap_chain_format,
-                        })
-                    } else {
-                        Err(RendererError::NoPreferredSwapChainFormat)
-                    }
-                },
-
-                Err(device_error) => Err(RendererError::NoAvailableGraphicsDevice(device_error)),
-            }
-        } else {
-            Err(RendererError::NoAvailableGraphicsAdapter)
-        }
+        let (adapter, device, queue, swap_chain_format) =
+            request_device(&instance, &surface, &adapter_settings).await?;
+
+        // NOTE:
+        // wgpu's default handler panics on every uncaptured error, most of which only break the
+        // frame they happen in.
+        device.on_uncaptured_error(|error| log::error!("Unhandled graphics error, {}", error));
+
+        let sample_count = supported_sample_count(sample_count);
+        let present_mode = supported_present_mode(&adapter, present_mode);
+        let primary_window = window.id();
+        let window_surface = WindowSurface::new(
+            &device,
+            surface,
+            swap_chain_format,
+            window.inner_size(),
+            present_mode,
+            sample_count,
+        );
+
+        let mut windows = HashMap::new();
+        windows.insert(primary_window, window_surface);
+
+        let default_textures = DefaultTextures::new(&device, &queue);
+        let globals = Globals::new(&device, &default_textures.black_cube);
+        let skybox = Skybox::new(&device, &globals.bind_group_layout, sample_count);
+        let material_bind_group_layout = Material::create_bind_group_layout(&device);
+        let gpu_timer = GpuTimer::new(&device, &queue);
+
+        Ok(Self {
+            instance,
+            adapter,
+            device,
+            queue,
+            present_mode,
+            primary_window,
+            windows,
+            sample_count,
+            globals,
+            material_bind_group_layout,
+            material_pipelines: HashMap::new(),
+            default_textures,
+            environment: None,
+            skybox,
+            counters: FrameCounters::default(),
+            gpu_timer,
+            stats_overlay: None,
+            frame_stats: FrameStats::default(),
+        })
+    }
+
+    /// Starts presenting to another window, e.g. one opened while the app is running.
+    pub fn add_window(&mut self, window: &Window) -> Result<(), RendererError> {
+        let surface = unsafe { self.instance.create_surface(window) };
+
+        // NOTE:
+        // The adapter was only chosen to be compatible with the primary window, so other windows
+        // may not be able to present with it, e.g. on another GPU's display.
+        let swap_chain_format = self
+            .adapter
+            .get_swap_chain_preferred_format(&surface)
+            .ok_or(RendererError::NoPreferredSwapChainFormat)?;
+
+        let window_surface = WindowSurface::new(
+            &self.device,
+            surface,
+            swap_chain_format,
+            window.inner_size(),
+            self.present_mode,
+            self.sample_count,
+        );
+
+        self.windows.insert(window.id(), window_surface);
+
+        Ok(())
+    }
+
+    /// Stops presenting to the window, its render graph and post processing are dropped with it.
+    pub fn remove_window(&mut self, window: WindowId) {
+        assert_ne!(window, self.primary_window, "The primary window can't be removed");
+
+        self.windows.remove(&window);
+    }
+
+    /// The window the renderer was created with. GPU timings and the stats overlay only cover
+    /// its frames.
+    pub fn primary_window(&self) -> WindowId {
+        self.primary_window
+    }
+
+    pub fn has_window(&self, window: WindowId) -> bool {
+        self.windows.contains_key(&window)
+    }
+
+    pub fn windows(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
     }
 
-    pub fn handle_event(&mut self, event: &WindowEvent) {
-        match event {
-            WindowEvent::Resized(new_size) => self.resize(*new_size),
-            _ => {},
+    pub fn window_size(&self, window: WindowId) -> PhysicalSize<u32> {
+        self.window_surface(window).size
+    }
+
+    pub fn handle_event(&mut self, window: WindowId, event: &WindowEvent) {
+        if let WindowEvent::Resized(new_size) = event {
+            self.resize(window, *new_size);
         }
     }
 
@@ -151,53 +294,250 @@ impl Renderer {
         }
     }
 
-    pub fn resize(&mut self, new_size: PhysicalSize<u32>) {
-        self.size = new_size;
-        self.swap_chain_descriptor.width = new_size.width;
-        self.swap_chain_descriptor.height = new_size.height;
+    pub fn resize(&mut self, window: WindowId, new_size: PhysicalSize<u32>) {
+        let Self {
+            device,
+            windows,
+            sample_count,
+            ..
+        } = self;
+
+        if let Some(window_surface) = windows.get_mut(&window) {
+            window_surface.resize(device, new_size, *sample_count);
+        }
+    }
+
+    /// The present mode the swap chains present with. When the adapter doesn't support the mode
+    /// that was asked for this is `PresentMode::Fifo`, which is always available.
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    /// Changes the present mode of every window, falling back to `PresentMode::Fifo` when the
+    /// adapter doesn't support it.
+    pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+        self.present_mode = supported_present_mode(&self.adapter, present_mode);
+
+        for window_surface in self.windows.values_mut() {
+            window_surface.set_present_mode(&self.device, self.present_mode);
+        }
+    }
+
+    pub fn vsync(&self) -> bool {
+        self.present_mode() == PresentMode::Fifo
+    }
+
+    /// Turns vsync on or off, e.g. from an options menu. Without vsync frames are presented with
+    /// `PresentMode::Immediate`, or `PresentMode::Mailbox` where that isn't supported, so the
+    /// frame rate runs uncapped. Adapters supporting neither stay on vsync.
+    pub fn set_vsync(&mut self, enabled: bool) {
+        if enabled {
+            self.set_present_mode(PresentMode::Fifo);
+        } else if self.vsync() {
+            let present_mode = [PresentMode::Immediate, PresentMode::Mailbox]
+                .iter()
+                .copied()
+                .find(|&mode| supports_present_mode(&self.adapter, mode))
+                .unwrap_or(PresentMode::Fifo);
+
+            self.set_present_mode(present_mode);
+        }
+    }
+
+    /// The adapter the renderer is using and what it supports, which may be more than what was
+    /// enabled on the device.
+    pub fn adapter_capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities::of(&self.adapter)
+    }
+
+    /// The features enabled on the device, the required and supported optional features.
+    pub fn features(&self) -> Features {
+        self.device.features()
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.device.limits()
+    }
+
+    /// The number of samples per pixel the scene is drawn with, 1 when MSAA is disabled.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
 
-        self.swap_chain = self
-            .device
-            .create_swap_chain(&self.surface, &self.swap_chain_descriptor);
+    /// Passes run every frame of the window after the scene and before post processing.
+    pub fn render_graph(&mut self, window: WindowId) -> &mut RenderGraph {
+        &mut self.window_surface_mut(window).render_graph
     }
 
-    pub fn recreate_swap_chain(&mut self) {
-        self.resize(self.size);
+    /// The chain of full screen effects applied to the window's scene before it is presented.
+    pub fn post_processing(&mut self, window: WindowId) -> &mut PostProcessing {
+        &mut self.window_surface_mut(window).post_processing
+    }
+
+    /// Uploads the camera and lighting used by every lit material this frame.
+    pub fn update_globals(&self, camera: &Camera, lighting: &Lighting) {
+        self.globals.update(
+            &self.queue,
+            &self.counters,
+            camera,
+            lighting,
+            self.environment.as_ref(),
+        );
+    }
+
+    /// Sets the environment drawn behind the scene and lighting it, `None` removes it and leaves
+    /// lit materials with only the direct and ambient light.
+    pub fn set_environment(&mut self, environment: Option<Environment>) {
+        let cubemap = environment
+            .as_ref()
+            .map_or(&self.default_textures.black_cube, Environment::cubemap);
+
+        self.globals.set_environment(&self.device, cubemap);
+        self.environment = environment;
     }
 
-    #[must_use]
-    pub fn begin_frame(&mut self) -> Result<RenderFrame, ControlFlow> {
-        match self.swap_chain.get_current_frame() {
+    pub fn environment(&self) -> Option<&Environment> {
+        self.environment.as_ref()
+    }
+
+    /// Changes to the environment's intensity take effect at the next `update_globals`.
+    pub fn environment_mut(&mut self) -> Option<&mut Environment> {
+        self.environment.as_mut()
+    }
+
+    /// Timings and counters for the last finished frame.
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.frame_stats
+    }
+
+    /// Whether the adapter supports the timestamp queries needed for GPU timings.
+    pub fn supports_gpu_timing(&self) -> bool {
+        self.gpu_timer.is_some()
+    }
+
+    pub fn stats_overlay_enabled(&self) -> bool {
+        self.stats_overlay.is_some()
+    }
+
+    /// Shows or hides a graph of recent frame times over the bottom left corner of the primary
+    /// window.
+    pub fn set_stats_overlay(&mut self, enabled: bool) {
+        if !enabled {
+            self.stats_overlay = None;
+        } else if self.stats_overlay.is_none() {
+            let format = self.window_surface(self.primary_window).swap_chain_format();
+
+            self.stats_overlay = Some(StatsOverlay::new(&self.device, format));
+        }
+    }
+
+    /// Returns the pipeline for materials with the given layout, creating it on first use.
+    pub(in crate::graphics) fn material_pipeline(
+        &mut self,
+        layout: MaterialLayout,
+    ) -> Rc<Pipeline> {
+        if let Some(pipeline) = self.material_pipelines.get(&layout) {
+            return Rc::clone(pipeline);
+        }
+
+        let pipeline = Rc::new(Pipeline::pbr(self, layout));
+        self.material_pipelines.insert(layout, Rc::clone(&pipeline));
+
+        pipeline
+    }
+
+    pub fn recreate_swap_chain(&mut self, window: WindowId) {
+        let Self {
+            device, windows, ..
+        } = self;
+
+        if let Some(window_surface) = windows.get_mut(&window) {
+            window_surface.recreate_swap_chain(device);
+        }
+    }
+
+    /// Begins recording a frame for the window. When it can't be begun the error says whether the
+    /// frame should just be skipped or rendering has to stop.
+    pub fn begin_frame(&mut self, window: WindowId) -> Result<RenderFrame, FrameError> {
+        let primary = window == self.primary_window;
+        let window_surface = self
+            .windows
+            .get_mut(&window)
+            .expect("Unknown window, it has to be added to the renderer first");
+
+        if window_surface.is_minimised() {
+            return Err(FrameError::Minimised);
+        }
+
+        match window_surface.swap_chain.get_current_frame() {
             Ok(frame) => {
-                let encoder = self
+                let mut encoder = self
                     .device
                     .create_command_encoder(&CommandEncoderDescriptor { label: None });
 
-                Ok(RenderFrame { frame, encoder })
-            },
+                let started = Instant::now();
+                let frame_time = window_surface
+                    .last_frame_start
+                    .map_or(Duration::ZERO, |last_frame_start| started - last_frame_start);
 
-            Err(swap_chain_error) => {
-                let mut critical_failure = false;
+                window_surface.last_frame_start = Some(started);
 
-                match swap_chain_error {
-                    SwapChainError::Lost => {
-                        log::warn!("{}", swap_chain_error);
-                        self.recreate_swap_chain();
-                    },
+                if let Some(gpu_timer) = self.gpu_timer.as_mut().filter(|_| primary) {
+                    gpu_timer.begin_frame();
+                    gpu_timer.begin_span(&mut encoder, "scene");
+                }
 
-                    SwapChainError::OutOfMemory => {
-                        log::error!("{}", swap_chain_error);
-                        critical_failure = true;
-                    },
+                // NOTE:
+                // With MSAA the scene is drawn into the multisampled target and every pass resolves
+                // it into the scene target that the render graph and post processing read from.
+                let scene_view = window_surface.post_processing.scene_view();
+                let (colour_view, resolve_view) = match &window_surface.msaa_target {
+                    Some(msaa_target) => (Rc::clone(&msaa_target.view), Some(scene_view)),
+                    None => (scene_view, None),
+                };
 
-                    _ => log::error!("{}", swap_chain_error),
-                }
+                let mut render_frame = RenderFrame {
+                    window,
+                    encoder,
+                    frame,
+                    colour_view,
+                    resolve_view,
+                    depth_view: Rc::clone(&window_surface.depth_target.view),
+                    globals: Rc::clone(&self.globals.bind_group),
+                    started,
+                    frame_time,
+                    counters: FrameCounters::default(),
+                };
+
+                render_frame.clear();
 
-                if critical_failure {
-                    Err(ControlFlow::Exit)
-                } else {
-                    Err(ControlFlow::Poll)
+                let draw_skybox = self
+                    .environment
+                    .as_ref()
+                    .is_some_and(|environment| environment.draw_skybox);
+
+                if draw_skybox {
+                    render_frame.render_skybox(&self.skybox);
                 }
+
+                Ok(render_frame)
+            },
+
+            Err(SwapChainError::Timeout) => {
+                log::warn!("{}", SwapChainError::Timeout);
+                Err(FrameError::Timeout)
+            },
+
+            Err(swap_chain_error @ SwapChainError::Outdated)
+            | Err(swap_chain_error @ SwapChainError::Lost) => {
+                log::warn!("{}", swap_chain_error);
+                window_surface.recreate_swap_chain(&self.device);
+                Err(FrameError::SwapChainRecreated)
+            },
+
+            Err(SwapChainError::OutOfMemory) => {
+                log::error!("{}", SwapChainError::OutOfMemory);
+                Err(FrameError::OutOfMemory)
             },
         }
     }
@@ -206,7 +546,159 @@ impl Renderer {
     // It would have been good to do this though a drop impl on the RenderFrame, but that would
     // require storing a `&'a mut` reference to the renderer which makes winit `event_loop.run` sad
     // because of the `move` keyword.
-    pub fn finish_frame(&mut self, frame: RenderFrame) {
-        self.queue.submit(std::iter::once(frame.encoder.finish()));
+    pub fn finish_frame(&mut self, mut frame: RenderFrame) {
+        let primary = frame.window == self.primary_window;
+        let window_surface = self
+            .windows
+            .get_mut(&frame.window)
+            .expect("Window removed while one of its frames was being recorded");
+
+        let mut gpu_timer = self.gpu_timer.as_mut().filter(|_| primary);
+
+        if let Some(gpu_timer) = &mut gpu_timer {
+            gpu_timer.end_span(&mut frame.encoder);
+        }
+
+        let graph_result = window_surface.render_graph.execute(
+            &self.device,
+            &self.queue,
+            &mut frame.encoder,
+            frame.resolve_view.as_ref().unwrap_or(&frame.colour_view),
+            &frame.depth_view,
+            gpu_timer.as_deref_mut(),
+        );
+
+        if let Err(graph_error) = graph_result {
+            log::error!("{}", graph_error);
+        }
+
+        if let Some(gpu_timer) = &mut gpu_timer {
+            gpu_timer.begin_span(&mut frame.encoder, "post processing");
+        }
+
+        window_surface.post_processing.apply(
+            &self.device,
+            &mut frame.encoder,
+            &frame.frame.output.view,
+            &frame.counters,
+        );
+
+        if let Some(gpu_timer) = &mut gpu_timer {
+            gpu_timer.resolve(&mut frame.encoder);
+        }
+
+        if let Some(stats_overlay) = self.stats_overlay.as_mut().filter(|_| primary) {
+            stats_overlay.push(frame.frame_time);
+            stats_overlay.record(
+                &self.queue,
+                &mut frame.encoder,
+                &frame.frame.output.view,
+                window_surface.size.width,
+                window_surface.size.height,
+            );
+        }
+
+        self.queue.submit(iter::once(frame.encoder.finish()));
+
+        // NOTE:
+        // The stats follow the primary window, anything recorded for other windows is counted
+        // towards its next frame.
+        if primary {
+            self.update_frame_stats(frame.frame_time, frame.started, &frame.counters);
+        } else {
+            self.counters.add(&frame.counters);
+        }
+    }
+
+    fn update_frame_stats(
+        &mut self,
+        frame_time: Duration,
+        started: Instant,
+        frame_counters: &FrameCounters,
+    ) {
+        let mut stats = FrameStats {
+            frame_time,
+            cpu_time: started.elapsed(),
+            gpu_time: self.frame_stats.gpu_time,
+            gpu_passes: mem::take(&mut self.frame_stats.gpu_passes),
+            ..FrameStats::default()
+        };
+
+        self.counters.drain_into(&mut stats);
+        frame_counters.drain_into(&mut stats);
+
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.finish_frame();
+
+            if let Some(gpu_passes) = gpu_timer.collect(&self.device) {
+                stats.gpu_time = Some(gpu_passes.iter().map(|pass| pass.duration).sum());
+                stats.gpu_passes = gpu_passes;
+            }
+        }
+
+        self.frame_stats = stats;
+    }
+
+    // NOTE:
+    // Effects are created against the primary window's stack. Every stack creates an identical
+    // source layout and wgpu deduplicates identical layouts, so they work on any window.
+    pub(in crate::graphics) fn primary_post_processing(&self) -> &PostProcessing {
+        &self.window_surface(self.primary_window).post_processing
+    }
+
+    pub(in crate::graphics) fn window_surface(&self, window: WindowId) -> &WindowSurface {
+        self.windows
+            .get(&window)
+            .expect("Unknown window, it has to be added to the renderer first")
+    }
+
+    pub(in crate::graphics) fn window_surface_mut(
+        &mut self,
+        window: WindowId,
+    ) -> &mut WindowSurface {
+        self.windows
+            .get_mut(&window)
+            .expect("Unknown window, it has to be added to the renderer first")
+    }
+}
+
+// NOTE:
+// wgpu does not report which present modes a surface supports, and quietly falls back to Fifo for
+// any it doesn't. What each backend supports is known though, except for Vulkan where it is up to
+// the driver and every mode is assumed to be available.
+fn supports_present_mode(adapter: &Adapter, present_mode: PresentMode) -> bool {
+    match adapter.get_info().backend {
+        Backend::Metal | Backend::Dx12 | Backend::Dx11 => {
+            matches!(present_mode, PresentMode::Immediate | PresentMode::Fifo)
+        },
+        Backend::Gl | Backend::BrowserWebGpu => present_mode == PresentMode::Fifo,
+        Backend::Vulkan | Backend::Empty => true,
+    }
+}
+
+fn supported_present_mode(adapter: &Adapter, requested: PresentMode) -> PresentMode {
+    if supports_present_mode(adapter, requested) {
+        requested
+    } else {
+        log::warn!("Present mode {:?} is not supported, using Fifo instead", requested);
+        PresentMode::Fifo
+    }
+}
+
+// NOTE:
+// wgpu does not report which sample counts an adapter supports, only 1 and 4 are guaranteed to
+// work with every format. Some Metal and Vulkan adapters reject 2 or 8 for the HDR or depth
+// formats, which would only show up once the targets are created.
+fn supported_sample_count(requested: u32) -> u32 {
+    match requested {
+        1 | 4 => requested,
+        0 => {
+            log::warn!("0 is not a valid MSAA sample count, disabling MSAA");
+            1
+        },
+        _ => {
+            log::warn!("MSAA sample count {} is not supported, using 4 instead", requested);
+            4
+        },
     }
 }
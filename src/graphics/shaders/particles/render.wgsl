@@ -0,0 +1,69 @@
+[[block]]
+struct Globals {
+    view_projection: mat4x4<f32>;
+    camera_position: vec4<f32>;
+    light_direction: vec4<f32>;
+    light_colour:    vec4<f32>;
+    ambient_colour:  vec4<f32>;
+    camera_right:    vec4<f32>;
+    camera_up:       vec4<f32>;
+};
+
+[[block]]
+struct Style {
+    start_colour: vec4<f32>;
+    end_colour:   vec4<f32>;
+    start_size:   f32;
+    end_size:     f32;
+};
+
+[[group(0), binding(0)]] var<uniform> globals: Globals;
+[[group(1), binding(0)]] var<uniform> style: Style;
+
+struct VertexInput {
+    [[location(0)]] corner:            vec2<f32>;
+    // Particles are read straight from the simulation's storage buffer.
+    [[location(1)]] position_and_age:  vec4<f32>;
+    [[location(2)]] velocity_and_life: vec4<f32>;
+};
+
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]]       colour:   vec4<f32>;
+    [[location(1)]]       uv:       vec2<f32>;
+};
+
+[[stage(vertex)]]
+fn main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    let age: f32 = input.position_and_age.w;
+    let lifetime: f32 = input.velocity_and_life.w;
+    let life: f32 = clamp(age / max(lifetime, 0.0001), 0.0, 1.0);
+
+    // Dead particles collapse to a point and produce no fragments.
+    var size: f32 = mix(style.start_size, style.end_size, life);
+    if (age >= lifetime) {
+        size = 0.0;
+    }
+
+    let offset: vec2<f32> = (input.corner - vec2<f32>(0.5)) * size;
+    let position: vec3<f32> = input.position_and_age.xyz
+        + globals.camera_right.xyz * offset.x
+        + globals.camera_up.xyz * offset.y;
+
+    output.position = globals.view_projection * vec4<f32>(position, 1.0);
+    output.colour = mix(style.start_colour, style.end_colour, vec4<f32>(life));
+    output.uv = input.corner;
+
+    return output;
+}
+
+[[stage(fragment)]]
+fn main(input: VertexOutput) -> [[location(0)]] vec4<f32> {
+    // Fade the quad into a soft disc.
+    let radius: f32 = length(input.uv * 2.0 - vec2<f32>(1.0));
+    let falloff: f32 = 1.0 - smoothStep(0.5, 1.0, radius);
+
+    return vec4<f32>(input.colour.rgb, input.colour.a * falloff);
+}
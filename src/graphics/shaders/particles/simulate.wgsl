@@ -0,0 +1,72 @@
+struct Particle {
+    position: vec3<f32>;
+    age:      f32;
+    velocity: vec3<f32>;
+    lifetime: f32;
+};
+
+[[block]]
+struct Particles {
+    particles: [[stride(32)]] array<Particle>;
+};
+
+[[block]]
+struct Simulation {
+    emitter_position:  vec3<f32>;
+    delta_time:        f32;
+    velocity:          vec3<f32>;
+    velocity_spread:   f32;
+    gravity:           vec3<f32>;
+    lifetime:          f32;
+    lifetime_variance: f32;
+    spawn_start:       u32;
+    spawn_count:       u32;
+    capacity:          u32;
+    seed:              u32;
+};
+
+[[group(0), binding(0)]] var<uniform> simulation: Simulation;
+[[group(0), binding(1)]] var<storage> particles: [[access(read_write)]] Particles;
+
+fn hash(value: u32) -> u32 {
+    var state: u32 = value * 747796405u + 2891336453u;
+    let word: u32 = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// Maps a hash to [0, 1].
+fn unit(value: u32) -> f32 {
+    return f32(value) / 4294967295.0;
+}
+
+[[stage(compute), workgroup_size(64)]]
+fn main([[builtin(global_invocation_id)]] global_id: vec3<u32>) {
+    let index: u32 = global_id.x;
+
+    if (index >= simulation.capacity) {
+        return;
+    }
+
+    var particle: Particle = particles.particles[index];
+    // Particles are spawned into a ring, overwriting the oldest slots first.
+    let offset: u32 = (index + simulation.capacity - simulation.spawn_start) % simulation.capacity;
+
+    if (offset < simulation.spawn_count) {
+        let x: u32 = hash(index ^ hash(simulation.seed));
+        let y: u32 = hash(x);
+        let z: u32 = hash(y);
+        let w: u32 = hash(z);
+        let spread: vec3<f32> = vec3<f32>(unit(x), unit(y), unit(z)) * 2.0 - vec3<f32>(1.0);
+
+        particle.position = simulation.emitter_position;
+        particle.velocity = simulation.velocity + spread * simulation.velocity_spread;
+        particle.age = 0.0;
+        particle.lifetime = simulation.lifetime * (1.0 - unit(w) * simulation.lifetime_variance);
+    } elseif (particle.age < particle.lifetime) {
+        particle.velocity = particle.velocity + simulation.gravity * simulation.delta_time;
+        particle.position = particle.position + particle.velocity * simulation.delta_time;
+        particle.age = particle.age + simulation.delta_time;
+    }
+
+    particles.particles[index] = particle;
+}
@@ -0,0 +1,169 @@
+[[block]]
+struct Globals {
+    view_projection: mat4x4<f32>;
+    camera_position: vec4<f32>;
+    light_direction: vec4<f32>;
+    light_colour:    vec4<f32>;
+    ambient_colour:  vec4<f32>;
+    camera_right:    vec4<f32>;
+    camera_up:       vec4<f32>;
+    // Maps clip space to world space directions, ignoring the camera's position.
+    inverse_sky_view_projection: mat4x4<f32>;
+    // Intensity of the environment's lighting and the index of its smallest mip.
+    environment:     vec4<f32>;
+};
+
+[[block]]
+struct Material {
+    base_colour:  vec4<f32>;
+    emissive:     vec4<f32>;
+    metallic:     f32;
+    roughness:    f32;
+    normal_scale: f32;
+    alpha_cutoff: f32;
+};
+
+[[group(0), binding(0)]] var<uniform> globals: Globals;
+[[group(0), binding(1)]] var environment_texture: texture_cube<f32>;
+[[group(0), binding(2)]] var environment_sampler: sampler;
+
+[[group(1), binding(0)]] var<uniform> material: Material;
+[[group(1), binding(1)]] var base_colour_texture:        texture_2d<f32>;
+[[group(1), binding(2)]] var base_colour_sampler:        sampler;
+[[group(1), binding(3)]] var metallic_roughness_texture: texture_2d<f32>;
+[[group(1), binding(4)]] var metallic_roughness_sampler: sampler;
+[[group(1), binding(5)]] var normal_texture:             texture_2d<f32>;
+[[group(1), binding(6)]] var normal_sampler:             sampler;
+[[group(1), binding(7)]] var emissive_texture:           texture_2d<f32>;
+[[group(1), binding(8)]] var emissive_sampler:           sampler;
+
+struct VertexInput {
+    [[location(0)]]  position: vec3<f32>;
+    [[location(1)]]  normal:   vec3<f32>;
+    [[location(2)]]  tangent:  vec4<f32>;
+    [[location(3)]]  uv:       vec2<f32>;
+    [[location(4)]]  model_0:  vec4<f32>;
+    [[location(5)]]  model_1:  vec4<f32>;
+    [[location(6)]]  model_2:  vec4<f32>;
+    [[location(7)]]  model_3:  vec4<f32>;
+    [[location(8)]]  normal_0: vec3<f32>;
+    [[location(9)]]  normal_1: vec3<f32>;
+    [[location(10)]] normal_2: vec3<f32>;
+};
+
+struct VertexOutput {
+    [[builtin(position)]] position:       vec4<f32>;
+    [[location(0)]]      world_position: vec3<f32>;
+    [[location(1)]]      normal:         vec3<f32>;
+    [[location(2)]]      tangent:        vec4<f32>;
+    [[location(3)]]      uv:             vec2<f32>;
+};
+
+[[stage(vertex)]]
+fn main(input: VertexInput) -> VertexOutput {
+    let model: mat4x4<f32> = mat4x4<f32>(input.model_0, input.model_1, input.model_2, input.model_3);
+    let normal_matrix: mat3x3<f32> = mat3x3<f32>(input.normal_0, input.normal_1, input.normal_2);
+    let world_position: vec4<f32> = model * vec4<f32>(input.position, 1.0);
+
+    var output: VertexOutput;
+
+    output.position       = globals.view_projection * world_position;
+    output.world_position = world_position.xyz;
+    // NOTE:
+    // Normals need the inverse transpose to stay perpendicular to the surface under non-uniform
+    // scales, whereas tangents lie along the surface so are transformed like any direction.
+    output.normal         = normal_matrix * input.normal;
+    output.tangent        = vec4<f32>((model * vec4<f32>(input.tangent.xyz, 0.0)).xyz, input.tangent.w);
+    output.uv             = input.uv;
+
+    return output;
+}
+
+fn distribution_ggx(n_dot_h: f32, roughness: f32) -> f32 {
+    let a: f32 = roughness * roughness;
+    let a2: f32 = a * a;
+    let d: f32 = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+
+    return a2 / (3.14159265359 * d * d);
+}
+
+fn geometry_schlick_ggx(n_dot_x: f32, roughness: f32) -> f32 {
+    let r: f32 = roughness + 1.0;
+    let k: f32 = r * r / 8.0;
+
+    return n_dot_x / (n_dot_x * (1.0 - k) + k);
+}
+
+fn fresnel_schlick(cos_theta: f32, f0: vec3<f32>) -> vec3<f32> {
+    return f0 + (vec3<f32>(1.0, 1.0, 1.0) - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+// Rough surfaces reflect less of the environment at grazing angles.
+fn fresnel_schlick_roughness(cos_theta: f32, f0: vec3<f32>, roughness: f32) -> vec3<f32> {
+    let grazing: vec3<f32> = max(vec3<f32>(1.0 - roughness, 1.0 - roughness, 1.0 - roughness), f0);
+
+    return f0 + (grazing - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+[[stage(fragment)]]
+fn main(input: VertexOutput) -> [[location(0)]] vec4<f32> {
+    let base_colour: vec4<f32> = material.base_colour * textureSample(base_colour_texture, base_colour_sampler, input.uv);
+
+    // Metallic and roughness are packed into the blue and green channels, as in glTF.
+    let metallic_roughness: vec4<f32> = textureSample(metallic_roughness_texture, metallic_roughness_sampler, input.uv);
+    let normal_sample: vec4<f32> = textureSample(normal_texture, normal_sampler, input.uv);
+    let emissive_sample: vec4<f32> = textureSample(emissive_texture, emissive_sampler, input.uv);
+
+    // NOTE:
+    // Every texture has to be sampled before discarding since implicit derivatives are only valid
+    // in uniform control flow.
+    if (base_colour.a < material.alpha_cutoff) {
+        discard;
+    }
+
+    let metallic: f32 = clamp(material.metallic * metallic_roughness.b, 0.0, 1.0);
+    let roughness: f32 = clamp(material.roughness * metallic_roughness.g, 0.04, 1.0);
+
+    let tangent_normal: vec3<f32> = normal_sample.xyz * 2.0 - vec3<f32>(1.0, 1.0, 1.0);
+    let scaled_normal: vec3<f32> = vec3<f32>(tangent_normal.xy * material.normal_scale, tangent_normal.z);
+
+    let geometry_normal: vec3<f32> = normalize(input.normal);
+    let t: vec3<f32> = normalize(input.tangent.xyz - geometry_normal * dot(geometry_normal, input.tangent.xyz));
+    // Mirrored uvs flip the bitangent, which the tangent's w carries.
+    let b: vec3<f32> = cross(geometry_normal, t) * input.tangent.w;
+    let n: vec3<f32> = normalize(mat3x3<f32>(t, b, geometry_normal) * scaled_normal);
+
+    let v: vec3<f32> = normalize(globals.camera_position.xyz - input.world_position);
+    let l: vec3<f32> = normalize(-globals.light_direction.xyz);
+    let h: vec3<f32> = normalize(v + l);
+
+    let n_dot_v: f32 = max(dot(n, v), 0.0001);
+    let n_dot_l: f32 = max(dot(n, l), 0.0);
+    let n_dot_h: f32 = max(dot(n, h), 0.0);
+    let h_dot_v: f32 = max(dot(h, v), 0.0);
+
+    let f0: vec3<f32> = mix(vec3<f32>(0.04, 0.04, 0.04), base_colour.rgb, vec3<f32>(metallic, metallic, metallic));
+    let f: vec3<f32> = fresnel_schlick(h_dot_v, f0);
+    let d: f32 = distribution_ggx(n_dot_h, roughness);
+    let g: f32 = geometry_schlick_ggx(n_dot_v, roughness) * geometry_schlick_ggx(n_dot_l, roughness);
+
+    let specular: vec3<f32> = f * (d * g / (4.0 * n_dot_v * max(n_dot_l, 0.0001)));
+    let diffuse: vec3<f32> = (vec3<f32>(1.0, 1.0, 1.0) - f) * (1.0 - metallic) * base_colour.rgb / 3.14159265359;
+
+    let radiance: vec3<f32> = globals.light_colour.rgb * globals.light_colour.a;
+    let ambient: vec3<f32> = globals.ambient_colour.rgb * base_colour.rgb;
+    let emissive: vec3<f32> = material.emissive.rgb * emissive_sample.rgb;
+
+    // Image based lighting. The environment's smallest mip stands in for irradiance and rougher
+    // surfaces reflect blurrier mips. Explicit levels are used since this is past the discard.
+    let max_mip: f32 = globals.environment.y;
+    let f_environment: vec3<f32> = fresnel_schlick_roughness(n_dot_v, f0, roughness);
+    let irradiance: vec3<f32> = textureSampleLevel(environment_texture, environment_sampler, n, max_mip).rgb;
+    let reflection: vec3<f32> = textureSampleLevel(environment_texture, environment_sampler, reflect(-v, n), roughness * max_mip).rgb;
+    let environment_diffuse: vec3<f32> = (vec3<f32>(1.0, 1.0, 1.0) - f_environment) * (1.0 - metallic) * irradiance * base_colour.rgb;
+    let environment: vec3<f32> = (environment_diffuse + reflection * f_environment) * globals.environment.x;
+
+    let colour: vec3<f32> = (diffuse + specular) * radiance * n_dot_l + ambient + environment + emissive;
+
+    return vec4<f32>(colour, base_colour.a);
+}
@@ -0,0 +1,4 @@
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    return textureSample(source_texture, source_sampler, input.uv);
+}
@@ -0,0 +1,26 @@
+[[block]]
+struct Blur {
+    direction: vec2<f32>;
+};
+
+[[group(1), binding(0)]] var<uniform> blur: Blur;
+
+fn tap(uv: vec2<f32>, offset: vec2<f32>) -> vec3<f32> {
+    return textureSampleLevel(source_texture, source_sampler, uv + offset, 0.0).rgb
+         + textureSampleLevel(source_texture, source_sampler, uv - offset, 0.0).rgb;
+}
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let dimensions: vec2<i32> = textureDimensions(source_texture);
+    let stride: vec2<f32> = blur.direction / vec2<f32>(f32(dimensions.x), f32(dimensions.y));
+
+    var colour: vec3<f32> = textureSampleLevel(source_texture, source_sampler, input.uv, 0.0).rgb * 0.227027;
+
+    colour = colour + tap(input.uv, stride * 1.0) * 0.1945946;
+    colour = colour + tap(input.uv, stride * 2.0) * 0.1216216;
+    colour = colour + tap(input.uv, stride * 3.0) * 0.054054;
+    colour = colour + tap(input.uv, stride * 4.0) * 0.016216;
+
+    return vec4<f32>(colour, 1.0);
+}
@@ -0,0 +1,18 @@
+[[block]]
+struct Bloom {
+    threshold: f32;
+    knee:      f32;
+    intensity: f32;
+};
+
+[[group(1), binding(0)]] var<uniform> bloom:         Bloom;
+[[group(1), binding(1)]] var          bloom_texture: texture_2d<f32>;
+[[group(1), binding(2)]] var          bloom_sampler: sampler;
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let colour: vec4<f32> = textureSample(source_texture, source_sampler, input.uv);
+    let blurred: vec3<f32> = textureSample(bloom_texture, bloom_sampler, input.uv).rgb;
+
+    return vec4<f32>(colour.rgb + blurred * bloom.intensity, colour.a);
+}
@@ -0,0 +1,21 @@
+[[block]]
+struct Bloom {
+    threshold: f32;
+    knee:      f32;
+    intensity: f32;
+};
+
+[[group(1), binding(0)]] var<uniform> bloom: Bloom;
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let colour: vec3<f32> = textureSample(source_texture, source_sampler, input.uv).rgb;
+    let brightness: f32 = max(colour.r, max(colour.g, colour.b));
+
+    // Quadratic soft knee around the threshold so highlights fade in rather than pop.
+    let knee: f32 = max(bloom.knee, 0.00001);
+    let soft: f32 = clamp(brightness - bloom.threshold + knee, 0.0, 2.0 * knee);
+    let contribution: f32 = max(soft * soft / (4.0 * knee), brightness - bloom.threshold) / max(brightness, 0.00001);
+
+    return vec4<f32>(colour * contribution, 1.0);
+}
@@ -0,0 +1,35 @@
+[[block]]
+struct ColourGrading {
+    lut_size:     f32;
+    contribution: f32;
+};
+
+[[group(1), binding(0)]] var<uniform> grading:     ColourGrading;
+[[group(1), binding(1)]] var          lut_texture: texture_2d<f32>;
+[[group(1), binding(2)]] var          lut_sampler: sampler;
+
+// The LUT is a horizontal strip of `lut_size` slices, each `lut_size` texels square, with blue
+// selecting the slice.
+fn sample_lut(colour: vec3<f32>) -> vec3<f32> {
+    let size: f32 = grading.lut_size;
+    let scaled: vec3<f32> = clamp(colour, vec3<f32>(0.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 1.0)) * (size - 1.0);
+    let slice: f32 = floor(scaled.b);
+    let blend: f32 = scaled.b - slice;
+
+    let texel: vec2<f32> = vec2<f32>(1.0 / (size * size), 1.0 / size);
+    let uv: vec2<f32> = (scaled.rg + vec2<f32>(0.5, 0.5)) * texel;
+
+    let lower: vec3<f32> = textureSampleLevel(lut_texture, lut_sampler, uv + vec2<f32>(slice * size * texel.x, 0.0), 0.0).rgb;
+    let upper: vec3<f32> = textureSampleLevel(lut_texture, lut_sampler, uv + vec2<f32>(min(slice + 1.0, size - 1.0) * size * texel.x, 0.0), 0.0).rgb;
+
+    return mix(lower, upper, vec3<f32>(blend, blend, blend));
+}
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let colour: vec4<f32> = textureSample(source_texture, source_sampler, input.uv);
+    let graded: vec3<f32> = sample_lut(colour.rgb);
+    let amount: f32 = grading.contribution;
+
+    return vec4<f32>(mix(colour.rgb, graded, vec3<f32>(amount, amount, amount)), colour.a);
+}
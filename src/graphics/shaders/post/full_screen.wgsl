@@ -0,0 +1,23 @@
+// Shared by every post effect, the effect's own source is appended to this.
+
+struct FullScreenOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]]       uv:       vec2<f32>;
+};
+
+[[group(0), binding(0)]] var source_texture: texture_2d<f32>;
+[[group(0), binding(1)]] var source_sampler: sampler;
+
+[[stage(vertex)]]
+fn main([[builtin(vertex_index)]] vertex_index: u32) -> FullScreenOutput {
+    // A single triangle large enough to cover the whole screen.
+    let x: f32 = f32((vertex_index << 1u) & 2u);
+    let y: f32 = f32(vertex_index & 2u);
+
+    var output: FullScreenOutput;
+
+    output.position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    output.uv       = vec2<f32>(x, y);
+
+    return output;
+}
@@ -0,0 +1,57 @@
+// Based on the FXAA 3.11 console algorithm by Timothy Lottes. Expects tonemapped input.
+
+fn luma(colour: vec3<f32>) -> f32 {
+    return dot(colour, vec3<f32>(0.299, 0.587, 0.114));
+}
+
+fn sample_offset(uv: vec2<f32>, offset: vec2<f32>) -> vec3<f32> {
+    return textureSampleLevel(source_texture, source_sampler, uv + offset, 0.0).rgb;
+}
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let span_max: f32 = 8.0;
+    let reduce_multiplier: f32 = 1.0 / 8.0;
+    let reduce_minimum: f32 = 1.0 / 128.0;
+
+    let dimensions: vec2<i32> = textureDimensions(source_texture);
+    let texel: vec2<f32> = vec2<f32>(1.0 / f32(dimensions.x), 1.0 / f32(dimensions.y));
+
+    let centre: vec4<f32> = textureSampleLevel(source_texture, source_sampler, input.uv, 0.0);
+
+    let luma_nw: f32 = luma(sample_offset(input.uv, vec2<f32>(-texel.x, -texel.y)));
+    let luma_ne: f32 = luma(sample_offset(input.uv, vec2<f32>( texel.x, -texel.y)));
+    let luma_sw: f32 = luma(sample_offset(input.uv, vec2<f32>(-texel.x,  texel.y)));
+    let luma_se: f32 = luma(sample_offset(input.uv, vec2<f32>( texel.x,  texel.y)));
+    let luma_m: f32 = luma(centre.rgb);
+
+    let luma_min: f32 = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    let luma_max: f32 = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    let edge: vec2<f32> = vec2<f32>(
+        -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+         ((luma_nw + luma_sw) - (luma_ne + luma_se)),
+    );
+
+    let reduce: f32 = max((luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * reduce_multiplier, reduce_minimum);
+    let scale: f32 = 1.0 / (min(abs(edge.x), abs(edge.y)) + reduce);
+    let direction: vec2<f32> = clamp(edge * scale, vec2<f32>(-span_max, -span_max), vec2<f32>(span_max, span_max)) * texel;
+
+    let colour_a: vec3<f32> = 0.5 * (
+        sample_offset(input.uv, direction * (1.0 / 3.0 - 0.5)) +
+        sample_offset(input.uv, direction * (2.0 / 3.0 - 0.5))
+    );
+
+    let colour_b: vec3<f32> = colour_a * 0.5 + 0.25 * (
+        sample_offset(input.uv, direction * -0.5) +
+        sample_offset(input.uv, direction *  0.5)
+    );
+
+    let luma_b: f32 = luma(colour_b);
+
+    if (luma_b < luma_min || luma_b > luma_max) {
+        return vec4<f32>(colour_a, centre.a);
+    }
+
+    return vec4<f32>(colour_b, centre.a);
+}
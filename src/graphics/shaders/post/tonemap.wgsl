@@ -0,0 +1,38 @@
+[[block]]
+struct Tonemap {
+    exposure: f32;
+    operator: u32;
+};
+
+[[group(1), binding(0)]] var<uniform> tonemap: Tonemap;
+
+fn reinhard(colour: vec3<f32>) -> vec3<f32> {
+    return colour / (colour + vec3<f32>(1.0, 1.0, 1.0));
+}
+
+// Krzysztof Narkowicz's fit of the ACES filmic curve.
+fn aces(colour: vec3<f32>) -> vec3<f32> {
+    let a: f32 = 2.51;
+    let b: f32 = 0.03;
+    let c: f32 = 2.43;
+    let d: f32 = 0.59;
+    let e: f32 = 0.14;
+
+    return clamp((colour * (a * colour + b)) / (colour * (c * colour + d) + e), vec3<f32>(0.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 1.0));
+}
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let hdr: vec4<f32> = textureSample(source_texture, source_sampler, input.uv);
+    let exposed: vec3<f32> = hdr.rgb * tonemap.exposure;
+
+    var mapped: vec3<f32>;
+
+    if (tonemap.operator == 0u) {
+        mapped = aces(exposed);
+    } else {
+        mapped = reinhard(exposed);
+    }
+
+    return vec4<f32>(mapped, hdr.a);
+}
@@ -0,0 +1,17 @@
+[[block]]
+struct Vignette {
+    colour:     vec4<f32>;
+    intensity:  f32;
+    smoothness: f32;
+};
+
+[[group(1), binding(0)]] var<uniform> vignette: Vignette;
+
+[[stage(fragment)]]
+fn main(input: FullScreenOutput) -> [[location(0)]] vec4<f32> {
+    let colour: vec4<f32> = textureSample(source_texture, source_sampler, input.uv);
+    let radius: f32 = length(input.uv - vec2<f32>(0.5, 0.5)) * 1.41421356;
+    let amount: f32 = smoothStep(1.0 - vignette.smoothness, 1.0, radius) * vignette.intensity;
+
+    return vec4<f32>(mix(colour.rgb, vignette.colour.rgb, vec3<f32>(amount, amount, amount)), colour.a);
+}
@@ -0,0 +1,43 @@
+[[block]]
+struct Globals {
+    view_projection: mat4x4<f32>;
+    camera_position: vec4<f32>;
+    light_direction: vec4<f32>;
+    light_colour:    vec4<f32>;
+    ambient_colour:  vec4<f32>;
+    camera_right:    vec4<f32>;
+    camera_up:       vec4<f32>;
+    inverse_sky_view_projection: mat4x4<f32>;
+    environment:     vec4<f32>;
+};
+
+[[group(0), binding(0)]] var<uniform> globals: Globals;
+[[group(0), binding(1)]] var environment_texture: texture_cube<f32>;
+[[group(0), binding(2)]] var environment_sampler: sampler;
+
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]]       clip:     vec2<f32>;
+};
+
+[[stage(vertex)]]
+fn main([[builtin(vertex_index)]] vertex_index: u32) -> VertexOutput {
+    // A single triangle large enough to cover the whole screen.
+    let x: f32 = f32((vertex_index << 1u) & 2u) * 2.0 - 1.0;
+    let y: f32 = 1.0 - f32(vertex_index & 2u) * 2.0;
+
+    var output: VertexOutput;
+
+    output.position = vec4<f32>(x, y, 1.0, 1.0);
+    output.clip     = vec2<f32>(x, y);
+
+    return output;
+}
+
+[[stage(fragment)]]
+fn main(input: VertexOutput) -> [[location(0)]] vec4<f32> {
+    let far: vec4<f32> = globals.inverse_sky_view_projection * vec4<f32>(input.clip, 1.0, 1.0);
+    let direction: vec3<f32> = normalize(far.xyz / far.w);
+
+    return vec4<f32>(textureSample(environment_texture, environment_sampler, direction).rgb, 1.0);
+}
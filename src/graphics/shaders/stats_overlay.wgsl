@@ -0,0 +1,67 @@
+[[block]]
+struct Overlay {
+    // Bottom left corner and size of the graph in clip space.
+    origin:    vec2<f32>;
+    size:      vec2<f32>;
+    // Frame time in milliseconds of a full height bar.
+    max_time:  f32;
+    // Frame time in milliseconds that a frame should fit in, e.g. 16.6 for 60 FPS.
+    budget:    f32;
+    bar_count: f32;
+    _padding:  f32;
+};
+
+[[group(0), binding(0)]]
+var<uniform> overlay: Overlay;
+
+struct VertexInput {
+    [[location(0)]] corner:     vec2<f32>;
+    // Negative for the background quad drawn behind the bars.
+    [[location(1)]] frame_time: f32;
+};
+
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]]       colour:   vec4<f32>;
+};
+
+[[stage(vertex)]]
+fn main(
+    input: VertexInput,
+    [[builtin(instance_index)]] instance_index: u32,
+) -> VertexOutput {
+    var output: VertexOutput;
+
+    if (input.frame_time < 0.0) {
+        output.position = vec4<f32>(overlay.origin + input.corner * overlay.size, 0.0, 1.0);
+        output.colour   = vec4<f32>(0.0, 0.0, 0.0, 0.6);
+
+        return output;
+    }
+
+    let bar_width: f32 = overlay.size.x / overlay.bar_count;
+    let height: f32 = min(input.frame_time / overlay.max_time, 1.0) * overlay.size.y;
+    let left: f32 = overlay.origin.x + f32(instance_index - 1u) * bar_width;
+
+    output.position = vec4<f32>(
+        left + input.corner.x * bar_width * 0.8,
+        overlay.origin.y + input.corner.y * height,
+        0.0,
+        1.0,
+    );
+
+    if (input.frame_time <= overlay.budget) {
+        output.colour = vec4<f32>(0.2, 0.8, 0.2, 1.0);
+    } elseif (input.frame_time <= overlay.budget * 2.0) {
+        output.colour = vec4<f32>(0.9, 0.8, 0.1, 1.0);
+    } else {
+        output.colour = vec4<f32>(0.9, 0.2, 0.1, 1.0);
+    }
+
+    return output;
+}
+
+[[stage(fragment)]]
+fn main(input: VertexOutput) -> [[location(0)]] vec4<f32> {
+    return input.colour;
+}
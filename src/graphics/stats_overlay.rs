@@ -0,0 +1,253 @@
+use std::{collections::VecDeque, mem, time::Duration};
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroup,
+    BindGroupDescriptor,
+    BindGroupEntry,
+    BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry,
+    BindingType,
+    BlendState,
+    Buffer as WgpuBuffer,
+    BufferAddress,
+    BufferBindingType,
+    BufferDescriptor,
+    BufferUsage,
+    ColorTargetState as ColourTargetState,
+    ColorWrite as ColourWrite,
+    CommandEncoder,
+    Device,
+    FragmentState,
+    InputStepMode,
+    LoadOp,
+    MultisampleState,
+    Operations,
+    PipelineLayoutDescriptor,
+    PrimitiveState,
+    Queue,
+    RenderPassColorAttachment as RenderPassColourAttachment,
+    RenderPassDescriptor,
+    RenderPipeline,
+    RenderPipelineDescriptor,
+    ShaderFlags,
+    ShaderModuleDescriptor,
+    ShaderSource,
+    ShaderStage,
+    TextureFormat,
+    TextureView,
+    VertexAttribute,
+    VertexBufferLayout,
+    VertexFormat,
+    VertexState,
+};
+
+// The number of frames shown, one bar each.
+const HISTORY: usize = 120;
+
+// Size and distance from the bottom left corner of the window, in pixels.
+const WIDTH: f32 = 240.0;
+const HEIGHT: f32 = 80.0;
+const MARGIN: f32 = 8.0;
+
+// In milliseconds, bars are green within the budget, yellow within twice it and red beyond.
+const BUDGET: f32 = 1000.0 / 60.0;
+const MAX_TIME: f32 = BUDGET * 3.0;
+
+const CORNERS: [[f32; 2]; 6] = [
+    [0.0, 0.0],
+    [1.0, 0.0],
+    [1.0, 1.0],
+    [0.0, 0.0],
+    [1.0, 1.0],
+    [0.0, 1.0],
+];
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct OverlayUniform {
+    origin:    [f32; 2],
+    size:      [f32; 2],
+    max_time:  f32,
+    budget:    f32,
+    bar_count: f32,
+    _padding:  f32,
+}
+
+/// A bar graph of recent frame times drawn over the bottom left corner of the window.
+pub(in crate::graphics) struct StatsOverlay {
+    pipeline:    RenderPipeline,
+    uniform:     WgpuBuffer,
+    bind_group:  BindGroup,
+    corners:     WgpuBuffer,
+    frame_times: WgpuBuffer,
+    history:     VecDeque<f32>,
+}
+
+impl StatsOverlay {
+    pub(in crate::graphics) fn new(device: &Device, format: TextureFormat) -> Self {
+        let uniform = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some("stats overlay"),
+            contents: bytemuck::bytes_of(&OverlayUniform::zeroed()),
+            usage:    BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+        });
+
+        let corners = device.create_buffer_init(&BufferInitDescriptor {
+            label:    Some("stats overlay corners"),
+            contents: bytemuck::cast_slice(&CORNERS),
+            usage:    BufferUsage::VERTEX,
+        });
+
+        // The background quad followed by a bar for each frame.
+        let frame_times = device.create_buffer(&BufferDescriptor {
+            label:              Some("stats overlay frame times"),
+            size:               ((HISTORY + 1) * mem::size_of::<f32>()) as BufferAddress,
+            usage:              BufferUsage::VERTEX | BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label:   Some("stats overlay"),
+            entries: &[BindGroupLayoutEntry {
+                binding:    0,
+                visibility: ShaderStage::VERTEX,
+                ty:         BindingType::Buffer {
+                    ty:                 BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size:   None,
+                },
+                count:      None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label:   Some("stats overlay"),
+            layout:  &bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding:  0,
+                resource: uniform.as_entire_binding(),
+            }],
+        });
+
+        let shader_module = device.create_shader_module(&ShaderModuleDescriptor {
+            label:  Some("stats overlay"),
+            flags:  ShaderFlags::all(),
+            source: ShaderSource::Wgsl(include_str!("./shaders/stats_overlay.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label:                Some("stats overlay"),
+            bind_group_layouts:   &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let vertex_buffers = [
+            VertexBufferLayout {
+                array_stride: mem::size_of::<[f32; 2]>() as BufferAddress,
+                step_mode:    InputStepMode::Vertex,
+                attributes:   &[VertexAttribute {
+                    offset:          0,
+                    shader_location: 0,
+                    format:          VertexFormat::Float32x2,
+                }],
+            },
+            VertexBufferLayout {
+                array_stride: mem::size_of::<f32>() as BufferAddress,
+                step_mode:    InputStepMode::Instance,
+                attributes:   &[VertexAttribute {
+                    offset:          0,
+                    shader_location: 1,
+                    format:          VertexFormat::Float32,
+                }],
+            },
+        ];
+
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label:         Some("stats overlay"),
+            layout:        Some(&pipeline_layout),
+            vertex:        VertexState {
+                module:      &shader_module,
+                entry_point: "main",
+                buffers:     &vertex_buffers,
+            },
+            fragment:      Some(FragmentState {
+                module:      &shader_module,
+                entry_point: "main",
+                targets:     &[ColourTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColourWrite::ALL,
+                }],
+            }),
+            primitive:     PrimitiveState::default(),
+            depth_stencil: None,
+            multisample:   MultisampleState::default(),
+        });
+
+        Self {
+            pipeline,
+            uniform,
+            bind_group,
+            corners,
+            frame_times,
+            history: VecDeque::with_capacity(HISTORY),
+        }
+    }
+
+    pub(in crate::graphics) fn push(&mut self, frame_time: Duration) {
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(frame_time.as_secs_f32() * 1000.0);
+    }
+
+    /// Draws the graph over whatever is already in `target`, which is `width` by `height` pixels.
+    pub(in crate::graphics) fn record(
+        &self,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        target: &TextureView,
+        width: u32,
+        height: u32,
+    ) {
+        let width = width.max(1) as f32;
+        let height = height.max(1) as f32;
+
+        let uniform = OverlayUniform {
+            origin:    [-1.0 + 2.0 * MARGIN / width, -1.0 + 2.0 * MARGIN / height],
+            size:      [2.0 * WIDTH / width, 2.0 * HEIGHT / height],
+            max_time:  MAX_TIME,
+            budget:    BUDGET,
+            bar_count: HISTORY as f32,
+            _padding:  0.0,
+        };
+
+        let frame_times = std::iter::once(-1.0)
+            .chain(self.history.iter().copied())
+            .collect::<Vec<f32>>();
+
+        queue.write_buffer(&self.uniform, 0, bytemuck::bytes_of(&uniform));
+        queue.write_buffer(&self.frame_times, 0, bytemuck::cast_slice(&frame_times));
+
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label:                    Some("stats overlay"),
+            color_attachments:        &[RenderPassColourAttachment {
+                view:           target,
+                resolve_target: None,
+                ops:            Operations {
+                    load:  LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.corners.slice(..));
+        render_pass.set_vertex_buffer(1, self.frame_times.slice(..));
+        render_pass.draw(0..CORNERS.len() as u32, 0..frame_times.len() as u32);
+    }
+}
@@ -0,0 +1,148 @@
+use std::num::NonZeroU32;
+
+use wgpu::{
+    AddressMode,
+    Device,
+    Extent3d,
+    FilterMode,
+    ImageCopyTexture,
+    ImageDataLayout,
+    Origin3d,
+    Queue,
+    Sampler,
+    SamplerDescriptor,
+    TextureDescriptor,
+    TextureDimension,
+    TextureFormat,
+    TextureUsage,
+    TextureView,
+    TextureViewDescriptor,
+};
+
+use crate::graphics::Renderer;
+
+/// How the texels of a texture should be interpreted when sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColourSpace {
+    /// Colour data authored by artists, e.g. base colour and emissive maps.
+    Srgb,
+    /// Non-colour data, e.g. normal and metallic/roughness maps.
+    Linear,
+}
+
+impl ColourSpace {
+    pub(in crate::graphics) fn rgba8_format(self) -> TextureFormat {
+        match self {
+            ColourSpace::Srgb => TextureFormat::Rgba8UnormSrgb,
+            ColourSpace::Linear => TextureFormat::Rgba8Unorm,
+        }
+    }
+}
+
+// NOTE:
+// The texture itself isn't kept, its view holds on to it for as long as the view is alive.
+pub struct Texture {
+    pub(in crate::graphics) view:    TextureView,
+    pub(in crate::graphics) sampler: Sampler,
+    pub(in crate::graphics) width:   u32,
+    pub(in crate::graphics) height:  u32,
+}
+
+impl Texture {
+    /// Creates a texture from tightly packed RGBA8 texels.
+    pub fn from_rgba8(
+        renderer: &Renderer,
+        width: u32,
+        height: u32,
+        texels: &[u8],
+        colour_space: ColourSpace,
+    ) -> Self {
+        Self::from_rgba8_with_device(
+            &renderer.device,
+            &renderer.queue,
+            width,
+            height,
+            texels,
+            colour_space,
+        )
+    }
+
+    pub(in crate::graphics) fn from_rgba8_with_device(
+        device: &Device,
+        queue: &Queue,
+        width: u32,
+        height: u32,
+        texels: &[u8],
+        colour_space: ColourSpace,
+    ) -> Self {
+        assert_eq!(
+            texels.len(),
+            (width * height * 4) as usize,
+            "Texel data does not match a {}x{} RGBA8 texture",
+            width,
+            height
+        );
+
+        let size = Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: colour_space.rgba8_format(),
+            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+        });
+
+        queue.write_texture(
+            ImageCopyTexture {
+                texture:   &texture,
+                mip_level: 0,
+                origin:    Origin3d::ZERO,
+            },
+            texels,
+            ImageDataLayout {
+                offset:         0,
+                bytes_per_row:  NonZeroU32::new(4 * width),
+                rows_per_image: NonZeroU32::new(height),
+            },
+            size,
+        );
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            address_mode_w: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self {
+            view,
+            sampler,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a 1x1 texture of a single colour, useful as a stand in for missing maps.
+    pub fn solid(renderer: &Renderer, texel: [u8; 4], colour_space: ColourSpace) -> Self {
+        Self::from_rgba8(renderer, 1, 1, &texel, colour_space)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
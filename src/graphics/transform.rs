@@ -1,5 +1,6 @@
 use crate::core::maths::{Mat4, Quat, Scale3, Vec3};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     pub translation: Vec3,
     pub rotation:    Quat,
@@ -14,7 +15,7 @@ impl Transform {
             scale,
         } = self;
 
-        Mat4::translation(translation) * Mat4::scale(scale) * Mat4::rotation(rotation)
+        Mat4::translation(translation) * Mat4::rotation(rotation) * Mat4::scale(scale)
     }
 }
 
@@ -27,3 +28,29 @@ impl Default for Transform {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::core::maths::Angle;
+
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let transform = Transform {
+            translation: Vec3::new(1.0, -2.0, 3.5),
+            rotation:    Quat::from_axis_angle(
+                &Vec3::unit_y(),
+                Angle::degrees(30.0),
+            ),
+            scale:       Scale3::new(2.0, 1.0, 0.5),
+        };
+
+        let json = serde_json::to_string(&transform).unwrap();
+        let loaded = serde_json::from_str::<Transform>(&json).unwrap();
+
+        assert_eq!(loaded.translation, transform.translation);
+        assert_eq!(loaded.rotation, transform.rotation);
+        assert_eq!(loaded.scale, transform.scale);
+    }
+}
@@ -3,7 +3,7 @@ use std::mem;
 use bytemuck::{Pod, Zeroable};
 use wgpu::{BufferAddress, InputStepMode, VertexAttribute, VertexBufferLayout, VertexFormat};
 
-use crate::core::maths::Vec3;
+use crate::core::maths::{Vec2, Vec3, Vec4};
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
@@ -37,3 +37,49 @@ impl Vertex {
         }
     }
 }
+
+/// A vertex carrying everything the physically based shader needs.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct PbrVertex {
+    pub position: Vec3,
+    pub normal:   Vec3,
+    /// The direction of increasing u, with the handedness of the bitangent in `w` as in glTF,
+    /// either 1 or -1 where the uvs are mirrored.
+    pub tangent:  Vec4,
+    pub uv:       Vec2,
+}
+
+impl PbrVertex {
+    pub fn buffer_descriptor() -> VertexBufferLayout<'static> {
+        const VEC3: BufferAddress = mem::size_of::<Vec3>() as BufferAddress;
+        const VEC4: BufferAddress = mem::size_of::<Vec4>() as BufferAddress;
+
+        VertexBufferLayout {
+            array_stride: mem::size_of::<PbrVertex>() as BufferAddress,
+            step_mode:    InputStepMode::Vertex,
+            attributes:   &[
+                VertexAttribute {
+                    offset:          0,
+                    shader_location: 0,
+                    format:          VertexFormat::Float32x3,
+                },
+                VertexAttribute {
+                    offset:          VEC3,
+                    shader_location: 1,
+                    format:          VertexFormat::Float32x3,
+                },
+                VertexAttribute {
+                    offset:          VEC3 * 2,
+                    shader_location: 2,
+                    format:          VertexFormat::Float32x4,
+                },
+                VertexAttribute {
+                    offset:          VEC3 * 2 + VEC4,
+                    shader_location: 3,
+                    format:          VertexFormat::Float32x2,
+                },
+            ],
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::{
+    ptr,
+    task::{RawWaker, RawWakerVTable, Waker},
+};
+
+// NOTE:
+// wgpu's native futures complete while the device is polled rather than waking their task, so
+// there's never anything to be woken and a waker that does nothing is enough to check them.
+pub(in crate::graphics) fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    fn noop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
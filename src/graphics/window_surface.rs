@@ -0,0 +1,150 @@
+use std::time::Instant;
+
+use wgpu::{
+    Device,
+    PresentMode,
+    Surface,
+    SwapChain,
+    SwapChainDescriptor,
+    TextureFormat,
+    TextureUsage,
+};
+use winit::dpi::PhysicalSize;
+
+use crate::graphics::{PostProcessing, RenderGraph, RenderTarget, DEPTH_FORMAT, HDR_FORMAT};
+
+/// Everything the renderer keeps for each window it presents to.
+pub(in crate::graphics) struct WindowSurface {
+    pub(in crate::graphics) size:                  PhysicalSize<u32>,
+    pub(in crate::graphics) surface:               Surface,
+    pub(in crate::graphics) swap_chain:            SwapChain,
+    pub(in crate::graphics) swap_chain_descriptor: SwapChainDescriptor,
+    pub(in crate::graphics) msaa_target:           Option<RenderTarget>,
+    pub(in crate::graphics) depth_target:          RenderTarget,
+    pub(in crate::graphics) render_graph:          RenderGraph,
+    pub(in crate::graphics) post_processing:       PostProcessing,
+    pub(in crate::graphics) last_frame_start:      Option<Instant>,
+}
+
+impl WindowSurface {
+    pub(in crate::graphics) fn new(
+        device: &Device,
+        surface: Surface,
+        swap_chain_format: TextureFormat,
+        size: PhysicalSize<u32>,
+        present_mode: PresentMode,
+        sample_count: u32,
+    ) -> Self {
+        let swap_chain_descriptor = SwapChainDescriptor {
+            usage: TextureUsage::RENDER_ATTACHMENT,
+            format: swap_chain_format,
+            width: size.width,
+            height: size.height,
+            present_mode,
+        };
+
+        Self {
+            size,
+            swap_chain: device.create_swap_chain(&surface, &swap_chain_descriptor),
+            surface,
+            swap_chain_descriptor,
+            msaa_target: create_msaa_target(device, size, sample_count),
+            depth_target: create_depth_target(device, size, sample_count),
+            render_graph: RenderGraph::new(size.width, size.height, sample_count),
+            post_processing: PostProcessing::new(
+                device,
+                swap_chain_format,
+                size.width,
+                size.height,
+            ),
+            last_frame_start: None,
+        }
+    }
+
+    pub(in crate::graphics) fn is_minimised(&self) -> bool {
+        self.size.width == 0 || self.size.height == 0
+    }
+
+    pub(in crate::graphics) fn swap_chain_format(&self) -> TextureFormat {
+        self.swap_chain_descriptor.format
+    }
+
+    pub(in crate::graphics) fn resize(
+        &mut self,
+        device: &Device,
+        new_size: PhysicalSize<u32>,
+        sample_count: u32,
+    ) {
+        self.size = new_size;
+
+        // NOTE:
+        // Minimised windows are resized to 0x0, which a swap chain can't be created with, so
+        // everything keeps its old size until the window is restored and resized again.
+        if self.is_minimised() {
+            return;
+        }
+
+        self.swap_chain_descriptor.width = new_size.width;
+        self.swap_chain_descriptor.height = new_size.height;
+        self.recreate_swap_chain(device);
+
+        self.msaa_target = create_msaa_target(device, new_size, sample_count);
+        self.depth_target = create_depth_target(device, new_size, sample_count);
+        self.render_graph.resize(new_size.width, new_size.height);
+        self.post_processing
+            .resize(device, new_size.width, new_size.height);
+    }
+
+    pub(in crate::graphics) fn set_present_mode(&mut self, device: &Device, mode: PresentMode) {
+        if self.swap_chain_descriptor.present_mode != mode {
+            self.swap_chain_descriptor.present_mode = mode;
+            self.recreate_swap_chain(device);
+        }
+    }
+
+    /// Recreates the swap chain, unless the window is minimised in which case it's recreated by
+    /// `resize` when the window is restored.
+    pub(in crate::graphics) fn recreate_swap_chain(&mut self, device: &Device) {
+        if self.is_minimised() {
+            return;
+        }
+
+        self.swap_chain = device.create_swap_chain(&self.surface, &self.swap_chain_descriptor);
+    }
+}
+
+fn create_msaa_target(
+    device: &Device,
+    size: PhysicalSize<u32>,
+    sample_count: u32,
+) -> Option<RenderTarget> {
+    if sample_count == 1 {
+        return None;
+    }
+
+    Some(RenderTarget::multisampled(
+        device,
+        "msaa",
+        size.width,
+        size.height,
+        HDR_FORMAT,
+        TextureUsage::RENDER_ATTACHMENT,
+        sample_count,
+    ))
+}
+
+fn create_depth_target(
+    device: &Device,
+    size: PhysicalSize<u32>,
+    sample_count: u32,
+) -> RenderTarget {
+    RenderTarget::multisampled(
+        device,
+        "depth",
+        size.width,
+        size.height,
+        DEPTH_FORMAT,
+        TextureUsage::RENDER_ATTACHMENT,
+        sample_count,
+    )
+}
@@ -6,5 +6,9 @@ pub mod http;
 pub mod keyboard;
 pub mod mouse;
 pub mod physics;
+pub mod state;
+pub mod timer;
+pub mod tween;
+pub mod window;
 
 pub use bumpalo::Bump;
@@ -2,4 +2,4 @@ mod aab2;
 mod aab3;
 mod ray2;
 
-pub use crate::collision::{aab2::*, aab3::*, ray2::*};
+pub use crate::physics::{aab2::*, aab3::*, ray2::*};
@@ -4,18 +4,18 @@ use crate::core::maths::{Point2, Scale2, Vec2};
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
-pub struct AxisAlignedBox3 {
+pub struct AxisAlignedBox2 {
     centre: Vec2,
     scale:  Scale2,
 }
 
-impl AxisAlignedBox3 {
+impl AxisAlignedBox2 {
     pub const fn new(centre: Vec2, scale: Scale2) -> Self {
         Self { centre, scale }
     }
 
     #[rustfmt::skip]
-    pub fn intersects(&self, rhs: &AxisAlignedBox3) -> bool {
+    pub fn intersects(&self, rhs: &AxisAlignedBox2) -> bool {
            f32::abs(self.centre.x - rhs.centre.x) <= self.scale.w + rhs.scale.w
         && f32::abs(self.centre.y - rhs.centre.y) <= self.scale.h + rhs.scale.h
     }
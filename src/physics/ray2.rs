@@ -1,5 +1,8 @@
 use crate::core::maths::{Unit, Vec2};
 
+// NOTE:
+// The fields are only read once the ray/box test below is ported.
+#[allow(dead_code)]
 pub struct Ray2 {
     perpendicular: Vec2,
     reciprocal:    Vec2,
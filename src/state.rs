@@ -0,0 +1,452 @@
+use std::time::{Duration, Instant};
+
+use bumpalo::Bump;
+use winit::{event::WindowEvent, window::WindowId};
+
+use crate::{
+    app::{App, LoopState},
+    graphics::{RenderFrame, Renderer},
+    keyboard::Keyboard,
+    mouse::Mouse,
+    window::Windows,
+};
+
+/// What the state stack should do after a state has handled an update or event.
+pub enum Transition<UserEvent = ()> {
+    Continue,
+    /// Puts a state on top of the current one, which stays on the stack underneath it.
+    Push(Box<dyn State<UserEvent>>),
+    /// Removes the current state, returning to the one underneath it. Popping the last state
+    /// exits the app.
+    Pop,
+    /// Removes the current state and puts another in its place.
+    Replace(Box<dyn State<UserEvent>>),
+    Exit,
+}
+
+/// One screen of an app, such as a menu, the game itself or a pause screen, run by a
+/// `StateStack`. Only the state on top of the stack is updated and receives events.
+#[allow(unused_variables)]
+pub trait State<UserEvent = ()> {
+    fn update(
+        &mut self,
+        windows: &mut Windows<UserEvent>,
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        frame_memory: &mut Bump,
+        renderer: &mut Renderer,
+    ) -> Transition<UserEvent>;
+
+    /// Draws the state into the primary window's frame. `visibility` ramps from 0 to 1 over
+    /// `transition_duration` as the state enters and back down to 0 as it leaves, so it can fade
+    /// or slide itself in and out.
+    fn render(&mut self, frame: &mut RenderFrame, visibility: f32, renderer: &mut Renderer);
+
+    fn fixed_update(&mut self, step: Duration) {}
+
+    fn window_event(
+        &mut self,
+        window: WindowId,
+        event: &WindowEvent,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) -> Transition<UserEvent> {
+        Transition::Continue
+    }
+
+    fn user_event(
+        &mut self,
+        event: UserEvent,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) -> Transition<UserEvent> {
+        Transition::Continue
+    }
+
+    /// Whether the states underneath keep rendering below this one, e.g. gameplay under a pause
+    /// menu. They are not updated either way.
+    fn renders_below(&self) -> bool {
+        false
+    }
+
+    /// How long the state takes to enter and leave the stack.
+    fn transition_duration(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Called when another state is pushed on top of this one.
+    fn covered(&mut self) {}
+
+    /// Called when the state on top of this one has been popped.
+    fn uncovered(&mut self) {}
+
+    /// Called once the state has left the stack, after its transition has finished.
+    fn exited(&mut self) {}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Entering(Instant),
+    Active,
+    Exiting(Instant),
+}
+
+struct Layer<UserEvent> {
+    state: Box<dyn State<UserEvent>>,
+    phase: Phase,
+}
+
+impl<UserEvent> Layer<UserEvent> {
+    fn new(state: Box<dyn State<UserEvent>>, now: Instant) -> Self {
+        let phase = if state.transition_duration() == Duration::ZERO {
+            Phase::Active
+        } else {
+            Phase::Entering(now)
+        };
+
+        Self { state, phase }
+    }
+
+    fn visibility(&self, now: Instant) -> f32 {
+        let progress = |started: Instant| {
+            let duration = self.state.transition_duration();
+
+            if duration == Duration::ZERO {
+                return 1.0;
+            }
+
+            (now.saturating_duration_since(started).as_secs_f32() / duration.as_secs_f32()).min(1.0)
+        };
+
+        match self.phase {
+            Phase::Entering(started) => progress(started),
+            Phase::Active => 1.0,
+            Phase::Exiting(started) => 1.0 - progress(started),
+        }
+    }
+
+    fn is_exiting(&self) -> bool {
+        matches!(self.phase, Phase::Exiting(_))
+    }
+
+    // NOTE:
+    // States that are still fading in or out never fully cover what is underneath them.
+    fn hides_below(&self) -> bool {
+        self.phase == Phase::Active && !self.state.renders_below()
+    }
+}
+
+/// Runs an app as a stack of states, pushing, popping and replacing them as they ask to.
+pub struct StateStack<UserEvent = ()> {
+    layers:         Vec<Layer<UserEvent>>,
+    exit_requested: bool,
+}
+
+impl<UserEvent> StateStack<UserEvent> {
+    pub fn new(initial: impl State<UserEvent> + 'static) -> Self {
+        Self {
+            layers:         vec![Layer::new(Box::new(initial), Instant::now())],
+            exit_requested: false,
+        }
+    }
+
+    /// The number of states on the stack, not counting any that are leaving it.
+    pub fn len(&self) -> usize {
+        self.layers
+            .iter()
+            .filter(|layer| !layer.is_exiting())
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The state on top of the stack, ignoring states that are leaving it.
+    fn top_mut(&mut self) -> Option<&mut Layer<UserEvent>> {
+        self.layers
+            .iter_mut()
+            .rev()
+            .find(|layer| !layer.is_exiting())
+    }
+
+    fn apply(&mut self, transition: Transition<UserEvent>, now: Instant) {
+        match transition {
+            Transition::Continue => {},
+            Transition::Push(state) => {
+                if let Some(top) = self.top_mut() {
+                    top.state.covered();
+                }
+
+                self.layers.push(Layer::new(state, now));
+            },
+            Transition::Pop => {
+                if let Some(top) = self.top_mut() {
+                    top.phase = Phase::Exiting(now);
+                }
+
+                if let Some(top) = self.top_mut() {
+                    top.state.uncovered();
+                }
+            },
+            Transition::Replace(state) => {
+                if let Some(top) = self.top_mut() {
+                    top.phase = Phase::Exiting(now);
+                }
+
+                self.layers.push(Layer::new(state, now));
+            },
+            Transition::Exit => self.exit_requested = true,
+        }
+
+        self.finish_transitions(now);
+    }
+
+    // Activates states that have finished entering and removes those that have finished leaving.
+    fn finish_transitions(&mut self, now: Instant) {
+        self.layers.retain_mut(|layer| {
+            let visibility = layer.visibility(now);
+
+            match layer.phase {
+                Phase::Entering(_) if visibility >= 1.0 => layer.phase = Phase::Active,
+                Phase::Exiting(_) if visibility <= 0.0 => {
+                    layer.state.exited();
+                    return false;
+                },
+                _ => {},
+            }
+
+            true
+        });
+    }
+
+    // The index of the lowest state that can be seen, everything from there up is rendered.
+    fn lowest_visible(&self) -> usize {
+        self.layers
+            .iter()
+            .rposition(|layer| layer.hides_below())
+            .unwrap_or(0)
+    }
+
+    fn render(&mut self, windows: &Windows<UserEvent>, renderer: &mut Renderer) -> LoopState {
+        let mut frame = match renderer.begin_frame(windows.primary()) {
+            Ok(frame) => frame,
+            Err(frame_error) if frame_error.is_fatal() => {
+                log::error!("{}", frame_error);
+                return LoopState::Exit;
+            },
+            Err(_) => return LoopState::Continue,
+        };
+
+        let now = Instant::now();
+        let lowest_visible = self.lowest_visible();
+
+        for layer in &mut self.layers[lowest_visible..] {
+            let visibility = layer.visibility(now);
+            layer.state.render(&mut frame, visibility, renderer);
+        }
+
+        renderer.finish_frame(frame);
+
+        LoopState::Continue
+    }
+}
+
+impl<UserEvent> App<UserEvent> for StateStack<UserEvent> {
+    const EXIT_ON_CLOSE: bool = true;
+
+    fn fixed_update(&mut self, step: Duration) {
+        if let Some(top) = self.top_mut() {
+            top.state.fixed_update(step);
+        }
+    }
+
+    fn update_and_render(
+        &mut self,
+        windows: &mut Windows<UserEvent>,
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        frame_memory: &mut Bump,
+        renderer: &mut Renderer,
+    ) -> LoopState {
+        let transition = match self.top_mut() {
+            Some(top) => top
+                .state
+                .update(windows, keyboard, mouse, frame_memory, renderer),
+            None => Transition::Continue,
+        };
+
+        self.apply(transition, Instant::now());
+
+        // NOTE:
+        // The last state leaving only exits the app once it has finished its transition.
+        if self.exit_requested || self.layers.is_empty() {
+            return LoopState::Exit;
+        }
+
+        self.render(windows, renderer)
+    }
+
+    fn window_event(
+        &mut self,
+        window: WindowId,
+        event: &WindowEvent,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) {
+        if let Some(top) = self.top_mut() {
+            let transition = top.state.window_event(window, event, windows, renderer);
+            self.apply(transition, Instant::now());
+        }
+    }
+
+    fn user_event(
+        &mut self,
+        event: UserEvent,
+        windows: &mut Windows<UserEvent>,
+        renderer: &mut Renderer,
+    ) {
+        if let Some(top) = self.top_mut() {
+            let transition = top.state.user_event(event, windows, renderer);
+            self.apply(transition, Instant::now());
+        }
+    }
+
+    fn clean_up(&mut self) {
+        for layer in self.layers.drain(..).rev() {
+            let mut state = layer.state;
+            state.exited();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    struct Named {
+        name:          &'static str,
+        renders_below: bool,
+        duration:      Duration,
+        log:           Log,
+    }
+
+    impl Named {
+        fn new(name: &'static str, log: &Log) -> Box<Self> {
+            Box::new(Self {
+                name,
+                renders_below: false,
+                duration: Duration::ZERO,
+                log: log.clone(),
+            })
+        }
+    }
+
+    impl State for Named {
+        fn update(
+            &mut self,
+            _windows: &mut Windows,
+            _keyboard: &Keyboard,
+            _mouse: &Mouse,
+            _frame_memory: &mut Bump,
+            _renderer: &mut Renderer,
+        ) -> Transition {
+            Transition::Continue
+        }
+
+        fn render(&mut self, _frame: &mut RenderFrame, _visibility: f32, _renderer: &mut Renderer) {
+        }
+
+        fn renders_below(&self) -> bool {
+            self.renders_below
+        }
+
+        fn transition_duration(&self) -> Duration {
+            self.duration
+        }
+
+        fn covered(&mut self) {
+            self.log.borrow_mut().push(format!("{} covered", self.name));
+        }
+
+        fn uncovered(&mut self) {
+            self.log.borrow_mut().push(format!("{} uncovered", self.name));
+        }
+
+        fn exited(&mut self) {
+            self.log.borrow_mut().push(format!("{} exited", self.name));
+        }
+    }
+
+    fn stack(log: &Log) -> StateStack {
+        StateStack {
+            layers:         vec![Layer::new(Named::new("game", log), Instant::now())],
+            exit_requested: false,
+        }
+    }
+
+    #[test]
+    fn push_and_pop_notify_states() {
+        let log = Log::default();
+        let mut stack = stack(&log);
+        let now = Instant::now();
+
+        stack.apply(Transition::Push(Named::new("pause", &log)), now);
+        assert_eq!(stack.len(), 2);
+
+        stack.apply(Transition::Pop, now);
+        assert_eq!(stack.len(), 1);
+        assert_eq!(*log.borrow(), ["game covered", "game uncovered", "pause exited"]);
+    }
+
+    #[test]
+    fn replace_exits_the_replaced_state() {
+        let log = Log::default();
+        let mut stack = stack(&log);
+
+        stack.apply(Transition::Replace(Named::new("menu", &log)), Instant::now());
+
+        assert_eq!(stack.len(), 1);
+        assert_eq!(*log.borrow(), ["game exited"]);
+    }
+
+    #[test]
+    fn leaving_states_stay_until_their_transition_ends() {
+        let log = Log::default();
+        let mut stack = stack(&log);
+        let now = Instant::now();
+        let mut fading = Named::new("fading", &log);
+        fading.duration = Duration::from_secs(1);
+
+        stack.apply(Transition::Push(fading), now);
+        stack.apply(Transition::Continue, now + Duration::from_secs(1));
+        stack.apply(Transition::Pop, now + Duration::from_secs(1));
+
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack.layers.len(), 2);
+        assert_eq!(stack.layers[1].visibility(now + Duration::from_millis(1500)), 0.5);
+
+        stack.apply(Transition::Continue, now + Duration::from_secs(2));
+
+        assert_eq!(stack.layers.len(), 1);
+    }
+
+    #[test]
+    fn only_states_below_overlays_are_rendered() {
+        let log = Log::default();
+        let mut stack = stack(&log);
+        let now = Instant::now();
+        let mut pause = Named::new("pause", &log);
+        pause.renders_below = true;
+
+        stack.apply(Transition::Push(pause), now);
+        assert_eq!(stack.lowest_visible(), 0);
+
+        stack.apply(Transition::Push(Named::new("options", &log)), now);
+        assert_eq!(stack.lowest_visible(), 2);
+    }
+}
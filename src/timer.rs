@@ -0,0 +1,229 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+// NOTE:
+// OS sleeps can overshoot by a millisecond or more depending on the platform's timer resolution,
+// so the last stretch before a deadline is spent spinning instead.
+const SPIN_THRESHOLD: Duration = Duration::from_millis(2);
+
+/// Caps how often frames are started by sleeping out whatever is left of each frame.
+pub struct FrameLimiter {
+    frame_duration: Duration,
+    next_frame:     Instant,
+}
+
+impl FrameLimiter {
+    pub fn new(target_fps: u32) -> Self {
+        assert!(target_fps > 0, "The target frame rate must be greater than 0");
+
+        let frame_duration = Duration::from_secs(1) / target_fps;
+
+        Self {
+            frame_duration,
+            next_frame: Instant::now() + frame_duration,
+        }
+    }
+
+    pub fn frame_duration(&self) -> Duration {
+        self.frame_duration
+    }
+
+    /// Blocks until the current frame's time is up. Frames that ran over are not made up for,
+    /// the next frame simply starts from now.
+    pub fn wait(&mut self) {
+        let now = Instant::now();
+
+        match self.next_frame.checked_duration_since(now) {
+            Some(remaining) => {
+                sleep_precise(remaining);
+                self.next_frame += self.frame_duration;
+            },
+            None => self.next_frame = now + self.frame_duration,
+        }
+    }
+}
+
+/// Runs a fixed number of updates per second however fast frames are being rendered, so
+/// simulations behave the same at any frame rate.
+pub struct FixedClock {
+    step:        Duration,
+    accumulated: Duration,
+    last_tick:   Option<Instant>,
+    paused:      bool,
+}
+
+impl FixedClock {
+    // NOTE:
+    // When updates take longer than the step the clock falls further behind every frame, so any
+    // time past this many steps is dropped rather than caught up on.
+    const MAX_STEPS_PER_TICK: u32 = 8;
+
+    pub fn new(updates_per_second: u32) -> Self {
+        assert!(updates_per_second > 0, "The update rate must be greater than 0");
+
+        Self {
+            step:        Duration::from_secs(1) / updates_per_second,
+            accumulated: Duration::ZERO,
+            last_tick:   None,
+            paused:      false,
+        }
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Advances the clock to now, returning how many updates are due.
+    pub fn tick(&mut self) -> u32 {
+        self.tick_at(Instant::now())
+    }
+
+    fn tick_at(&mut self, now: Instant) -> u32 {
+        if let Some(last_tick) = self.last_tick.replace(now) {
+            if !self.paused {
+                self.accumulated += now - last_tick;
+            }
+        }
+
+        let mut steps = 0;
+
+        while self.accumulated >= self.step {
+            self.accumulated -= self.step;
+            steps += 1;
+
+            if steps == Self::MAX_STEPS_PER_TICK {
+                self.accumulated = Duration::ZERO;
+                break;
+            }
+        }
+
+        steps
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops time from passing until `resume` is called, no updates are due in between.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+}
+
+/// Sleeps for the given duration, spinning for the final part to avoid oversleeping.
+pub fn sleep_precise(duration: Duration) {
+    let deadline = Instant::now() + duration;
+
+    if duration > SPIN_THRESHOLD {
+        thread::sleep(duration - SPIN_THRESHOLD);
+    }
+
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_durations() {
+        assert_eq!(FrameLimiter::new(50).frame_duration(), Duration::from_millis(20));
+        assert_eq!(FrameLimiter::new(1).frame_duration(), Duration::from_secs(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn needs_a_frame_rate() {
+        FrameLimiter::new(0);
+    }
+
+    #[test]
+    fn waits_out_the_rest_of_the_frame() {
+        let started = Instant::now();
+        let mut limiter = FrameLimiter::new(50);
+
+        limiter.wait();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+
+        limiter.wait();
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn does_not_make_up_for_slow_frames() {
+        let mut limiter = FrameLimiter::new(50);
+
+        thread::sleep(Duration::from_millis(50));
+
+        // The frame ran over, so there's nothing left of it to wait out.
+        let started = Instant::now();
+        limiter.wait();
+        assert!(started.elapsed() < Duration::from_millis(20));
+
+        // The next frame gets its full duration rather than being cut short to catch up.
+        limiter.wait();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn accumulates_steps() {
+        let start = Instant::now();
+        let mut clock = FixedClock::new(100);
+
+        // The first tick only starts the clock.
+        assert_eq!(clock.tick_at(start), 0);
+        assert_eq!(clock.tick_at(start + Duration::from_millis(25)), 2);
+
+        // What was left over from the last tick counts towards the next one.
+        assert_eq!(clock.tick_at(start + Duration::from_millis(30)), 1);
+        assert_eq!(clock.tick_at(start + Duration::from_millis(39)), 0);
+        assert_eq!(clock.tick_at(start + Duration::from_millis(40)), 1);
+    }
+
+    #[test]
+    fn time_stops_while_paused() {
+        let start = Instant::now();
+        let mut clock = FixedClock::new(100);
+
+        clock.tick_at(start);
+        clock.pause();
+        assert!(clock.is_paused());
+        assert_eq!(clock.tick_at(start + Duration::from_millis(500)), 0);
+
+        clock.resume();
+        assert!(!clock.is_paused());
+        assert_eq!(clock.tick_at(start + Duration::from_millis(515)), 1);
+    }
+
+    #[test]
+    fn drops_time_it_cannot_catch_up_on() {
+        let start = Instant::now();
+        let mut clock = FixedClock::new(100);
+
+        clock.tick_at(start);
+
+        assert_eq!(
+            clock.tick_at(start + Duration::from_secs(1)),
+            FixedClock::MAX_STEPS_PER_TICK
+        );
+        assert_eq!(clock.tick_at(start + Duration::from_millis(1005)), 0);
+    }
+
+    #[test]
+    fn sleeps_at_least_as_long_as_asked() {
+        for &millis in &[0, 1, 5] {
+            let started = Instant::now();
+
+            sleep_precise(Duration::from_millis(millis));
+            assert!(started.elapsed() >= Duration::from_millis(millis));
+        }
+    }
+}
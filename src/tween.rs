@@ -0,0 +1,6 @@
+mod animation;
+mod easing;
+mod group;
+mod interpolate;
+
+pub use crate::tween::{animation::*, easing::*, group::*, interpolate::*};
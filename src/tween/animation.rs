@@ -0,0 +1,361 @@
+use std::time::Duration;
+
+use crate::tween::{Easing, Interpolate};
+
+/// Something that changes a `Target` over time, such as a `Tween` or a `Sequence` of them.
+///
+/// Animations are driven by the time that passed since they were last updated, usually the frame
+/// time from `Renderer::frame_stats` or the step passed to `fixed_update`.
+pub trait Animation<Target> {
+    /// Advances the animation by `delta` and applies it to `target`. Returns how much of `delta`
+    /// was left over once the animation finished, so whatever comes next can start on time.
+    fn update(&mut self, delta: Duration, target: &mut Target) -> Duration;
+
+    fn is_finished(&self) -> bool;
+
+    /// Rewinds the animation back to its start.
+    fn reset(&mut self);
+}
+
+/// How many times an animation plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Looping {
+    #[default]
+    Once,
+    /// Plays the given number of times, at least once.
+    Times(u32),
+    Forever,
+}
+
+impl Looping {
+    pub(in crate::tween) fn plays(self) -> Option<u32> {
+        match self {
+            Looping::Once => Some(1),
+            Looping::Times(plays) => Some(plays),
+            Looping::Forever => None,
+        }
+    }
+}
+
+/// Moves a value from `start` to `end` over `duration`, following an easing curve.
+///
+/// By itself a tween sets the whole target, use `with_setter` to animate part of one, e.g. the
+/// position of a sprite.
+#[derive(Debug, Clone)]
+pub struct Tween<T, Target = T> {
+    start:    T,
+    end:      T,
+    duration: Duration,
+    easing:   Easing,
+    delay:    Duration,
+    looping:  Looping,
+    yoyo:     bool,
+    elapsed:  Duration,
+    setter:   fn(&mut Target, T),
+}
+
+impl<T> Tween<T>
+where
+    T: Interpolate,
+{
+    pub fn new(start: T, end: T, duration: Duration) -> Self {
+        Self {
+            start,
+            end,
+            duration,
+            easing: Easing::Linear,
+            delay: Duration::ZERO,
+            looping: Looping::Once,
+            yoyo: false,
+            elapsed: Duration::ZERO,
+            setter: |target, value| *target = value,
+        }
+    }
+}
+
+impl<T, Target> Tween<T, Target>
+where
+    T: Interpolate,
+{
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Waits before the first play. Loops don't wait again.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn with_looping(mut self, looping: Looping) -> Self {
+        assert_ne!(looping, Looping::Times(0), "An animation has to play at least once");
+
+        self.looping = looping;
+        self
+    }
+
+    /// Plays every other loop backwards, from `end` to `start`.
+    pub fn with_yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    /// Applies the value to part of the target instead, e.g.
+    /// `|sprite: &mut Sprite, position| sprite.position = position`.
+    pub fn with_setter<U>(self, setter: fn(&mut U, T)) -> Tween<T, U> {
+        Tween {
+            start: self.start,
+            end: self.end,
+            duration: self.duration,
+            easing: self.easing,
+            delay: self.delay,
+            looping: self.looping,
+            yoyo: self.yoyo,
+            elapsed: self.elapsed,
+            setter,
+        }
+    }
+
+    /// Advances the tween without applying it to anything, see `value`. Returns how much of
+    /// `delta` was left over once the tween finished.
+    pub fn advance(&mut self, delta: Duration) -> Duration {
+        self.elapsed += delta;
+
+        match self.total_duration() {
+            Some(total) if self.elapsed > total => {
+                let left_over = self.elapsed - total;
+                self.elapsed = total;
+                left_over
+            },
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// The current value, `start` until the delay is over.
+    pub fn value(&self) -> T {
+        T::interpolate(&self.start, &self.end, self.easing.ease(self.progress()))
+    }
+
+    /// How far through the current play the tween is, from 0 to 1, before easing. Counts back
+    /// down on the way back of a yoyo.
+    pub fn progress(&self) -> f32 {
+        let playing = self.elapsed.saturating_sub(self.delay);
+
+        let plays = if self.duration.is_zero() {
+            f64::INFINITY
+        } else {
+            playing.as_secs_f64() / self.duration.as_secs_f64()
+        };
+
+        // NOTE:
+        // At the very end of the last play the fraction wraps back to 0, so the end is special
+        // cased to stay on the end value.
+        let (play, fraction) = match self.looping.plays() {
+            Some(total) if plays >= total as f64 => (total.saturating_sub(1), 1.0),
+            None if plays.is_infinite() => (0, 1.0),
+            _ => (plays as u32, plays.fract()),
+        };
+
+        if self.yoyo && play % 2 == 1 {
+            (1.0 - fraction) as f32
+        } else {
+            fraction as f32
+        }
+    }
+
+    /// The delay plus every play, `None` when looping forever.
+    pub fn total_duration(&self) -> Option<Duration> {
+        self.looping.plays().map(|plays| self.delay + self.duration * plays)
+    }
+}
+
+impl<T, Target> Animation<Target> for Tween<T, Target>
+where
+    T: Interpolate,
+{
+    fn update(&mut self, delta: Duration, target: &mut Target) -> Duration {
+        let left_over = self.advance(delta);
+        (self.setter)(target, self.value());
+
+        left_over
+    }
+
+    fn is_finished(&self) -> bool {
+        self.total_duration().is_some_and(|total| self.elapsed >= total)
+    }
+
+    fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+/// Does nothing for a while, to space out the animations in a `Sequence`.
+#[derive(Debug, Clone)]
+pub struct Delay {
+    duration: Duration,
+    elapsed:  Duration,
+}
+
+impl Delay {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl<Target> Animation<Target> for Delay {
+    fn update(&mut self, delta: Duration, _: &mut Target) -> Duration {
+        self.elapsed += delta;
+
+        let left_over = self.elapsed.saturating_sub(self.duration);
+        self.elapsed = self.elapsed.min(self.duration);
+
+        left_over
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_approx_eq,
+        core::maths::{Angle, Quat, Unit, Vec3},
+    };
+
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn moves_from_start_to_end() {
+        let mut tween = Tween::new(0.0, 10.0, millis(1000));
+        let mut value = 0.0;
+
+        tween.update(millis(250), &mut value);
+        assert_approx_eq!(value, 2.5);
+
+        let left_over = tween.update(millis(1000), &mut value);
+        assert_approx_eq!(value, 10.0);
+        assert_eq!(left_over, millis(250));
+        assert!(tween.is_finished());
+    }
+
+    #[test]
+    fn follows_its_easing() {
+        let mut tween = Tween::new(0.0, 1.0, millis(1000)).with_easing(Easing::QuadIn);
+
+        tween.advance(millis(500));
+        assert_approx_eq!(tween.value(), 0.25);
+    }
+
+    #[test]
+    fn waits_for_its_delay() {
+        let mut tween = Tween::new(0.0, 1.0, millis(1000)).with_delay(millis(500));
+
+        tween.advance(millis(400));
+        assert_approx_eq!(tween.value(), 0.0);
+
+        tween.advance(millis(600));
+        assert_approx_eq!(tween.value(), 0.5);
+        assert_eq!(tween.total_duration(), Some(millis(1500)));
+    }
+
+    #[test]
+    fn loops_and_yoyos() {
+        let mut tween = Tween::new(0.0, 1.0, millis(1000)).with_looping(Looping::Times(3));
+
+        tween.advance(millis(1250));
+        assert_approx_eq!(tween.value(), 0.25);
+
+        let mut tween = tween.with_yoyo(true);
+        assert_approx_eq!(tween.value(), 0.75);
+
+        // The third play goes forwards again, so it ends on `end`.
+        tween.advance(millis(5000));
+        assert_approx_eq!(tween.value(), 1.0);
+        assert!(tween.is_finished());
+
+        let mut tween = Tween::new(0.0, 1.0, millis(1000))
+            .with_looping(Looping::Times(2))
+            .with_yoyo(true);
+
+        tween.advance(millis(2000));
+        assert_approx_eq!(tween.value(), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn plays_at_least_once() {
+        Tween::new(0.0, 1.0, millis(100)).with_looping(Looping::Times(0));
+    }
+
+    #[test]
+    fn forever_never_finishes() {
+        let mut tween = Tween::new(0.0, 1.0, millis(100)).with_looping(Looping::Forever);
+
+        assert_eq!(tween.advance(millis(100_050)), Duration::ZERO);
+        assert!(!Animation::<f32>::is_finished(&tween));
+        assert_approx_eq!(tween.value(), 0.5, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn zero_durations_jump_to_the_end() {
+        let mut tween = Tween::new(0.0, 1.0, Duration::ZERO);
+
+        assert_eq!(tween.advance(millis(10)), millis(10));
+        assert_approx_eq!(tween.value(), 1.0);
+    }
+
+    #[test]
+    fn sets_part_of_a_target() {
+        struct Sprite {
+            position: Vec3,
+            rotation: Angle,
+        }
+
+        let mut sprite = Sprite {
+            position: Vec3::zero(),
+            rotation: Angle::zero(),
+        };
+
+        let mut tween = Tween::new(Vec3::zero(), Vec3::new(2.0, 4.0, 6.0), millis(100))
+            .with_setter(|sprite: &mut Sprite, position| sprite.position = position);
+
+        tween.update(millis(50), &mut sprite);
+
+        assert_approx_eq!(sprite.position, Vec3::new(1.0, 2.0, 3.0));
+        assert_approx_eq!(sprite.rotation, Angle::zero());
+    }
+
+    #[test]
+    fn rotations_slerp() {
+        let end = Quat::from_axis_angle(&Unit::new(Vec3::new(0.0, 1.0, 0.0)), Angle::degrees(90.0));
+        let mut tween = Tween::new(Quat::identity(), end, millis(100));
+
+        tween.advance(millis(50));
+
+        let (_, angle) = tween.value().to_axis_angle();
+        assert_approx_eq!(angle, Angle::degrees(45.0), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn delays_pass_on_what_is_left() {
+        let mut delay = Delay::new(millis(100));
+
+        assert_eq!(Animation::<()>::update(&mut delay, millis(60), &mut ()), Duration::ZERO);
+        assert_eq!(Animation::<()>::update(&mut delay, millis(60), &mut ()), millis(20));
+        assert!(Animation::<()>::is_finished(&delay));
+    }
+}
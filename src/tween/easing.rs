@@ -0,0 +1,315 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+// NOTE:
+// The constants below are the usual ones from Robert Penner's easing equations, the back curves
+// overshoot by about 10% and the elastic ones oscillate three times.
+const BACK:           f32 = 1.70158;
+const BACK_IN_OUT:    f32 = BACK * 1.525;
+const ELASTIC:        f32 = TAU / 3.0;
+const ELASTIC_IN_OUT: f32 = TAU / 4.5;
+const BOUNCE:         f32 = 7.5625;
+const BOUNCE_WIDTH:   f32 = 2.75;
+
+const BEZIER_ITERATIONS: usize = 8;
+const BEZIER_TOLERANCE:  f32 = 1e-6;
+
+/// Maps how far through a tween it is to how far between its start and end the value should be.
+/// Every curve starts at 0 and ends at 1, though back and elastic curves overshoot in between.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    /// A CSS style curve from (0, 0) to (1, 1) with the control points `(x1, y1)` and `(x2, y2)`.
+    /// The x coordinates must be between 0 and 1.
+    CubicBezier(f32, f32, f32, f32),
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// Eases `t`, which is clamped between 0 and 1.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut => in_out(t, |t| t * t),
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => in_out(t, |t| t.powi(3)),
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => 1.0 - (1.0 - t).powi(4),
+            Easing::QuartInOut => in_out(t, |t| t.powi(4)),
+            Easing::QuintIn => t.powi(5),
+            Easing::QuintOut => 1.0 - (1.0 - t).powi(5),
+            Easing::QuintInOut => in_out(t, |t| t.powi(5)),
+            Easing::SineIn => 1.0 - (t * FRAC_PI_2).cos(),
+            Easing::SineOut => (t * FRAC_PI_2).sin(),
+            Easing::SineInOut => (1.0 - (t * PI).cos()) / 2.0,
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => 1.0 - expo_in(1.0 - t),
+            Easing::ExpoInOut => in_out(t, expo_in),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => 1.0 - circ_in(1.0 - t),
+            Easing::CircInOut => in_out(t, circ_in),
+            Easing::BackIn => back_in(t, BACK),
+            Easing::BackOut => 1.0 - back_in(1.0 - t, BACK),
+            Easing::BackInOut => in_out(t, |t| back_in(t, BACK_IN_OUT)),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => 1.0 - elastic_in(1.0 - t),
+            Easing::ElasticInOut => elastic_in_out(t),
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => in_out(t, |t| 1.0 - bounce_out(1.0 - t)),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(t, x1, y1, x2, y2),
+            Easing::Custom(ease) => ease(t),
+        }
+    }
+}
+
+/// Runs an ease in curve over the first half and its mirror image over the second.
+fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.0) / 2.0
+    } else {
+        1.0 - ease_in((1.0 - t) * 2.0) / 2.0
+    }
+}
+
+fn expo_in(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else {
+        2f32.powf(10.0 * t - 10.0)
+    }
+}
+
+fn circ_in(t: f32) -> f32 {
+    1.0 - (1.0 - t * t).max(0.0).sqrt()
+}
+
+fn back_in(t: f32, overshoot: f32) -> f32 {
+    (overshoot + 1.0) * t.powi(3) - overshoot * t * t
+}
+
+fn elastic_in(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        t
+    } else {
+        -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC).sin()
+    }
+}
+
+fn elastic_in_out(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        t
+    } else if t < 0.5 {
+        -(2f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin()) / 2.0
+    } else {
+        2f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin() / 2.0 + 1.0
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    if t < 1.0 / BOUNCE_WIDTH {
+        BOUNCE * t * t
+    } else if t < 2.0 / BOUNCE_WIDTH {
+        let t = t - 1.5 / BOUNCE_WIDTH;
+        BOUNCE * t * t + 0.75
+    } else if t < 2.5 / BOUNCE_WIDTH {
+        let t = t - 2.25 / BOUNCE_WIDTH;
+        BOUNCE * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / BOUNCE_WIDTH;
+        BOUNCE * t * t + 0.984375
+    }
+}
+
+/// One coordinate of a cubic Bezier from 0 to 1 with the control points `a` and `b`.
+fn bezier(s: f32, a: f32, b: f32) -> f32 {
+    let inverse = 1.0 - s;
+
+    3.0 * inverse * inverse * s * a + 3.0 * inverse * s * s * b + s * s * s
+}
+
+fn bezier_slope(s: f32, a: f32, b: f32) -> f32 {
+    let inverse = 1.0 - s;
+
+    3.0 * inverse * inverse * a + 6.0 * inverse * s * (b - a) + 3.0 * s * s * (1.0 - b)
+}
+
+fn cubic_bezier(t: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    // NOTE:
+    // The curve is parametric, so first the parameter where x is t has to be found. Newton's
+    // method converges in a few steps except where the curve is nearly flat, where it falls back
+    // on bisection which always gets there.
+    let mut s = t;
+
+    for _ in 0..BEZIER_ITERATIONS {
+        let error = bezier(s, x1, x2) - t;
+        let slope = bezier_slope(s, x1, x2);
+
+        if error.abs() < BEZIER_TOLERANCE {
+            return bezier(s, y1, y2);
+        }
+
+        if slope.abs() < BEZIER_TOLERANCE {
+            break;
+        }
+
+        s = (s - error / slope).clamp(0.0, 1.0);
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+
+    while high - low > BEZIER_TOLERANCE {
+        if bezier(s, x1, x2) < t {
+            low = s;
+        } else {
+            high = s;
+        }
+
+        s = (low + high) / 2.0;
+    }
+
+    bezier(s, y1, y2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_approx_eq;
+
+    use super::*;
+
+    const ALL: [Easing; 32] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::QuintIn,
+        Easing::QuintOut,
+        Easing::QuintInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+        Easing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+    ];
+
+    #[test]
+    fn every_curve_starts_at_0_and_ends_at_1() {
+        for easing in ALL.iter() {
+            assert_approx_eq!(easing.ease(0.0), 0.0, epsilon = 1e-3);
+            assert_approx_eq!(easing.ease(1.0), 1.0, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn every_curve_is_continuous() {
+        for easing in ALL.iter() {
+            for step in 0..1000 {
+                let t = step as f32 / 1000.0;
+                let jump = (easing.ease(t + 0.001) - easing.ease(t)).abs();
+
+                assert!(jump < 0.05, "{:?} jumps by {} at {}", easing, jump, t);
+            }
+        }
+    }
+
+    #[test]
+    fn in_out_curves_are_symmetric() {
+        let easings = [Easing::QuadInOut, Easing::CubicInOut, Easing::SineInOut, Easing::BounceInOut];
+
+        for easing in easings.iter() {
+            assert_approx_eq!(easing.ease(0.5), 0.5, epsilon = 1e-6);
+            assert_approx_eq!(easing.ease(0.2), 1.0 - easing.ease(0.8), epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        assert_approx_eq!(Easing::QuadIn.ease(0.5), 0.25);
+        assert_approx_eq!(Easing::CubicOut.ease(0.5), 0.875);
+        assert_approx_eq!(Easing::BounceOut.ease(1.0 / BOUNCE_WIDTH), 1.0, epsilon = 1e-5);
+        assert!(Easing::BackIn.ease(0.3) < 0.0);
+        assert!(Easing::BackOut.ease(0.7) > 1.0);
+        assert!(Easing::ElasticOut.ease(0.2) > 1.0);
+    }
+
+    #[test]
+    fn cubic_bezier_matches_its_special_cases() {
+        for step in 0..=10 {
+            let t = step as f32 / 10.0;
+
+            // A Bezier with its control points on the diagonal is linear.
+            assert_approx_eq!(
+                Easing::CubicBezier(1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0).ease(t),
+                t,
+                epsilon = 1e-4
+            );
+        }
+
+        // CSS's `ease` curve is about 80% of the way there halfway through.
+        let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        assert_approx_eq!(ease.ease(0.5), 0.8024, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn input_is_clamped() {
+        assert_approx_eq!(Easing::Linear.ease(-1.0), 0.0);
+        assert_approx_eq!(Easing::QuadOut.ease(2.0), 1.0);
+        assert_approx_eq!(Easing::Custom(|t| t * t * t).ease(0.5), 0.125);
+    }
+}
@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use crate::tween::{Animation, Looping};
+
+/// Plays animations one after another, each starting where the last one finished.
+pub struct Sequence<Target> {
+    animations: Vec<Box<dyn Animation<Target>>>,
+    current:    usize,
+    looping:    Looping,
+    plays:      u32,
+}
+
+impl<Target> Sequence<Target> {
+    pub fn new() -> Self {
+        Self {
+            animations: Vec::new(),
+            current:    0,
+            looping:    Looping::Once,
+            plays:      0,
+        }
+    }
+
+    pub fn then<A>(mut self, animation: A) -> Self
+    where
+        A: Animation<Target> + 'static,
+    {
+        self.animations.push(Box::new(animation));
+        self
+    }
+
+    /// Starts the whole sequence over once it has finished. Put a `Parallel` group in a looping
+    /// sequence to loop the group.
+    pub fn with_looping(mut self, looping: Looping) -> Self {
+        assert_ne!(looping, Looping::Times(0), "A sequence has to play at least once");
+
+        self.looping = looping;
+        self
+    }
+
+    fn restart(&mut self) {
+        self.current = 0;
+
+        for animation in &mut self.animations {
+            animation.reset();
+        }
+    }
+}
+
+impl<Target> Default for Sequence<Target> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Target> Animation<Target> for Sequence<Target> {
+    fn update(&mut self, mut delta: Duration, target: &mut Target) -> Duration {
+        let mut play_start = delta;
+
+        while !self.is_finished() {
+            let animation = &mut self.animations[self.current];
+            delta = animation.update(delta, target);
+
+            if !animation.is_finished() {
+                return Duration::ZERO;
+            }
+
+            self.current += 1;
+
+            if self.current == self.animations.len() {
+                self.plays += 1;
+
+                if self.looping.plays().is_none_or(|plays| self.plays < plays) {
+                    // NOTE:
+                    // A play that takes no time at all would loop forever within a single update,
+                    // so the rest of the time is spent waiting for the next one instead.
+                    if delta == play_start {
+                        self.restart();
+                        return Duration::ZERO;
+                    }
+
+                    self.restart();
+                    play_start = delta;
+                }
+            }
+        }
+
+        delta
+    }
+
+    fn is_finished(&self) -> bool {
+        self.current >= self.animations.len()
+    }
+
+    fn reset(&mut self) {
+        self.plays = 0;
+        self.restart();
+    }
+}
+
+/// Plays animations at the same time, finishing once they all have.
+pub struct Parallel<Target> {
+    animations: Vec<Box<dyn Animation<Target>>>,
+}
+
+impl<Target> Parallel<Target> {
+    pub fn new() -> Self {
+        Self {
+            animations: Vec::new(),
+        }
+    }
+
+    pub fn with<A>(mut self, animation: A) -> Self
+    where
+        A: Animation<Target> + 'static,
+    {
+        self.animations.push(Box::new(animation));
+        self
+    }
+}
+
+impl<Target> Default for Parallel<Target> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Target> Animation<Target> for Parallel<Target> {
+    fn update(&mut self, delta: Duration, target: &mut Target) -> Duration {
+        // NOTE:
+        // Whatever is left over is what the longest running animation didn't need.
+        self.animations
+            .iter_mut()
+            .filter(|animation| !animation.is_finished())
+            .fold(delta, |left_over, animation| left_over.min(animation.update(delta, target)))
+    }
+
+    fn is_finished(&self) -> bool {
+        self.animations.iter().all(|animation| animation.is_finished())
+    }
+
+    fn reset(&mut self) {
+        for animation in &mut self.animations {
+            animation.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assert_approx_eq,
+        core::maths::Vec2,
+        tween::{Delay, Easing, Tween},
+    };
+
+    use super::*;
+
+    struct Button {
+        position: Vec2,
+        opacity:  f32,
+    }
+
+    impl Default for Button {
+        fn default() -> Self {
+            Self {
+                position: Vec2::zero(),
+                opacity:  0.0,
+            }
+        }
+    }
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn slide(from: Vec2, to: Vec2) -> Tween<Vec2, Button> {
+        Tween::new(from, to, millis(100)).with_setter(|button: &mut Button, position| {
+            button.position = position
+        })
+    }
+
+    fn fade(from: f32, to: f32) -> Tween<f32, Button> {
+        Tween::new(from, to, millis(200))
+            .with_setter(|button: &mut Button, opacity| button.opacity = opacity)
+    }
+
+    #[test]
+    fn sequences_play_in_order() {
+        let mut button = Button::default();
+        let mut sequence = Sequence::new()
+            .then(slide(Vec2::zero(), Vec2::new(10.0, 0.0)))
+            .then(Delay::new(millis(50)))
+            .then(slide(Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)));
+
+        sequence.update(millis(120), &mut button);
+        assert_approx_eq!(button.position, Vec2::new(10.0, 0.0));
+
+        // The time left over from the first slide counts towards the delay.
+        sequence.update(millis(80), &mut button);
+        assert_approx_eq!(button.position, Vec2::new(10.0, 5.0));
+
+        assert_eq!(sequence.update(millis(100), &mut button), millis(50));
+        assert_approx_eq!(button.position, Vec2::new(10.0, 10.0));
+        assert!(sequence.is_finished());
+    }
+
+    #[test]
+    fn parallel_groups_finish_with_their_longest_animation() {
+        let mut button = Button::default();
+        let mut group = Parallel::new()
+            .with(slide(Vec2::zero(), Vec2::new(10.0, 0.0)))
+            .with(fade(0.0, 1.0).with_easing(Easing::Linear));
+
+        group.update(millis(150), &mut button);
+        assert_approx_eq!(button.position, Vec2::new(10.0, 0.0));
+        assert_approx_eq!(button.opacity, 0.75);
+        assert!(!group.is_finished());
+
+        assert_eq!(group.update(millis(100), &mut button), millis(50));
+        assert_approx_eq!(button.opacity, 1.0);
+        assert!(group.is_finished());
+    }
+
+    #[test]
+    fn looping_sequences_start_over() {
+        let mut button = Button::default();
+        let mut sequence = Sequence::new()
+            .then(fade(0.0, 1.0))
+            .then(fade(1.0, 0.0))
+            .with_looping(Looping::Times(2));
+
+        sequence.update(millis(500), &mut button);
+        assert_approx_eq!(button.opacity, 0.5);
+        assert!(!sequence.is_finished());
+
+        assert_eq!(sequence.update(millis(400), &mut button), millis(100));
+        assert_approx_eq!(button.opacity, 0.0);
+        assert!(sequence.is_finished());
+    }
+
+    #[test]
+    #[should_panic]
+    fn sequences_play_at_least_once() {
+        Sequence::<Button>::new()
+            .then(fade(0.0, 1.0))
+            .with_looping(Looping::Times(0));
+    }
+
+    #[test]
+    fn empty_loops_do_not_hang() {
+        let mut sequence = Sequence::<Button>::new()
+            .then(Delay::new(Duration::ZERO))
+            .with_looping(Looping::Forever);
+
+        assert_eq!(sequence.update(millis(100), &mut Button::default()), Duration::ZERO);
+        assert!(!sequence.is_finished());
+    }
+}
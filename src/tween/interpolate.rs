@@ -0,0 +1,83 @@
+use crate::{
+    core::maths::{Angle, Scalar, TPoint2, TPoint3, TQuat, TScale2, TScale3, TVec2, TVec3, TVec4},
+    graphics::Colour,
+};
+
+/// Values a `Tween` can move between.
+pub trait Interpolate: Clone {
+    /// The value `t` of the way from `start` to `end`. `t` is usually between 0 and 1, but easing
+    /// curves that overshoot take it past either end.
+    fn interpolate(start: &Self, end: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(start: &f32, end: &f32, t: f32) -> f32 {
+        start + (end - start) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(start: &f64, end: &f64, t: f32) -> f64 {
+        start + (end - start) * t as f64
+    }
+}
+
+macro_rules! interpolate {
+    ($($struct:ident { $( $member:ident )* },)*) => {
+        $(
+            impl<S> Interpolate for $struct<S>
+            where
+                S: Scalar,
+            {
+                fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+                    let t = S::from_f32(t);
+
+                    Self {
+                        $(
+                            $member: start.$member + (end.$member - start.$member) * t,
+                        )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+interpolate!(
+    TVec2 { x y },
+    TVec3 { x y z },
+    TVec4 { x y z w },
+    TPoint2 { x y },
+    TPoint3 { x y z },
+    TScale2 { w h },
+    TScale3 { w h d },
+);
+
+/// Rotations are interpolated along the shortest arc at a constant angular speed.
+impl<S> Interpolate for TQuat<S>
+where
+    S: Scalar,
+{
+    fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+        *TQuat::slerp(start, end, S::from_f32(t))
+    }
+}
+
+/// Angles are interpolated as they are, so going from 0 to 2π turns all the way round. Tween to
+/// `start + start.shortest_difference(end)` to take the shortest way instead.
+impl Interpolate for Angle {
+    fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+        Angle::lerp(start, end, t)
+    }
+}
+
+impl Interpolate for Colour {
+    fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+        Colour {
+            r: f64::interpolate(&start.r, &end.r, t),
+            g: f64::interpolate(&start.g, &end.g, t),
+            b: f64::interpolate(&start.b, &end.b, t),
+            a: f64::interpolate(&start.a, &end.a, t),
+        }
+    }
+}
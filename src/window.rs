@@ -0,0 +1,499 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    io,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use winit::{
+    dpi::{LogicalPosition, LogicalSize},
+    error::OsError,
+    event::WindowEvent,
+    event_loop::EventLoopWindowTarget,
+    monitor::{MonitorHandle, VideoMode},
+    window::{Fullscreen, Icon, Window, WindowBuilder, WindowId},
+};
+
+use crate::{
+    engine::{EngineError, EventProxy},
+    graphics::Renderer,
+};
+
+pub use winit::window::CursorIcon;
+
+/// How a window covers the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FullscreenMode {
+    Windowed,
+    /// A borderless window covering the monitor at the desktop's resolution.
+    Borderless,
+    /// Takes over the monitor with a video mode. When the monitor can't display it exactly the
+    /// closest mode it supports is used.
+    Exclusive(VideoModeInfo),
+}
+
+/// A resolution and refresh rate a monitor can display, see `Windows::video_modes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoModeInfo {
+    pub width:        u32,
+    pub height:       u32,
+    pub refresh_rate: u16,
+    pub bit_depth:    u16,
+}
+
+impl VideoModeInfo {
+    fn of(video_mode: &VideoMode) -> Self {
+        Self {
+            width:        video_mode.size().width,
+            height:       video_mode.size().height,
+            refresh_rate: video_mode.refresh_rate(),
+            bit_depth:    video_mode.bit_depth(),
+        }
+    }
+
+    // Orders video modes by how far they are from this one, resolution first.
+    fn distance(&self, other: &VideoModeInfo) -> (u32, u16, u16) {
+        let width = (self.width as i64 - other.width as i64).abs();
+        let height = (self.height as i64 - other.height as i64).abs();
+
+        (
+            (width + height) as u32,
+            self.refresh_rate.abs_diff(other.refresh_rate),
+            self.bit_depth.abs_diff(other.bit_depth),
+        )
+    }
+}
+
+/// The parts of a window that are kept between runs. Sizes and positions are logical so windows
+/// come back at the same apparent size on displays with a different scale factor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowSettings {
+    /// The size of the window's contents.
+    pub size:       LogicalSize<f64>,
+    /// The position of the window's top left corner, `None` leaves it up to the platform.
+    pub position:   Option<LogicalPosition<f64>>,
+    pub min_size:   Option<LogicalSize<f64>>,
+    pub resizable:  bool,
+    pub maximised:  bool,
+    pub fullscreen: FullscreenMode,
+}
+
+impl WindowSettings {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            size:       LogicalSize::new(width, height),
+            position:   None,
+            min_size:   None,
+            resizable:  true,
+            maximised:  false,
+            fullscreen: FullscreenMode::Windowed,
+        }
+    }
+
+    /// Reads settings saved with `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, WindowSettingsError> {
+        let json = fs::read_to_string(path).map_err(WindowSettingsError::Io)?;
+
+        serde_json::from_str(&json).map_err(WindowSettingsError::Format)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), WindowSettingsError> {
+        let json = serde_json::to_string_pretty(self).map_err(WindowSettingsError::Format)?;
+
+        fs::write(path, json).map_err(WindowSettingsError::Io)
+    }
+
+    pub(crate) fn build<UserEvent>(
+        &self,
+        title: &str,
+        target: &EventLoopWindowTarget<UserEvent>,
+    ) -> Result<OpenWindow, OsError> {
+        let mut builder = WindowBuilder::new()
+            .with_title(title)
+            .with_inner_size(self.size)
+            .with_resizable(self.resizable)
+            .with_maximized(self.maximised)
+            .with_fullscreen(fullscreen(self.fullscreen, target.primary_monitor()));
+
+        if let Some(position) = self.position {
+            builder = builder.with_position(position);
+        }
+
+        if let Some(min_size) = self.min_size {
+            builder = builder.with_min_inner_size(min_size);
+        }
+
+        Ok(OpenWindow {
+            window:   builder.build(target)?,
+            settings: self.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum WindowSettingsError {
+    Io(io::Error),
+    Format(serde_json::Error),
+}
+
+impl Display for WindowSettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use WindowSettingsError::*;
+
+        match self {
+            Io(io_error) => write!(f, "Unable to access window settings, {}", io_error),
+            Format(json_error) => write!(f, "Invalid window settings, {}", json_error),
+        }
+    }
+}
+
+impl Error for WindowSettingsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WindowSettingsError::Io(io_error) => Some(io_error),
+            WindowSettingsError::Format(json_error) => Some(json_error),
+        }
+    }
+}
+
+// NOTE:
+// winit can't report back everything a window was configured with, such as whether it's
+// resizable, so the settings last applied are kept alongside it.
+pub(crate) struct OpenWindow {
+    pub(crate) window:   Window,
+    pub(crate) settings: WindowSettings,
+}
+
+/// The engine's open windows, handed to the `App` so it can open, close and change windows while
+/// it runs. A window is drawn into by beginning a frame for its id.
+pub struct Windows<'a, UserEvent = ()>
+where
+    UserEvent: 'static,
+{
+    pub(crate) target:  &'a EventLoopWindowTarget<UserEvent>,
+    pub(crate) proxy:   &'a EventProxy<UserEvent>,
+    pub(crate) windows: &'a mut HashMap<WindowId, OpenWindow>,
+    pub(crate) primary: WindowId,
+}
+
+impl<'a, UserEvent> Windows<'a, UserEvent>
+where
+    UserEvent: 'static,
+{
+    /// Opens a window and starts presenting to it with the renderer's device.
+    pub fn open(
+        &mut self,
+        renderer: &mut Renderer,
+        title: &str,
+        settings: &WindowSettings,
+    ) -> Result<WindowId, EngineError> {
+        let open_window = settings.build(title, self.target)?;
+        let id = open_window.window.id();
+
+        renderer.add_window(&open_window.window)?;
+        self.windows.insert(id, open_window);
+
+        Ok(id)
+    }
+
+    /// Closes a window opened with `open`. The primary window stays open until the app exits.
+    pub fn close(&mut self, renderer: &mut Renderer, window: WindowId) {
+        assert_ne!(window, self.primary, "The primary window can't be closed");
+
+        renderer.remove_window(window);
+        self.windows.remove(&window);
+    }
+
+    /// The window the engine was created with.
+    pub fn primary(&self) -> WindowId {
+        self.primary
+    }
+
+    pub fn get(&self, window: WindowId) -> Option<&Window> {
+        self.windows.get(&window).map(|open_window| &open_window.window)
+    }
+
+    /// Creates a proxy for sending user events to the app from other threads.
+    pub fn create_proxy(&self) -> EventProxy<UserEvent> {
+        self.proxy.clone()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
+    }
+
+    /// The window's current settings, e.g. to be saved and restored on the next run. The size and
+    /// position are the ones it last had while windowed, so a window that is minimised, maximised
+    /// or fullscreen comes back at its normal size.
+    pub fn settings(&self, window: WindowId) -> WindowSettings {
+        let OpenWindow { window, settings } = self.open_window(window);
+
+        WindowSettings {
+            maximised: window.is_maximized(),
+            ..settings.clone()
+        }
+    }
+
+    /// Keeps the settings' size and position up to date while the window is windowed and neither
+    /// minimised nor maximised.
+    pub(crate) fn track(&mut self, window: WindowId, event: &WindowEvent) {
+        let OpenWindow { window, settings } = self.open_window_mut(window);
+        let size = window.inner_size();
+        let scale_factor = window.scale_factor();
+
+        let normal = settings.fullscreen == FullscreenMode::Windowed
+            && !window.is_maximized()
+            && size.width > 0
+            && size.height > 0;
+
+        if !normal {
+            return;
+        }
+
+        match event {
+            WindowEvent::Resized(new_size) if new_size.width > 0 && new_size.height > 0 => {
+                settings.size = new_size.to_logical(scale_factor);
+            },
+            WindowEvent::Moved(position) => {
+                settings.position = Some(position.to_logical(scale_factor));
+            },
+            _ => {},
+        }
+    }
+
+    /// Applies all of the settings to the window at once.
+    pub fn apply(&mut self, window: WindowId, settings: &WindowSettings) {
+        self.set_fullscreen(window, settings.fullscreen);
+        self.set_resizable(window, settings.resizable);
+        self.set_min_size(window, settings.min_size);
+        self.set_size(window, settings.size);
+
+        if let Some(position) = settings.position {
+            self.set_position(window, position);
+        }
+
+        self.set_maximised(window, settings.maximised);
+    }
+
+    /// The ratio of physical to logical pixels of the monitor the window is on.
+    pub fn scale_factor(&self, window: WindowId) -> f64 {
+        self.open_window(window).window.scale_factor()
+    }
+
+    /// The size of the window's contents in logical pixels.
+    pub fn size(&self, window: WindowId) -> LogicalSize<f64> {
+        let window = &self.open_window(window).window;
+
+        window.inner_size().to_logical(window.scale_factor())
+    }
+
+    pub fn set_title(&mut self, window: WindowId, title: &str) {
+        self.open_window(window).window.set_title(title);
+    }
+
+    pub fn set_size(&mut self, window: WindowId, size: LogicalSize<f64>) {
+        let open_window = self.open_window_mut(window);
+
+        open_window.window.set_inner_size(size);
+        open_window.settings.size = size;
+    }
+
+    pub fn set_position(&mut self, window: WindowId, position: LogicalPosition<f64>) {
+        let open_window = self.open_window_mut(window);
+
+        open_window.window.set_outer_position(position);
+        open_window.settings.position = Some(position);
+    }
+
+    pub fn set_min_size(&mut self, window: WindowId, min_size: Option<LogicalSize<f64>>) {
+        let open_window = self.open_window_mut(window);
+
+        open_window.window.set_min_inner_size(min_size);
+        open_window.settings.min_size = min_size;
+    }
+
+    pub fn set_resizable(&mut self, window: WindowId, resizable: bool) {
+        let open_window = self.open_window_mut(window);
+
+        open_window.window.set_resizable(resizable);
+        open_window.settings.resizable = resizable;
+    }
+
+    pub fn set_maximised(&mut self, window: WindowId, maximised: bool) {
+        let open_window = self.open_window_mut(window);
+
+        open_window.window.set_maximized(maximised);
+        open_window.settings.maximised = maximised;
+    }
+
+    /// Switches the window between windowed and fullscreen on the monitor it's currently on.
+    pub fn set_fullscreen(&mut self, window: WindowId, mode: FullscreenMode) {
+        let open_window = self.open_window_mut(window);
+        let monitor = open_window.window.current_monitor();
+
+        open_window.window.set_fullscreen(fullscreen(mode, monitor));
+        open_window.settings.fullscreen = mode;
+    }
+
+    /// The video modes of the monitor the window is on, largest and fastest first.
+    pub fn video_modes(&self, window: WindowId) -> Vec<VideoModeInfo> {
+        let mut video_modes = self
+            .open_window(window)
+            .window
+            .current_monitor()
+            .map(|monitor| {
+                monitor
+                    .video_modes()
+                    .map(|mode| VideoModeInfo::of(&mode))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        video_modes.sort_by_key(|mode| {
+            (mode.width * mode.height, mode.refresh_rate, mode.bit_depth)
+        });
+        video_modes.reverse();
+        video_modes.dedup();
+
+        video_modes
+    }
+
+    /// Sets the icon shown in the window's title bar and the taskbar from raw, tightly packed RGBA8
+    /// texels. Encoded images such as PNGs have to be decoded first.
+    pub fn set_icon_rgba(
+        &mut self,
+        window: WindowId,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), EngineError> {
+        let icon = Icon::from_rgba(rgba.to_vec(), width, height)?;
+
+        self.open_window(window).window.set_window_icon(Some(icon));
+
+        Ok(())
+    }
+
+    /// Restores the platform's default icon.
+    pub fn clear_icon(&mut self, window: WindowId) {
+        self.open_window(window).window.set_window_icon(None);
+    }
+
+    pub fn set_cursor(&mut self, window: WindowId, cursor: CursorIcon) {
+        self.open_window(window).window.set_cursor_icon(cursor);
+    }
+
+    pub fn set_cursor_visible(&mut self, window: WindowId, visible: bool) {
+        self.open_window(window).window.set_cursor_visible(visible);
+    }
+
+    /// Confines the cursor to the window, e.g. for mouse look.
+    pub fn set_cursor_grab(&mut self, window: WindowId, grab: bool) -> Result<(), EngineError> {
+        self.open_window(window).window.set_cursor_grab(grab)?;
+
+        Ok(())
+    }
+
+    fn open_window(&self, window: WindowId) -> &OpenWindow {
+        self.windows.get(&window).expect("Unknown window")
+    }
+
+    fn open_window_mut(&mut self, window: WindowId) -> &mut OpenWindow {
+        self.windows.get_mut(&window).expect("Unknown window")
+    }
+}
+
+fn fullscreen(mode: FullscreenMode, monitor: Option<MonitorHandle>) -> Option<Fullscreen> {
+    match mode {
+        FullscreenMode::Windowed => None,
+        FullscreenMode::Borderless => Some(Fullscreen::Borderless(monitor)),
+        FullscreenMode::Exclusive(requested) => {
+            let closest = monitor.and_then(|monitor| {
+                monitor
+                    .video_modes()
+                    .min_by_key(|mode| requested.distance(&VideoModeInfo::of(mode)))
+            });
+
+            match closest {
+                Some(video_mode) => Some(Fullscreen::Exclusive(video_mode)),
+                None => {
+                    log::warn!("No video modes available, using borderless fullscreen instead");
+                    Some(Fullscreen::Borderless(None))
+                },
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn video_mode(width: u32, height: u32, refresh_rate: u16) -> VideoModeInfo {
+        VideoModeInfo {
+            width,
+            height,
+            refresh_rate,
+            bit_depth: 32,
+        }
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let path = env::temp_dir().join(format!("velocity-window-{}.json", std::process::id()));
+        let settings = WindowSettings {
+            position: Some(LogicalPosition::new(100.0, -20.0)),
+            min_size: Some(LogicalSize::new(320.0, 240.0)),
+            resizable: false,
+            maximised: true,
+            fullscreen: FullscreenMode::Exclusive(video_mode(1920, 1080, 144)),
+            ..WindowSettings::new(1280.0, 720.0)
+        };
+
+        settings.save(&path).unwrap();
+        let loaded = WindowSettings::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), settings);
+    }
+
+    #[test]
+    fn loading_reports_errors() {
+        let path = env::temp_dir().join(format!("velocity-invalid-{}.json", std::process::id()));
+
+        assert!(matches!(WindowSettings::load(&path), Err(WindowSettingsError::Io(_))));
+
+        fs::write(&path, "{ \"size\": 7 }").unwrap();
+        let loaded = WindowSettings::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(loaded, Err(WindowSettingsError::Format(_))));
+    }
+
+    #[test]
+    fn closest_video_modes() {
+        let requested = video_mode(1920, 1080, 60);
+        let modes = [
+            video_mode(2560, 1440, 60),
+            video_mode(1920, 1080, 144),
+            video_mode(1920, 1080, 75),
+            video_mode(1600, 900, 60),
+        ];
+
+        let closest = modes
+            .iter()
+            .min_by_key(|mode| requested.distance(mode))
+            .unwrap();
+
+        // The resolution matters more than the refresh rate.
+        assert_eq!(*closest, video_mode(1920, 1080, 75));
+        assert_eq!(requested.distance(&requested), (0, 0, 0));
+        assert_eq!(requested.distance(&modes[3]), (500, 0, 0));
+        assert_eq!(requested.distance(&modes[0]), modes[0].distance(&requested));
+    }
+}